@@ -7,7 +7,7 @@
 //!
 //! ### Light-weight
 //!
-//! It does not rely on dependencies.
+//! It does not rely on dependencies. Integrations with other crates are opt-in through the Cargo features listed below.
 //!
 //! ### Transparent, Semantic API
 //!
@@ -40,12 +40,12 @@
 //! **Note the following:**
 //!
 //! - Without calling [`UInt256Builder::with_endian`] this operation will panic. There is no assumption of a default [endianness](https://dev.to/pancy/what-are-big-and-little-endians-91h).
-//! You are responsible to learn about and configure it. There is no shortcut to not understand endianness and able to "run it".
+//!   You are responsible to learn about and configure it. There is no shortcut to not understand endianness and able to "run it".
 //! - [`UInt256Builder::from_bytes`] takes a bytes array of exactly 32 bytes, not vectors. Fixed-size bytes array ensure correctness of data.
-//! But more importantly, it ensures the user actually understand bytes data. Vectors would have completely glazed over this for the sake of friendliness.
-//! But real friends expect the best from you and put you on the spot, not letting you off easy so you can have a beer early.
+//!   But more importantly, it ensures the user actually understand bytes data. Vectors would have completely glazed over this for the sake of friendliness.
+//!   But real friends expect the best from you and put you on the spot, not letting you off easy so you can have a beer early.
 //! - If you want to pass a vector, you will be required to call [`UInt256Builder::with_padding`] to pad the "missing space" of the bytes vector provided.
-//! This check once again check the user’s understanding of what he’s doing.
+//!   This check once again check the user’s understanding of what he’s doing.
 //!
 //! Check out the following example.
 //!
@@ -79,9 +79,52 @@
 //!
 //! However, it is really hard to miss what one is doing when they are required to call [`UInt256Builder::with_padding`].
 //!
+//! ## Optional features
+//!
+//! - `borsh`: [`borsh`](https://docs.rs/borsh) serialization of [`UInt256`] as 32 little-endian bytes.
+//! - `cbor`: `UInt256::to_cbor` and `UInt256::from_cbor` for CBOR (RFC 8949) unsigned integers and
+//!   tag 2 bignums, with the `CborError` type.
+//! - `ct`: constant-time [`MontgomeryContext::pow`] and the [`subtle`](https://docs.rs/subtle) traits.
+//!   See the [`Choice`] documentation for which APIs are constant time.
+//! - `diesel`: [`diesel`](https://docs.rs/diesel) `AsExpression`, `FromSqlRow`, `ToSql` and `FromSql` for
+//!   [`UInt256`] as the `Numeric` or `Binary` SQL types of the Postgres backend.
+//! - `ethnum`: conversions between [`UInt256`] and [`ethnum::U256`](https://docs.rs/ethnum).
+//! - `num-bigint`: conversions between [`UInt256`] and the `BigUint` and `BigInt` types of
+//!   [`num-bigint`](https://docs.rs/num-bigint).
+//! - `postgres`: [`postgres-types`](https://docs.rs/postgres-types) `ToSql` and `FromSql` for [`UInt256`]
+//!   as `NUMERIC` or as 32 big-endian bytes in `BYTEA`, failing on read for numbers out of range.
+//! - `primitive-types`: conversions between [`UInt256`] and
+//!   [`primitive_types::U256`](https://docs.rs/primitive-types).
+//! - `prost`: `ProtoUInt256`, a [`prost`](https://docs.rs/prost) message holding a [`UInt256`] in a
+//!   32-byte `bytes` field.
+//! - `rand`: random prime generation with `UInt256::random_prime`.
+//! - `rkyv`: [`rkyv`](https://docs.rs/rkyv) archiving of [`UInt256`] as 32 little-endian bytes.
+//! - `ruint`: conversions between [`UInt256`] and [`ruint::Uint<256, 4>`](https://docs.rs/ruint).
+//! - `scale`: the SCALE codec of [`parity-scale-codec`](https://docs.rs/parity-scale-codec) and
+//!   [`scale-info`](https://docs.rs/scale-info) type information, as 32 little-endian bytes.
+//! - `serde`: [`serde`](https://docs.rs/serde) `Serialize` and `Deserialize` for [`UInt256`] and [`Endian`].
+//!   Values are `"0x…"` strings in human-readable formats and 32 bytes in binary ones, and the
+//!   `serde_hex`, `serde_quantity`, `serde_dec` and `serde_bytes` modules pick one encoding for a field.
+//! - `sqlx`: [`sqlx`](https://docs.rs/sqlx) `Type`, `Encode` and `Decode` for [`UInt256`] with Postgres,
+//!   as `NUMERIC` or `BYTEA`, and SQLite, as decimal `TEXT` or big-endian `BLOB`.
+//! - `ssz`: Ethereum SSZ encoding with [`ethereum_ssz`](https://docs.rs/ethereum_ssz) as 32 little-endian bytes,
+//!   and `UInt256::hash_tree_root`.
+//! - `zeroize`: [`zeroize::Zeroize`](https://docs.rs/zeroize) for [`UInt256`], and wiping the buffer of
+//!   [`UInt256Builder`] when it is dropped.
+//!
 //! ## License
 //! MIT
 //!
 pub mod uint256;
 
-pub use uint256::{UInt256, UInt256Builder, Endian};
\ No newline at end of file
+pub use uint256::{
+    hex, moduli, UInt256, UInt256Builder, Endian, BarrettReducer, Base32Alphabet, Base64Alphabet, Base64Config, BitIter,
+    ByteView, Choice, ConversionError, Fp, MontgomeryContext, Ones, ParseErrorKind, ParseUInt256Error,
+    PreparedDivisor, RlpError, Sign,
+};
+#[cfg(feature = "cbor")]
+pub use uint256::CborError;
+#[cfg(feature = "prost")]
+pub use uint256::ProtoUInt256;
+#[cfg(feature = "serde")]
+pub use uint256::{serde_bytes, serde_dec, serde_hex, serde_quantity};
\ No newline at end of file
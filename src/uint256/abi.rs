@@ -0,0 +1,136 @@
+//! Solidity ABI encoding of [`UInt256`] values.
+//!
+//! In the standard encoding every static value takes one 32-byte big-endian word, so a
+//! call to `transfer(address,uint256)` is the 4-byte selector followed by two words. The
+//! packed encoding of `abi.encodePacked` instead writes a `uintN` in its own `N / 8` bytes,
+//! either at a given width or at the narrowest one that holds the value.
+
+use super::*;
+
+impl UInt256 {
+    /// Returns the value as a 32-byte ABI word, its big-endian bytes.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// // transfer(address,uint256) of 1000 units to 0x00…01.
+    /// let mut calldata = vec![0xa9, 0x05, 0x9c, 0xbb];
+    /// calldata.extend(UInt256::ONE.abi_encode());
+    /// calldata.extend(UInt256::from(1000).abi_encode());
+    /// assert_eq!(calldata.len(), 68);
+    /// assert_eq!(calldata[66..], [0x03, 0xe8]);
+    /// ```
+    pub fn abi_encode(&self) -> [u8; 32] {
+        self.to_be_array()
+    }
+
+    /// Decodes a `uint256` from a 32-byte ABI word. Every word is a valid `uint256`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let word = UInt256::from(1000).abi_encode();
+    /// assert_eq!(UInt256::abi_decode(&word), UInt256::from(1000));
+    /// ```
+    pub fn abi_decode(word: &[u8; 32]) -> Self {
+        UInt256::from_be_bytes(word)
+    }
+
+    /// Returns the packed encoding of the value in the narrowest Solidity `uintN` that holds
+    /// it: the big-endian bytes without leading zeros, and a single zero byte for zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(0x1234).abi_encode_packed(), [0x12, 0x34]);
+    /// assert_eq!(UInt256::ZERO.abi_encode_packed(), [0x00]);
+    /// ```
+    pub fn abi_encode_packed(&self) -> Vec<u8> {
+        self.to_be_array()[32 - self.byte_len().max(1)..].to_vec()
+    }
+
+    /// Returns the packed encoding of the value as a Solidity `uint<bits>`, the
+    /// `bits / 8` lowest big-endian bytes, as `abi.encodePacked` writes it.
+    ///
+    /// The width comes from the Solidity type rather than the value, so a `uint256` is
+    /// always 32 bytes, even when it is small. Fails with [`ConversionError::Overflow`] if
+    /// the value does not fit in `bits` bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not a multiple of 8 between 8 and 256.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ConversionError, UInt256};
+    ///
+    /// let value = UInt256::from(0x1234);
+    /// assert_eq!(value.abi_encode_packed_uint(16), Ok(vec![0x12, 0x34]));
+    /// assert_eq!(value.abi_encode_packed_uint(256).unwrap().len(), 32);
+    /// assert_eq!(value.abi_encode_packed_uint(8), Err(ConversionError::Overflow));
+    /// ```
+    pub fn abi_encode_packed_uint(&self, bits: u32) -> Result<Vec<u8>, ConversionError> {
+        assert!(bits.is_multiple_of(8) && (8..=256).contains(&bits), "bits must be a multiple of 8 between 8 and 256");
+        let len = bits as usize / 8;
+        if self.byte_len() > len {
+            return Err(ConversionError::Overflow);
+        }
+        Ok(self.to_be_array()[32 - len..].to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_abi_word() {
+        let mut word = [0u8; 32];
+        word[31] = 1;
+        assert_eq!(UInt256::ONE.abi_encode(), word);
+        assert_eq!(UInt256::abi_decode(&word), UInt256::ONE);
+        assert_eq!(UInt256::MAX.abi_encode(), [0xff; 32]);
+        assert_eq!(UInt256::abi_decode(&[0xff; 32]), UInt256::MAX);
+
+        let value = UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Little);
+        assert_eq!(UInt256::abi_decode(&value.abi_encode()), value);
+    }
+
+    #[test]
+    fn test_abi_encode_packed() {
+        assert_eq!(UInt256::ZERO.abi_encode_packed(), [0]);
+        assert_eq!(UInt256::from(0xff).abi_encode_packed(), [0xff]);
+        assert_eq!(UInt256::from(0x100).abi_encode_packed(), [1, 0]);
+        assert_eq!(UInt256::MAX.abi_encode_packed(), [0xff; 32]);
+        for len in 1..=32 {
+            let value = UInt256::MAX >> (256 - 8 * len);
+            let bits = 8 * len as u32;
+            assert_eq!(Ok(value.abi_encode_packed()), value.abi_encode_packed_uint(bits));
+        }
+    }
+
+    #[test]
+    fn test_abi_encode_packed_uint() {
+        assert_eq!(UInt256::ZERO.abi_encode_packed_uint(8), Ok(vec![0]));
+        assert_eq!(UInt256::from(0xff).abi_encode_packed_uint(8), Ok(vec![0xff]));
+        assert_eq!(UInt256::from(0x100).abi_encode_packed_uint(8), Err(ConversionError::Overflow));
+        assert_eq!(UInt256::from(0x100).abi_encode_packed_uint(24), Ok(vec![0, 1, 0]));
+        assert_eq!(UInt256::ONE.abi_encode_packed_uint(256), Ok(UInt256::ONE.abi_encode().to_vec()));
+        assert_eq!(UInt256::MAX.abi_encode_packed_uint(256), Ok(vec![0xff; 32]));
+        assert_eq!(UInt256::MAX.abi_encode_packed_uint(248), Err(ConversionError::Overflow));
+    }
+
+    #[test]
+    #[should_panic(expected = "bits must be a multiple of 8 between 8 and 256")]
+    fn test_abi_encode_packed_uint_invalid_bits() {
+        let _ = UInt256::ONE.abi_encode_packed_uint(12);
+    }
+}
@@ -0,0 +1,236 @@
+//! Barrett reduction for repeated arithmetic modulo a fixed modulus.
+
+use super::division::div_rem_limbs;
+use super::*;
+
+/// Precomputed values for Barrett reduction modulo a fixed modulus.
+///
+/// Unlike [`MontgomeryContext`], the modulus may be even and values stay in their
+/// normal form. Reduction replaces the 512-bit division with two multiplications by
+/// the precomputed reciprocal `⌊b^2k / m⌋`, where `b = 2^64` and `k` is the number of
+/// 64-bit limbs of the modulus `m` (so it is `⌊2^512 / m⌋` for full-width moduli).
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::{BarrettReducer, UInt256};
+///
+/// let m = UInt256::from(1_000_000);
+/// let reducer = BarrettReducer::new(m);
+/// let a = UInt256::MAX;
+/// assert_eq!(reducer.mul_mod(a, a), a.mod_mul(a, m));
+/// assert_eq!(reducer.reduce(a.widening_mul(a)), a.mod_mul(a, m));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BarrettReducer {
+    modulus: UInt256,
+    /// The number of significant limbs of the modulus.
+    k: usize,
+    /// `⌊(b^2k - 1) / m⌋`, at most `k + 1` limbs.
+    mu: [u64; 5],
+}
+
+impl BarrettReducer {
+    /// Prepare a reducer for the given modulus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    pub fn new(modulus: UInt256) -> Self {
+        if modulus.is_zero() {
+            panic!("division by zero");
+        }
+        let m = modulus.to_limbs();
+        let k = m.iter().rposition(|&limb| limb != 0).map_or(0, |i| i + 1);
+
+        // b^2k itself needs one limb more than `div_rem_limbs` accepts, so divide
+        // b^2k - 1 instead. The result is at most one less than the exact reciprocal,
+        // which only costs one more correction step in `reduce_window`.
+        let numerator = [u64::MAX; 8];
+        let (q, _) = div_rem_limbs(&numerator[..2 * k], &m);
+        let mut mu = [0u64; 5];
+        mu[..=k].copy_from_slice(&q[..=k]);
+
+        BarrettReducer { modulus, k, mu }
+    }
+
+    /// Returns the modulus of this reducer.
+    pub fn modulus(&self) -> UInt256 {
+        self.modulus
+    }
+
+    /// Reduces the 512-bit value given as a `(low, high)` pair, as returned by
+    /// [`UInt256::widening_mul`], modulo the modulus.
+    pub fn reduce(&self, (low, high): (UInt256, UInt256)) -> UInt256 {
+        let mut x = [0u64; 8];
+        x[..4].copy_from_slice(&low.to_limbs());
+        x[4..].copy_from_slice(&high.to_limbs());
+
+        // Fold the value in from the most significant end, `k` limbs at a time, so
+        // that each window is below `m * b^k <= b^2k` as Barrett reduction requires.
+        let k = self.k;
+        let mut r = [0u64; 4];
+        let mut end = 8usize;
+        while end > 0 {
+            let start = end.saturating_sub(k);
+            let mut window = [0u64; 8];
+            let width = end - start;
+            window[..width].copy_from_slice(&x[start..end]);
+            window[width..width + k].copy_from_slice(&r[..k]);
+            r = self.reduce_window(&window[..width + k]);
+            end = start;
+        }
+
+        UInt256::from_limbs_with(r, low.endian)
+    }
+
+    /// Calculates `(a * b) % modulus`.
+    pub fn mul_mod(&self, a: UInt256, b: UInt256) -> UInt256 {
+        self.reduce(a.widening_mul(b))
+    }
+
+    /// Reduces a value `x < b^2k` (HAC Algorithm 14.42).
+    fn reduce_window(&self, x: &[u64]) -> [u64; 4] {
+        let k = self.k;
+        let m = self.modulus.to_limbs();
+
+        // q = ⌊⌊x / b^(k-1)⌋ * mu / b^(k+1)⌋ underestimates ⌊x / m⌋ by a small amount.
+        let q1 = &x[k - 1..];
+        let mut q2 = [0u64; 16];
+        mul_limbs(q1, &self.mu[..=k], &mut q2);
+        let q3 = &q2[k + 1..2 * k + 2];
+
+        // r = (x - q * m) mod b^(k+1)
+        let mut qm = [0u64; 16];
+        mul_limbs(q3, &m[..k], &mut qm);
+        let mut r = [0u64; 5];
+        let width = x.len().min(k + 1);
+        r[..width].copy_from_slice(&x[..width]);
+        sub_limbs(&mut r[..=k], &qm[..=k]);
+
+        // q is at most three below ⌊x / m⌋ (two from Barrett's estimate and one from the
+        // rounded-down mu), so r < 4m. Always make three masked subtractions so the
+        // running time does not depend on x.
+        let mut m_wide = [0u64; 5];
+        m_wide[..k].copy_from_slice(&m[..k]);
+        for _ in 0..3 {
+            sub_if_not_less(&mut r[..=k], &m_wide[..=k]);
+        }
+
+        [r[0], r[1], r[2], r[3]]
+    }
+}
+
+/// Writes the product of the little-endian limbs `a` and `b` into `out`, which must
+/// be zeroed and at least `a.len() + b.len()` limbs long.
+fn mul_limbs(a: &[u64], b: &[u64], out: &mut [u64]) {
+    for (i, &a_i) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &b_j) in b.iter().enumerate() {
+            let t = a_i as u128 * b_j as u128 + out[i + j] as u128 + carry;
+            out[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        out[i + b.len()] = carry as u64;
+    }
+}
+
+/// Subtracts `b` from `a` in place, wrapping around on underflow, and returns whether
+/// it underflowed.
+fn sub_limbs(a: &mut [u64], b: &[u64]) -> bool {
+    let mut borrow = false;
+    for (a_i, &b_i) in a.iter_mut().zip(b) {
+        let (d, b1) = a_i.overflowing_sub(b_i);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        *a_i = d;
+        borrow = b1 | b2;
+    }
+    borrow
+}
+
+/// Subtracts `b` from `a` in place if `a >= b`, without branching on the values.
+fn sub_if_not_less(a: &mut [u64], b: &[u64]) {
+    let mut difference = [0u64; 5];
+    let difference = &mut difference[..a.len()];
+    difference.copy_from_slice(a);
+    let borrow = sub_limbs(difference, b);
+    // All ones if the subtraction did not borrow, i.e. if a >= b.
+    let mask = (borrow as u64).wrapping_sub(1);
+    for (a_i, d_i) in a.iter_mut().zip(difference.iter()) {
+        *a_i ^= mask & (*a_i ^ d_i);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::super::division::div_rem_wide;
+    use super::*;
+    use crate::uint256::test_utils::xorshift;
+
+    #[test]
+    fn test_barrett_matches_mod_mul() {
+        let moduli = [
+            UInt256::from(2),
+            UInt256::from(1_000_000),
+            UInt256::new(0, 1 << 64, Endian::Big),
+            UInt256::new(0, u128::MAX, Endian::Big),
+            UInt256::new(1 << 100, 12345, Endian::Big),
+            UInt256::new(1 << 127, 0, Endian::Big),
+            UInt256::MAX - UInt256::ONE,
+            UInt256::MAX,
+        ];
+        let values = [
+            UInt256::ZERO,
+            UInt256::from(7),
+            UInt256::new(0xdeadbeef, 0xcafebabe, Endian::Big),
+            UInt256::MAX - UInt256::from(5),
+            UInt256::MAX,
+        ];
+        for m in moduli {
+            let reducer = BarrettReducer::new(m);
+            assert_eq!(reducer.modulus(), m);
+            for a in values {
+                for b in values {
+                    assert_eq!(reducer.mul_mod(a, b), a.mod_mul(b, m), "{} * {} mod {}", a, b, m);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_barrett_reduce_random() {
+        // Compare against the division routine with xorshift-generated operands, for
+        // moduli of every width, including ones with small top limbs.
+        let mut next = xorshift(0x9e3779b97f4a7c15);
+        for round in 0..200 {
+            let mut m = [next(), next(), next(), next()];
+            for limb in m.iter_mut().skip(round % 4 + 1) {
+                *limb = 0;
+            }
+            m[round % 4] >>= round % 64;
+            let m = UInt256::from_limbs_with(m, Endian::Big);
+            if m.is_zero() {
+                continue;
+            }
+            let low = UInt256::from_limbs_with([next(), next(), next(), next()], Endian::Big);
+            let high = UInt256::from_limbs_with([next(), next(), next(), next()], Endian::Big);
+            let reducer = BarrettReducer::new(m);
+            assert_eq!(reducer.reduce((low, high)), div_rem_wide(low, high, m).1);
+        }
+    }
+
+    #[test]
+    fn test_barrett_reduce() {
+        let m = UInt256::from(97);
+        let reducer = BarrettReducer::new(m);
+        assert_eq!(reducer.reduce((UInt256::from(1_000), UInt256::ZERO)), UInt256::from(30));
+        assert_eq!(reducer.reduce((UInt256::ZERO, UInt256::ONE)), UInt256::MAX.mod_add(UInt256::ONE, m));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_barrett_zero_modulus() {
+        let _ = BarrettReducer::new(UInt256::ZERO);
+    }
+}
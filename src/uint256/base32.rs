@@ -0,0 +1,203 @@
+//! Base32 encoding of [`UInt256`] values, with the RFC 4648 and Crockford alphabets.
+
+use super::*;
+
+/// A Base32 alphabet, and with it how the 256 bits are laid out in 52 digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Base32Alphabet {
+    /// RFC 4648 Base32 of the 32 big-endian bytes, so the last digit holds the lowest
+    /// bit followed by four zero bits. With `padding`, `====` makes a multiple of eight
+    /// characters.
+    Rfc4648 { padding: bool },
+    /// Crockford's Base32 of the number, so the first digit holds only the top bit.
+    /// It has no `I`, `L`, `O` or `U`, which makes it easy to read out and type.
+    Crockford,
+}
+
+const RFC4648: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// The number of digits for 256 bits, without padding.
+const DIGITS: usize = 52;
+
+impl UInt256 {
+    /// Encodes the value in Base32 with `alphabet`, always as 52 digits.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Base32Alphabet, UInt256};
+    ///
+    /// let value = UInt256::from(0x3ff);
+    /// let rfc = value.to_base32(Base32Alphabet::Rfc4648 { padding: true });
+    /// assert_eq!(rfc, format!("{}P7Q====", "A".repeat(49)));
+    /// assert_eq!(value.to_base32(Base32Alphabet::Crockford), format!("{}ZZ", "0".repeat(50)));
+    /// ```
+    pub fn to_base32(&self, alphabet: Base32Alphabet) -> String {
+        let (digits, low_bits) = match alphabet {
+            Base32Alphabet::Rfc4648 { .. } => (RFC4648, 4),
+            Base32Alphabet::Crockford => (CROCKFORD, 0),
+        };
+        // The digits are those of `self << low_bits`, which takes 260 bits.
+        let mut encoded = String::with_capacity(DIGITS + 4);
+        for i in (0..DIGITS as u32).rev() {
+            let digit = match (5 * i).checked_sub(low_bits) {
+                Some(shift) => (*self >> shift).low as usize & 0x1f,
+                None => (self.low as usize) << (low_bits - 5 * i) & 0x1f,
+            };
+            encoded.push(digits[digit] as char);
+        }
+        if alphabet == (Base32Alphabet::Rfc4648 { padding: true }) {
+            encoded.push_str("====");
+        }
+        encoded
+    }
+
+    /// Decodes a Base32 string with `alphabet`, as produced by [`Self::to_base32`].
+    ///
+    /// RFC 4648 input must have exactly 52 digits, with the four unused bits of the last
+    /// one set to zero, and the `====` is optional whatever `padding` says. Lower-case
+    /// digits are accepted.
+    ///
+    /// Crockford input is read as a number, so leading zeros may be left out. As the
+    /// alphabet specifies, it is case-insensitive, `I` and `L` read as `1`, `O` reads as
+    /// `0`, and hyphens are ignored. Check symbols are not supported.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Base32Alphabet, ParseErrorKind, UInt256};
+    ///
+    /// let id = UInt256::from_base32("1ZZ-ZZZ", Base32Alphabet::Crockford).unwrap();
+    /// assert_eq!(id, UInt256::from(0x3ff_ffff));
+    /// assert_eq!(UInt256::from_base32("lzz-zzz", Base32Alphabet::Crockford), Ok(id));
+    ///
+    /// let err = UInt256::from_base32("AAAA", Base32Alphabet::Rfc4648 { padding: false }).unwrap_err();
+    /// assert_eq!(err.kind(), ParseErrorKind::InvalidLength);
+    /// ```
+    pub fn from_base32(s: &str, alphabet: Base32Alphabet) -> Result<Self, ParseUInt256Error> {
+        if s.is_empty() {
+            return Err(ParseUInt256Error::new(ParseErrorKind::Empty, 0));
+        }
+        match alphabet {
+            Base32Alphabet::Rfc4648 { .. } => from_rfc4648(s),
+            Base32Alphabet::Crockford => from_crockford(s),
+        }
+    }
+}
+
+fn from_rfc4648(s: &str) -> Result<UInt256, ParseUInt256Error> {
+    let digits = s.strip_suffix("====").unwrap_or(s);
+    let mut value = UInt256::ZERO;
+    for (i, b) in digits.bytes().enumerate() {
+        let digit = match b.to_ascii_uppercase() {
+            b @ b'A'..=b'Z' => b - b'A',
+            b @ b'2'..=b'7' => b - b'2' + 26,
+            _ => return Err(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, i)),
+        };
+        if i >= DIGITS {
+            return Err(ParseUInt256Error::new(ParseErrorKind::InvalidLength, DIGITS));
+        }
+        if i == DIGITS - 1 {
+            // Only the top bit of the last digit belongs to the value.
+            if digit & 0xf != 0 {
+                return Err(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, i));
+            }
+            value = value << 1u32 | UInt256::from(digit as usize >> 4);
+        } else {
+            value = value << 5u32 | UInt256::from(digit as usize);
+        }
+    }
+    if digits.len() < DIGITS {
+        return Err(ParseUInt256Error::new(ParseErrorKind::InvalidLength, digits.len()));
+    }
+    Ok(value)
+}
+
+fn from_crockford(s: &str) -> Result<UInt256, ParseUInt256Error> {
+    let mut value = UInt256::ZERO;
+    let mut digits = 0;
+    for (i, b) in s.bytes().enumerate() {
+        let digit = match b.to_ascii_uppercase() {
+            b'-' => continue,
+            b'O' => 0,
+            b'I' | b'L' => 1,
+            b => CROCKFORD
+                .iter()
+                .position(|&a| a == b)
+                .ok_or(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, i))?,
+        };
+        value = value
+            .checked_mul_add_u64(32, digit as u64)
+            .ok_or(ParseUInt256Error::new(ParseErrorKind::Overflow, i))?;
+        digits += 1;
+    }
+    if digits == 0 {
+        return Err(ParseUInt256Error::new(ParseErrorKind::Empty, 0));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{random_values, SEED};
+
+    const PADDED: Base32Alphabet = Base32Alphabet::Rfc4648 { padding: true };
+    const UNPADDED: Base32Alphabet = Base32Alphabet::Rfc4648 { padding: false };
+
+    #[test]
+    fn test_base32_vectors() {
+        assert_eq!(UInt256::ZERO.to_base32(UNPADDED), "A".repeat(52));
+        assert_eq!(UInt256::ONE.to_base32(PADDED), format!("{}Q====", "A".repeat(51)));
+        assert_eq!(UInt256::MAX.to_base32(PADDED), format!("{}Q====", "7".repeat(51)));
+        let value = UInt256::from_be_bytes(b"The quick brown fox jumps over!!");
+        assert_eq!(value.to_base32(UNPADDED), "KRUGKIDROVUWG2ZAMJZG653OEBTG66BANJ2W24DTEBXXMZLSEEQQ");
+
+        assert_eq!(UInt256::ZERO.to_base32(Base32Alphabet::Crockford), "0".repeat(52));
+        assert_eq!(UInt256::ONE.to_base32(Base32Alphabet::Crockford), format!("{}1", "0".repeat(51)));
+        assert_eq!(UInt256::MAX.to_base32(Base32Alphabet::Crockford), format!("1{}", "Z".repeat(51)));
+        let value = UInt256::from(0x0123_4567_89ab_cdef);
+        assert_eq!(value.to_base32(Base32Alphabet::Crockford), format!("{}28T5CY4TQKFF", "0".repeat(40)));
+    }
+
+    #[test]
+    fn test_base32_round_trip() {
+        for value in random_values(SEED, 200) {
+            for alphabet in [PADDED, UNPADDED, Base32Alphabet::Crockford] {
+                let encoded = value.to_base32(alphabet);
+                assert_eq!(UInt256::from_base32(&encoded, alphabet), Ok(value), "{}", encoded);
+                assert_eq!(UInt256::from_base32(&encoded.to_lowercase(), alphabet), Ok(value));
+            }
+            let crockford = value.to_base32(Base32Alphabet::Crockford);
+            let trimmed = crockford.trim_start_matches('0');
+            if !trimmed.is_empty() {
+                assert_eq!(UInt256::from_base32(trimmed, Base32Alphabet::Crockford), Ok(value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_base32_errors() {
+        let err = |kind, offset| Err(ParseUInt256Error::new(kind, offset));
+        let zero = "A".repeat(52);
+        assert_eq!(UInt256::from_base32("", PADDED), err(ParseErrorKind::Empty, 0));
+        assert_eq!(UInt256::from_base32("====", PADDED), err(ParseErrorKind::InvalidLength, 0));
+        assert_eq!(UInt256::from_base32(&zero[1..], PADDED), err(ParseErrorKind::InvalidLength, 51));
+        assert_eq!(UInt256::from_base32(&format!("{}A", zero), PADDED), err(ParseErrorKind::InvalidLength, 52));
+        assert_eq!(UInt256::from_base32(&format!("{}=", zero), PADDED), err(ParseErrorKind::InvalidDigit, 52));
+        assert_eq!(UInt256::from_base32(&format!("1{}", &zero[1..]), PADDED), err(ParseErrorKind::InvalidDigit, 0));
+        // The last digit carries four bits beyond the 256.
+        assert_eq!(UInt256::from_base32(&format!("{}B", &zero[1..]), PADDED), err(ParseErrorKind::InvalidDigit, 51));
+
+        let crockford = Base32Alphabet::Crockford;
+        assert_eq!(UInt256::from_base32("", crockford), err(ParseErrorKind::Empty, 0));
+        assert_eq!(UInt256::from_base32("--", crockford), err(ParseErrorKind::Empty, 0));
+        assert_eq!(UInt256::from_base32("1U", crockford), err(ParseErrorKind::InvalidDigit, 1));
+        assert_eq!(UInt256::from_base32("1 2", crockford), err(ParseErrorKind::InvalidDigit, 1));
+        let max = UInt256::MAX.to_base32(crockford);
+        assert_eq!(UInt256::from_base32(&format!("{}0", max), crockford), err(ParseErrorKind::Overflow, 52));
+        assert_eq!(UInt256::from_base32(&format!("2{}", &max[1..]), crockford), err(ParseErrorKind::Overflow, 51));
+    }
+}
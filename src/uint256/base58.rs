@@ -0,0 +1,123 @@
+//! Base58 encoding of [`UInt256`] values, with the Bitcoin alphabet.
+
+use super::format::alphabet_digits;
+use super::*;
+
+/// The Bitcoin Base58 alphabet, which leaves out `0`, `O`, `I` and `l`.
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The most Base58 digits a 256-bit value needs.
+const MAX_DIGITS: usize = 44;
+
+impl UInt256 {
+    /// Encodes the 32 big-endian bytes of the value in plain Base58, without a checksum.
+    ///
+    /// As in Bitcoin, each leading zero byte becomes a leading `1`, so the result is
+    /// what other Base58 encoders produce for [`Self::to_be_array`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let value = UInt256::MAX;
+    /// assert_eq!(value.to_base58(), "JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
+    /// assert_eq!(UInt256::from_base58(&value.to_base58()), Ok(value));
+    /// assert_eq!(UInt256::from(57).to_base58(), format!("{}z", "1".repeat(31)));
+    /// ```
+    pub fn to_base58(&self) -> String {
+        let zero_bytes = 32 - self.byte_len();
+        let mut encoded = "1".repeat(zero_bytes);
+        if !self.is_zero() {
+            let mut buf = [0u8; MAX_DIGITS];
+            encoded.push_str(alphabet_digits(*self, ALPHABET, &mut buf));
+        }
+        encoded
+    }
+
+    /// Decodes a plain Base58 string, as produced by [`Self::to_base58`].
+    ///
+    /// Leading `1`s stand for zero bytes and may be left out, but the bytes they stand
+    /// for and those of the value must fit in 32 bytes. Whitespace is not accepted.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ParseErrorKind, UInt256};
+    ///
+    /// assert_eq!(UInt256::from_base58("z"), Ok(UInt256::from(57)));
+    /// assert_eq!(UInt256::from_base58("11z"), Ok(UInt256::from(57)));
+    ///
+    /// let err = UInt256::from_base58("0z").unwrap_err();
+    /// assert_eq!(err.kind(), ParseErrorKind::InvalidDigit);
+    /// assert_eq!(err.offset(), 0);
+    /// ```
+    pub fn from_base58(s: &str) -> Result<Self, ParseUInt256Error> {
+        if s.is_empty() {
+            return Err(ParseUInt256Error::new(ParseErrorKind::Empty, 0));
+        }
+        let zero_bytes = s.bytes().take_while(|&b| b == b'1').count();
+        if zero_bytes > 32 {
+            return Err(ParseUInt256Error::new(ParseErrorKind::InvalidLength, 32));
+        }
+
+        let mut value = UInt256::ZERO;
+        for (i, b) in s.bytes().enumerate().skip(zero_bytes) {
+            let digit = ALPHABET
+                .iter()
+                .position(|&a| a == b)
+                .ok_or(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, i))?;
+            value = value
+                .checked_mul_add_u64(58, digit as u64)
+                .ok_or(ParseUInt256Error::new(ParseErrorKind::Overflow, i))?;
+        }
+        if zero_bytes + value.byte_len() > 32 {
+            return Err(ParseUInt256Error::new(ParseErrorKind::InvalidLength, zero_bytes));
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{random_values, SEED};
+
+    #[test]
+    fn test_base58_vectors() {
+        // The Bitcoin Base58 encodings of the same 32 bytes.
+        let zero = "1".repeat(32);
+        assert_eq!(UInt256::ZERO.to_base58(), zero);
+        assert_eq!(UInt256::ONE.to_base58(), format!("{}2", "1".repeat(31)));
+        assert_eq!(UInt256::from(58).to_base58(), format!("{}21", "1".repeat(31)));
+        assert_eq!(UInt256::from(0x0100).to_base58(), format!("{}5R", "1".repeat(30)));
+        assert_eq!(UInt256::MAX.to_base58(), "JEKNVnkbo3jma5nREBBJCDoXFVeKkD56V3xKrvRmWxFG");
+        // The highest bit alone needs no leading `1`.
+        assert_eq!((UInt256::ONE << 255u32).to_base58(), "9cfBkPsoQ2NPHYPi7b69bcQG8FKfNc33k2UfRxiPFyd9");
+    }
+
+    #[test]
+    fn test_base58_round_trip() {
+        for value in random_values(SEED, 200) {
+            let encoded = value.to_base58();
+            assert_eq!(UInt256::from_base58(&encoded), Ok(value), "{}", encoded);
+            assert_eq!(UInt256::from_base58(encoded.trim_start_matches('1')), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_base58_errors() {
+        let err = |kind, offset| Err(ParseUInt256Error::new(kind, offset));
+        assert_eq!(UInt256::from_base58(""), err(ParseErrorKind::Empty, 0));
+        assert_eq!(UInt256::from_base58("2O"), err(ParseErrorKind::InvalidDigit, 1));
+        assert_eq!(UInt256::from_base58("1l"), err(ParseErrorKind::InvalidDigit, 1));
+        assert_eq!(UInt256::from_base58("I"), err(ParseErrorKind::InvalidDigit, 0));
+        assert_eq!(UInt256::from_base58(" 2"), err(ParseErrorKind::InvalidDigit, 0));
+        assert_eq!(UInt256::from_base58(&"1".repeat(33)), err(ParseErrorKind::InvalidLength, 32));
+        // A leading `1` in front of a value that already takes 32 bytes.
+        let max = UInt256::MAX.to_base58();
+        assert_eq!(UInt256::from_base58(&format!("1{}", max)), err(ParseErrorKind::InvalidLength, 1));
+        assert_eq!(UInt256::from_base58(&format!("{}z", max)), err(ParseErrorKind::Overflow, 44));
+    }
+}
@@ -0,0 +1,177 @@
+//! Base64 encoding of the 32 big-endian bytes of [`UInt256`] values.
+
+use super::*;
+
+/// The characters used for the digits 62 and 63, which are the only ones that differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Base64Alphabet {
+    /// The standard alphabet of RFC 4648, ending in `+` and `/`.
+    Standard,
+    /// The URL and filename safe alphabet of RFC 4648, ending in `-` and `_`.
+    UrlSafe,
+}
+
+/// How [`UInt256::to_base64`] encodes a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Base64Config {
+    /// The alphabet of the digits.
+    pub alphabet: Base64Alphabet,
+    /// Whether the 43 digits are followed by a `=` to make a multiple of four characters.
+    pub padding: bool,
+}
+
+impl Base64Config {
+    /// The standard alphabet, with padding.
+    pub const STANDARD: Self = Base64Config { alphabet: Base64Alphabet::Standard, padding: true };
+    /// The standard alphabet, without padding.
+    pub const STANDARD_NO_PAD: Self = Base64Config { alphabet: Base64Alphabet::Standard, padding: false };
+    /// The URL-safe alphabet, with padding.
+    pub const URL_SAFE: Self = Base64Config { alphabet: Base64Alphabet::UrlSafe, padding: true };
+    /// The URL-safe alphabet, without padding, as used in JWTs.
+    pub const URL_SAFE_NO_PAD: Self = Base64Config { alphabet: Base64Alphabet::UrlSafe, padding: false };
+}
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// The number of digits for 32 bytes, without padding.
+const DIGITS: usize = 43;
+
+impl UInt256 {
+    /// Encodes the 32 big-endian bytes of the value in Base64.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Base64Config, UInt256};
+    ///
+    /// let value = UInt256::MAX - UInt256::from(0x410);
+    /// assert_eq!(value.to_base64(Base64Config::STANDARD), "////////////////////////////////////////++8=");
+    /// assert_eq!(value.to_base64(Base64Config::URL_SAFE_NO_PAD), "________________________________________--8");
+    /// ```
+    pub fn to_base64(&self, config: Base64Config) -> String {
+        let alphabet = match config.alphabet {
+            Base64Alphabet::Standard => STANDARD,
+            Base64Alphabet::UrlSafe => URL_SAFE,
+        };
+        let mut encoded = String::with_capacity(DIGITS + 1);
+        for group in self.to_be_array().chunks(3) {
+            // The last group has two bytes, so 18 bits of which the lowest two are zero.
+            let bits = group.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+            for i in 0..=group.len() {
+                encoded.push(alphabet[(bits >> (18 - 6 * i) & 0x3f) as usize] as char);
+            }
+        }
+        if config.padding {
+            encoded.push('=');
+        }
+        encoded
+    }
+
+    /// Decodes the 32 big-endian bytes of a value from Base64, as produced by
+    /// [`Self::to_base64`] with any configuration.
+    ///
+    /// Both alphabets are accepted, and the trailing `=` is optional. The input must
+    /// encode exactly 32 bytes, with the two unused bits of the last digit set to zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Base64Config, ParseErrorKind, UInt256};
+    ///
+    /// let value = UInt256::from(0xdead_beef);
+    /// for config in [Base64Config::STANDARD, Base64Config::URL_SAFE_NO_PAD] {
+    ///     assert_eq!(UInt256::from_base64(&value.to_base64(config)), Ok(value));
+    /// }
+    /// assert_eq!(UInt256::from_base64("AAAA").unwrap_err().kind(), ParseErrorKind::InvalidLength);
+    /// ```
+    pub fn from_base64(s: &str) -> Result<Self, ParseUInt256Error> {
+        if s.is_empty() {
+            return Err(ParseUInt256Error::new(ParseErrorKind::Empty, 0));
+        }
+        let digits = s.strip_suffix('=').unwrap_or(s);
+
+        let mut bits = [0u8; DIGITS];
+        for (i, b) in digits.bytes().enumerate() {
+            let digit = match b {
+                b'A'..=b'Z' => b - b'A',
+                b'a'..=b'z' => b - b'a' + 26,
+                b'0'..=b'9' => b - b'0' + 52,
+                b'+' | b'-' => 62,
+                b'/' | b'_' => 63,
+                _ => return Err(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, i)),
+            };
+            if i >= DIGITS {
+                return Err(ParseUInt256Error::new(ParseErrorKind::InvalidLength, DIGITS));
+            }
+            bits[i] = digit;
+        }
+        if digits.len() < DIGITS {
+            return Err(ParseUInt256Error::new(ParseErrorKind::InvalidLength, digits.len()));
+        }
+        if bits[DIGITS - 1] & 0b11 != 0 {
+            return Err(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, DIGITS - 1));
+        }
+
+        let mut bytes = [0u8; 32];
+        for (group, digits) in bytes.chunks_mut(3).zip(bits.chunks(4)) {
+            let bits = digits.iter().enumerate().fold(0u32, |acc, (i, &d)| acc | (d as u32) << (18 - 6 * i));
+            for (i, byte) in group.iter_mut().enumerate() {
+                *byte = (bits >> (16 - 8 * i)) as u8;
+            }
+        }
+        Ok(UInt256::from_be_bytes(&bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{random_values, SEED};
+
+    #[test]
+    fn test_base64_vectors() {
+        let zero = format!("{}=", "A".repeat(43));
+        assert_eq!(UInt256::ZERO.to_base64(Base64Config::STANDARD), zero);
+        assert_eq!(UInt256::ZERO.to_base64(Base64Config::STANDARD_NO_PAD), "A".repeat(43));
+        assert_eq!(UInt256::ONE.to_base64(Base64Config::STANDARD), format!("{}E=", "A".repeat(42)));
+        assert_eq!(UInt256::MAX.to_base64(Base64Config::URL_SAFE), format!("{}8=", "_".repeat(42)));
+        assert_eq!(UInt256::MAX.to_base64(Base64Config::STANDARD_NO_PAD), format!("{}8", "/".repeat(42)));
+
+        let value = UInt256::from_be_bytes(b"The quick brown fox jumps over!!");
+        assert_eq!(value.to_base64(Base64Config::STANDARD), "VGhlIHF1aWNrIGJyb3duIGZveCBqdW1wcyBvdmVyISE=");
+    }
+
+    #[test]
+    fn test_base64_round_trip() {
+        let configs = [
+            Base64Config::STANDARD,
+            Base64Config::STANDARD_NO_PAD,
+            Base64Config::URL_SAFE,
+            Base64Config::URL_SAFE_NO_PAD,
+        ];
+        for value in random_values(SEED, 100) {
+            for config in configs {
+                let encoded = value.to_base64(config);
+                assert_eq!(encoded.len(), if config.padding { 44 } else { 43 });
+                assert_eq!(UInt256::from_base64(&encoded), Ok(value), "{}", encoded);
+            }
+        }
+    }
+
+    #[test]
+    fn test_base64_errors() {
+        let err = |kind, offset| Err(ParseUInt256Error::new(kind, offset));
+        let zero = "A".repeat(43);
+        assert_eq!(UInt256::from_base64(""), err(ParseErrorKind::Empty, 0));
+        assert_eq!(UInt256::from_base64("="), err(ParseErrorKind::InvalidLength, 0));
+        assert_eq!(UInt256::from_base64(&zero[1..]), err(ParseErrorKind::InvalidLength, 42));
+        assert_eq!(UInt256::from_base64(&format!("{}A", zero)), err(ParseErrorKind::InvalidLength, 43));
+        assert_eq!(UInt256::from_base64(&format!("{}==", zero)), err(ParseErrorKind::InvalidDigit, 43));
+        assert_eq!(UInt256::from_base64(&format!("A.{}", &zero[2..])), err(ParseErrorKind::InvalidDigit, 1));
+        assert_eq!(UInt256::from_base64(&format!(" {}", &zero[1..])), err(ParseErrorKind::InvalidDigit, 0));
+        // The last digit carries two bits beyond the 256.
+        assert_eq!(UInt256::from_base64(&format!("{}B", &zero[1..])), err(ParseErrorKind::InvalidDigit, 42));
+    }
+}
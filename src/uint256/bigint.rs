@@ -0,0 +1,116 @@
+//! Conversions between [`UInt256`] and the arbitrary precision integers of
+//! [`num-bigint`](https://docs.rs/num-bigint), enabled by the `num-bigint` feature.
+
+use num_bigint::{BigInt, BigUint, Sign as BigSign};
+
+use super::*;
+
+impl From<UInt256> for BigUint {
+    fn from(value: UInt256) -> Self {
+        BigUint::from_bytes_le(&value.to_le_array())
+    }
+}
+
+impl From<UInt256> for BigInt {
+    fn from(value: UInt256) -> Self {
+        BigInt::from(BigUint::from(value))
+    }
+}
+
+/// Fails with [`ConversionError::Overflow`] for values of 2^256 and above.
+impl TryFrom<&BigUint> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(value: &BigUint) -> Result<Self, Self::Error> {
+        let digits = value.to_u64_digits();
+        if digits.len() > 4 {
+            return Err(ConversionError::Overflow);
+        }
+        let mut limbs = [0u64; 4];
+        limbs[..digits.len()].copy_from_slice(&digits);
+        Ok(UInt256::from_limbs(limbs))
+    }
+}
+
+/// Fails with [`ConversionError::Overflow`] for values of 2^256 and above.
+impl TryFrom<BigUint> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(value: BigUint) -> Result<Self, Self::Error> {
+        UInt256::try_from(&value)
+    }
+}
+
+/// Fails with [`ConversionError::Negative`] for negative values and with
+/// [`ConversionError::Overflow`] for values of 2^256 and above.
+///
+/// ## Examples
+///
+/// ```rust
+/// use num_bigint::BigInt;
+/// use uint256::{ConversionError, UInt256};
+///
+/// let big = BigInt::from(UInt256::MAX) * 3;
+/// assert_eq!(UInt256::try_from(&big / 3), Ok(UInt256::MAX));
+/// assert_eq!(UInt256::try_from(big), Err(ConversionError::Overflow));
+/// assert_eq!(UInt256::try_from(BigInt::from(-1)), Err(ConversionError::Negative));
+/// ```
+impl TryFrom<&BigInt> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(value: &BigInt) -> Result<Self, Self::Error> {
+        if value.sign() == BigSign::Minus {
+            return Err(ConversionError::Negative);
+        }
+        UInt256::try_from(value.magnitude())
+    }
+}
+
+/// Fails with [`ConversionError::Negative`] for negative values and with
+/// [`ConversionError::Overflow`] for values of 2^256 and above.
+impl TryFrom<BigInt> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(value: BigInt) -> Result<Self, Self::Error> {
+        UInt256::try_from(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_biguint_round_trip() {
+        let values = [
+            UInt256::ZERO,
+            UInt256::ONE,
+            UInt256::from(usize::MAX),
+            UInt256::new(1, 0, Endian::Big),
+            UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Little),
+            UInt256::MAX,
+        ];
+        for value in values {
+            let big = BigUint::from(value);
+            assert_eq!(big.to_str_radix(16), format!("{:x}", BigUint::from_bytes_be(&value.to_be_array())));
+            assert_eq!(UInt256::try_from(&big), Ok(value));
+            assert_eq!(UInt256::try_from(BigInt::from(value)), Ok(value));
+        }
+        assert_eq!(BigUint::from(UInt256::from(12345)), BigUint::from(12345u32));
+    }
+
+    #[test]
+    fn test_bigint_out_of_range() {
+        let two_256 = BigUint::from(UInt256::MAX) + 1u32;
+        assert_eq!(UInt256::try_from(&two_256), Err(ConversionError::Overflow));
+        assert_eq!(UInt256::try_from(BigInt::from(two_256)), Err(ConversionError::Overflow));
+        assert_eq!(UInt256::try_from(BigInt::from(-5)), Err(ConversionError::Negative));
+        assert_eq!(UInt256::try_from(BigInt::from(0)), Ok(UInt256::ZERO));
+
+        // Arithmetic beyond 256 bits, then back.
+        let product = BigUint::from(UInt256::MAX) * BigUint::from(UInt256::MAX);
+        let quotient = product / BigUint::from(UInt256::MAX);
+        assert_eq!(UInt256::try_from(quotient), Ok(UInt256::MAX));
+    }
+}
@@ -0,0 +1,1098 @@
+//! Bit counting and manipulation for [`UInt256`] values.
+
+use std::iter::{FusedIterator, Rev};
+use std::ops::{Bound, Index, RangeBounds};
+
+use super::*;
+
+impl UInt256 {
+    /// Returns the number of ones in the binary representation of `self`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(0b0100_1100).count_ones(), 3);
+    /// assert_eq!(UInt256::MAX.count_ones(), 256);
+    /// ```
+    pub const fn count_ones(self) -> u32 {
+        self.high.count_ones() + self.low.count_ones()
+    }
+
+    /// Returns the number of zeros in the binary representation of `self`.
+    pub const fn count_zeros(self) -> u32 {
+        self.high.count_zeros() + self.low.count_zeros()
+    }
+
+    /// Returns the number of leading zeros in the binary representation of `self`,
+    /// which is 256 for zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::ONE.leading_zeros(), 255);
+    /// assert_eq!(UInt256::ZERO.leading_zeros(), 256);
+    /// ```
+    pub const fn leading_zeros(self) -> u32 {
+        if self.high != 0 {
+            self.high.leading_zeros()
+        } else {
+            128 + self.low.leading_zeros()
+        }
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of `self`,
+    /// which is 256 for zero.
+    pub const fn trailing_zeros(self) -> u32 {
+        if self.low != 0 {
+            self.low.trailing_zeros()
+        } else {
+            128 + self.high.trailing_zeros()
+        }
+    }
+
+    /// Returns the number of leading ones in the binary representation of `self`.
+    pub const fn leading_ones(self) -> u32 {
+        if self.high != u128::MAX {
+            self.high.leading_ones()
+        } else {
+            128 + self.low.leading_ones()
+        }
+    }
+
+    /// Returns the number of trailing ones in the binary representation of `self`.
+    pub const fn trailing_ones(self) -> u32 {
+        if self.low != u128::MAX {
+            self.low.trailing_ones()
+        } else {
+            128 + self.high.trailing_ones()
+        }
+    }
+
+    /// Shifts the bits to the left by `n`, wrapping the truncated bits around to the end.
+    ///
+    /// Rotating by `n` is the same as rotating by `n % 256`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Endian, UInt256};
+    ///
+    /// let top = UInt256::new(1 << 127, 0, Endian::Big);
+    /// assert_eq!(top.rotate_left(1), UInt256::ONE);
+    /// assert_eq!(UInt256::ONE.rotate_left(128), UInt256::new(1, 0, Endian::Big));
+    /// ```
+    pub const fn rotate_left(self, n: u32) -> Self {
+        let (mut high, mut low) = (self.high, self.low);
+        let mut n = n % 256;
+        if n >= 128 {
+            (high, low) = (low, high);
+            n -= 128;
+        }
+        if n != 0 {
+            (high, low) = (high << n | low >> (128 - n), low << n | high >> (128 - n));
+        }
+        UInt256 { high, low, endian: self.endian }
+    }
+
+    /// Shifts the bits to the right by `n`, wrapping the truncated bits around to the
+    /// beginning.
+    ///
+    /// Rotating by `n` is the same as rotating by `n % 256`.
+    pub const fn rotate_right(self, n: u32) -> Self {
+        self.rotate_left(256 - n % 256)
+    }
+
+    /// Returns the index of the least significant set bit, or `None` for zero.
+    ///
+    /// This is the exponent of the largest power of two that divides `self`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(40).lowest_set_bit(), Some(3));
+    /// assert_eq!(UInt256::ZERO.lowest_set_bit(), None);
+    /// ```
+    pub const fn lowest_set_bit(&self) -> Option<usize> {
+        match self.trailing_zeros() {
+            256 => None,
+            tz => Some(tz as usize),
+        }
+    }
+
+    /// Returns the index of the most significant set bit, or `None` for zero.
+    ///
+    /// This is one less than the number of bits needed to represent `self`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(40).highest_set_bit(), Some(5));
+    /// assert_eq!(UInt256::MAX.highest_set_bit(), Some(255));
+    /// assert_eq!(UInt256::ZERO.highest_set_bit(), None);
+    /// ```
+    pub const fn highest_set_bit(&self) -> Option<usize> {
+        match self.leading_zeros() {
+            256 => None,
+            lz => Some(255 - lz as usize),
+        }
+    }
+
+    /// Returns `true` if `self` is even, i.e. its lowest bit is 0.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert!(UInt256::ZERO.is_even());
+    /// assert!(UInt256::MAX.is_odd());
+    /// ```
+    pub const fn is_even(&self) -> bool {
+        self.low & 1 == 0
+    }
+
+    /// Returns `true` if `self` is odd, i.e. its lowest bit is 1.
+    pub const fn is_odd(&self) -> bool {
+        self.low & 1 == 1
+    }
+
+    /// Returns `true` if the bit at the given index is set; `false` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    pub fn bit_at(&self, index: usize) -> bool {
+        assert!(index < 256, "Bit index out of range");
+
+        if index < 128 {
+            // Check bit in the `low` segment
+            (self.low & (1 << index)) != 0
+        } else {
+            // Check bit in the `high` segment
+            (self.high & (1 << (index - 128))) != 0
+        }
+    }
+
+    /// Sets the bit at the given index to 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    pub fn set_bit(&mut self, index: usize) {
+        let (limb, mask) = self.bit_limb(index);
+        *limb |= mask;
+    }
+
+    /// Sets the bit at the given index to 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    pub fn clear_bit(&mut self, index: usize) {
+        let (limb, mask) = self.bit_limb(index);
+        *limb &= !mask;
+    }
+
+    /// Flips the bit at the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    pub fn toggle_bit(&mut self, index: usize) {
+        let (limb, mask) = self.bit_limb(index);
+        *limb ^= mask;
+    }
+
+    /// Sets the bit at the given index to 1 if `value` is `true` and to 0 otherwise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    pub fn assign_bit(&mut self, index: usize, value: bool) {
+        let (limb, mask) = self.bit_limb(index);
+        *limb = (*limb & !mask) | (mask * value as u128);
+    }
+
+    /// Returns a copy of `self` with the bit at the given index set to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let flags = UInt256::ZERO.with_bit(0, true).with_bit(200, true).with_bit(0, false);
+    /// assert!(flags.bit_at(200));
+    /// assert_eq!(flags.count_ones(), 1);
+    /// ```
+    pub fn with_bit(mut self, index: usize, value: bool) -> Self {
+        self.assign_bit(index, value);
+        self
+    }
+
+    /// Returns the bits of `self` in `range`, shifted down to start at bit 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or extends past bit 255.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let word = UInt256::from(0xabcd);
+    /// assert_eq!(word.bits(4..12), UInt256::from(0xbc));
+    /// assert_eq!(word.bits(12..), UInt256::from(0xa));
+    /// assert_eq!(word.bits(..), word);
+    /// ```
+    pub fn bits<R: RangeBounds<usize>>(&self, range: R) -> Self {
+        let (start, width) = bit_range(range);
+        self.unbounded_shr(start as u32) & low_mask(width)
+    }
+
+    /// Replaces the bits of `self` in `range` with the low bits of `value`.
+    ///
+    /// This is the inverse of [`Self::bits`], for packing several fields into one word.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is decreasing or extends past bit 255, or if `value` does
+    /// not fit in the width of the range.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let mut word = UInt256::ZERO;
+    /// word.set_bits(0..64, UInt256::from(7));
+    /// word.set_bits(64..=127, UInt256::from(9));
+    /// assert_eq!(word.bits(64..128), UInt256::from(9));
+    /// assert_eq!(word.bits(0..64), UInt256::from(7));
+    /// ```
+    pub fn set_bits<R: RangeBounds<usize>>(&mut self, range: R, value: Self) {
+        let (start, width) = bit_range(range);
+        let mask = low_mask(width);
+        assert!((value & !mask).is_zero(), "Value does not fit in the bit range");
+        let shift = start as u32;
+        *self = (*self & !mask.unbounded_shl(shift)) | value.unbounded_shl(shift);
+    }
+
+    /// Returns an iterator over the 256 bits of `self`, least significant first.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let bits: Vec<bool> = UInt256::from(0b110).iter_bits_le().take(4).collect();
+    /// assert_eq!(bits, [false, true, true, false]);
+    /// assert_eq!(UInt256::MAX.iter_bits_le().len(), 256);
+    /// ```
+    pub fn iter_bits_le(&self) -> BitIter {
+        BitIter { value: *self, front: 0, back: 256 }
+    }
+
+    /// Returns an iterator over the 256 bits of `self`, most significant first.
+    ///
+    /// ## Examples
+    ///
+    /// A left-to-right square-and-multiply loop:
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let exponent = UInt256::from(13);
+    /// let mut result = 1u64;
+    /// for bit in exponent.iter_bits_be().skip_while(|bit| !bit) {
+    ///     result *= result;
+    ///     if bit {
+    ///         result *= 3;
+    ///     }
+    /// }
+    /// assert_eq!(result, 3u64.pow(13));
+    /// ```
+    pub fn iter_bits_be(&self) -> Rev<BitIter> {
+        self.iter_bits_le().rev()
+    }
+
+    /// Returns the byte at the given index, counting from the least significant byte.
+    ///
+    /// Byte `i` holds bits `8 * i` to `8 * i + 7`, matching the bit indices of
+    /// [`Self::bit_at`]. The same byte is returned by `self[i]`. For the opposite
+    /// order, as used by the EVM, see [`Self::evm_byte`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 31.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let a = UInt256::from(0x1234);
+    /// assert_eq!(a.byte(0), 0x34);
+    /// assert_eq!(a.byte(1), 0x12);
+    /// assert_eq!(a[1], 0x12);
+    /// assert_eq!(a.byte(31), 0);
+    /// ```
+    pub fn byte(&self, index: usize) -> u8 {
+        assert!(index < 32, "Byte index out of range");
+        if index < 16 {
+            (self.low >> (8 * index)) as u8
+        } else {
+            (self.high >> (8 * (index - 16))) as u8
+        }
+    }
+
+    /// Sets the byte at the given index, counting from the least significant byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 31.
+    pub fn set_byte(&mut self, index: usize, value: u8) {
+        assert!(index < 32, "Byte index out of range");
+        let (limb, shift) = if index < 16 {
+            (&mut self.low, 8 * index)
+        } else {
+            (&mut self.high, 8 * (index - 16))
+        };
+        *limb = (*limb & !(0xff << shift)) | (value as u128) << shift;
+    }
+
+    /// Returns the byte at the given index, counting from the most significant byte,
+    /// like the EVM `BYTE` opcode.
+    ///
+    /// As with the opcode, an index of 32 or more gives 0 instead of panicking.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let a = UInt256::from(0x1234);
+    /// assert_eq!(a.evm_byte(31), 0x34);
+    /// assert_eq!(a.evm_byte(30), 0x12);
+    /// assert_eq!(a.evm_byte(0), 0);
+    /// assert_eq!(a.evm_byte(32), 0);
+    /// ```
+    pub fn evm_byte(&self, index: usize) -> u8 {
+        if index < 32 {
+            self.byte(31 - index)
+        } else {
+            0
+        }
+    }
+
+    /// Gathers the bits of `self` selected by `mask` into the low bits of the result,
+    /// like the x86 `PEXT` instruction over 256 bits.
+    ///
+    /// The selected bits keep their order, and the rest of the result is zero. With
+    /// the `bmi2` target feature enabled on x86-64, each 64-bit limb is handled by a
+    /// single instruction.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let flags = UInt256::from(0b1000_0101);
+    /// let mask = UInt256::from(0b1100_0001);
+    /// assert_eq!(flags.bit_extract(mask), UInt256::from(0b101));
+    /// ```
+    pub fn bit_extract(self, mask: Self) -> Self {
+        let (value, mask) = (self.to_limbs(), mask.to_limbs());
+        let mut result = UInt256 { endian: self.endian, ..UInt256::ZERO };
+        let mut offset = 0;
+        for (&value, &mask) in value.iter().zip(&mask) {
+            let gathered = UInt256::from_limbs_with([pext64(value, mask), 0, 0, 0], self.endian);
+            result |= gathered.unbounded_shl(offset);
+            offset += mask.count_ones();
+        }
+        result
+    }
+
+    /// Scatters the low bits of `self` to the positions of the set bits of `mask`,
+    /// like the x86 `PDEP` instruction over 256 bits.
+    ///
+    /// This is the inverse of [`Self::bit_extract`] for the bits selected by `mask`.
+    /// With the `bmi2` target feature enabled on x86-64, each 64-bit limb is handled by
+    /// a single instruction.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let mask = UInt256::from(0b1100_0001);
+    /// assert_eq!(UInt256::from(0b101).bit_deposit(mask), UInt256::from(0b1000_0001));
+    /// ```
+    pub fn bit_deposit(self, mask: Self) -> Self {
+        let mut source = self;
+        let mut limbs = [0; 4];
+        for (limb, mask) in limbs.iter_mut().zip(mask.to_limbs()) {
+            *limb = pdep64(source.low as u64, mask);
+            source = source.unbounded_shr(mask.count_ones());
+        }
+        UInt256::from_limbs_with(limbs, self.endian)
+    }
+
+    /// Returns the half holding the bit at `index`, and the mask of that bit in it.
+    fn bit_limb(&mut self, index: usize) -> (&mut u128, u128) {
+        assert!(index < 256, "Bit index out of range");
+        if index < 128 {
+            (&mut self.low, 1 << index)
+        } else {
+            (&mut self.high, 1 << (index - 128))
+        }
+    }
+
+    /// Shifts `self` left by `rhs` bits, returning zero if `rhs` is 256 or more.
+    ///
+    /// The bits shifted out at the top are discarded, as with the `<<` operator, which
+    /// panics for shifts of 256 or more instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Endian, UInt256};
+    ///
+    /// assert_eq!(UInt256::ONE.unbounded_shl(130), UInt256::new(0b100, 0, Endian::Big));
+    /// assert_eq!(UInt256::ONE.unbounded_shl(256), UInt256::ZERO);
+    /// ```
+    pub const fn unbounded_shl(self, rhs: u32) -> Self {
+        let (high, low) = match rhs {
+            0 => (self.high, self.low),
+            1..=127 => (self.high << rhs | self.low >> (128 - rhs), self.low << rhs),
+            128..=255 => (self.low << (rhs - 128), 0),
+            _ => (0, 0),
+        };
+        UInt256 { high, low, endian: self.endian }
+    }
+
+    /// Shifts `self` right by `rhs` bits, returning zero if `rhs` is 256 or more.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::MAX.unbounded_shr(255), UInt256::ONE);
+    /// assert_eq!(UInt256::MAX.unbounded_shr(256), UInt256::ZERO);
+    /// ```
+    pub const fn unbounded_shr(self, rhs: u32) -> Self {
+        let (high, low) = match rhs {
+            0 => (self.high, self.low),
+            1..=127 => (self.high >> rhs, self.low >> rhs | self.high << (128 - rhs)),
+            128..=255 => (0, self.high >> (rhs - 128)),
+            _ => (0, 0),
+        };
+        UInt256 { high, low, endian: self.endian }
+    }
+
+    /// Shifts `self` left by `rhs` bits, returning `None` if `rhs` is 256 or more.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::ONE.checked_shl(4), Some(UInt256::from(16)));
+    /// assert_eq!(UInt256::ONE.checked_shl(256), None);
+    /// ```
+    pub const fn checked_shl(self, rhs: u32) -> Option<Self> {
+        if rhs < 256 {
+            Some(self.unbounded_shl(rhs))
+        } else {
+            None
+        }
+    }
+
+    /// Shifts `self` right by `rhs` bits, returning `None` if `rhs` is 256 or more.
+    pub const fn checked_shr(self, rhs: u32) -> Option<Self> {
+        if rhs < 256 {
+            Some(self.unbounded_shr(rhs))
+        } else {
+            None
+        }
+    }
+
+    /// Shifts `self` left by `rhs % 256` bits.
+    ///
+    /// As with the primitive integers, only the low bits of `rhs` are used, so this is
+    /// not a rotation: see [`Self::rotate_left`] for that.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::ONE.wrapping_shl(257), UInt256::from(2));
+    /// ```
+    pub const fn wrapping_shl(self, rhs: u32) -> Self {
+        self.unbounded_shl(rhs % 256)
+    }
+
+    /// Shifts `self` right by `rhs % 256` bits.
+    pub const fn wrapping_shr(self, rhs: u32) -> Self {
+        self.unbounded_shr(rhs % 256)
+    }
+
+    /// Shifts `self` left by `rhs % 256` bits, and returns whether `rhs` was 256 or more.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert!(!UInt256::ONE.overflowing_shl(255).1);
+    /// assert_eq!(UInt256::ONE.overflowing_shl(258), (UInt256::from(4), true));
+    /// ```
+    pub const fn overflowing_shl(self, rhs: u32) -> (Self, bool) {
+        (self.wrapping_shl(rhs), rhs >= 256)
+    }
+
+    /// Shifts `self` right by `rhs % 256` bits, and returns whether `rhs` was 256 or more.
+    pub const fn overflowing_shr(self, rhs: u32) -> (Self, bool) {
+        (self.wrapping_shr(rhs), rhs >= 256)
+    }
+
+    /// Reverses the byte order of the value.
+    ///
+    /// This changes the number itself, unlike the `endian` tag, which only describes
+    /// how the value is read from and written to bytes and is kept unchanged.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Endian, UInt256};
+    ///
+    /// let a = UInt256::from(0x1234);
+    /// assert_eq!(a.swap_bytes(), UInt256::new(0x3412 << 112, 0, Endian::Big));
+    /// assert_eq!(a.swap_bytes().swap_bytes(), a);
+    /// ```
+    pub const fn swap_bytes(self) -> Self {
+        UInt256 { high: self.low.swap_bytes(), low: self.high.swap_bytes(), endian: self.endian }
+    }
+
+    /// Converts `self` to big endian from the target's endianness.
+    ///
+    /// On big endian targets this is a no-op, on little endian targets the bytes are
+    /// swapped, as with the primitive integer types.
+    pub const fn to_be(self) -> Self {
+        if cfg!(target_endian = "big") {
+            self
+        } else {
+            self.swap_bytes()
+        }
+    }
+
+    /// Converts `self` to little endian from the target's endianness.
+    ///
+    /// On little endian targets this is a no-op, on big endian targets the bytes are
+    /// swapped, as with the primitive integer types.
+    pub const fn to_le(self) -> Self {
+        if cfg!(target_endian = "little") {
+            self
+        } else {
+            self.swap_bytes()
+        }
+    }
+}
+
+/// An iterator over the bits of a [`UInt256`], from the least significant one.
+///
+/// Created by [`UInt256::iter_bits_le`], and reversed by [`UInt256::iter_bits_be`].
+#[derive(Debug, Clone)]
+pub struct BitIter {
+    value: UInt256,
+    /// The index of the next bit from the front, and one past the next bit from the back.
+    front: usize,
+    back: usize,
+}
+
+impl Iterator for BitIter {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.value.bit_at(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for BitIter {
+    fn next_back(&mut self) -> Option<bool> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.value.bit_at(self.back))
+    }
+}
+
+impl ExactSizeIterator for BitIter {}
+
+impl FusedIterator for BitIter {}
+
+/// Every byte value, so that [`Index`] can hand out a reference to a byte that is not
+/// stored as such in a [`UInt256`].
+static BYTE_VALUES: [u8; 256] = {
+    let mut values = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        values[i] = i as u8;
+        i += 1;
+    }
+    values
+};
+
+impl Index<usize> for UInt256 {
+    type Output = u8;
+
+    /// Returns the byte at the given index, like [`UInt256::byte`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 31.
+    fn index(&self, index: usize) -> &u8 {
+        &BYTE_VALUES[self.byte(index) as usize]
+    }
+}
+
+/// Gathers the bits of `value` selected by `mask` into the low bits of the result.
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+fn pext64(value: u64, mask: u64) -> u64 {
+    // SAFETY: the whole crate is compiled with `bmi2`, so the instruction is available.
+    unsafe { std::arch::x86_64::_pext_u64(value, mask) }
+}
+
+/// Gathers the bits of `value` selected by `mask` into the low bits of the result.
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+fn pext64(value: u64, mut mask: u64) -> u64 {
+    let mut result = 0;
+    let mut bit = 1;
+    while mask != 0 {
+        if value & mask & mask.wrapping_neg() != 0 {
+            result |= bit;
+        }
+        bit <<= 1;
+        mask &= mask - 1;
+    }
+    result
+}
+
+/// Scatters the low bits of `value` to the positions of the set bits of `mask`.
+#[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+fn pdep64(value: u64, mask: u64) -> u64 {
+    // SAFETY: the whole crate is compiled with `bmi2`, so the instruction is available.
+    unsafe { std::arch::x86_64::_pdep_u64(value, mask) }
+}
+
+/// Scatters the low bits of `value` to the positions of the set bits of `mask`.
+#[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+fn pdep64(value: u64, mut mask: u64) -> u64 {
+    let mut result = 0;
+    let mut bit = 1;
+    while mask != 0 {
+        let lowest = mask & mask.wrapping_neg();
+        if value & bit != 0 {
+            result |= lowest;
+        }
+        bit <<= 1;
+        mask ^= lowest;
+    }
+    result
+}
+
+/// Returns the start and width of a range of bit indices.
+///
+/// # Panics
+///
+/// Panics if the range is decreasing or extends past bit 255.
+fn bit_range<R: RangeBounds<usize>>(range: R) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start.checked_add(1).expect("Bit range out of range"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end.checked_add(1).expect("Bit range out of range"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => 256,
+    };
+    assert!(start <= end && end <= 256, "Bit range out of range");
+    (start, end - start)
+}
+
+/// Returns a value with the lowest `width` bits set, for `width <= 256`.
+fn low_mask(width: usize) -> UInt256 {
+    UInt256::MAX.unbounded_shr(256 - width as u32)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::xorshift;
+
+    #[test]
+    fn test_count_ones_and_zeros() {
+        assert_eq!(UInt256::ZERO.count_ones(), 0);
+        assert_eq!(UInt256::ZERO.count_zeros(), 256);
+        assert_eq!(UInt256::MAX.count_zeros(), 0);
+        let a = UInt256::new(0b1011, u128::MAX, Endian::Big);
+        assert_eq!(a.count_ones(), 131);
+        assert_eq!(a.count_zeros(), 125);
+    }
+
+    #[test]
+    fn test_leading_and_trailing() {
+        // (value, leading zeros, trailing zeros, leading ones, trailing ones)
+        let cases = [
+            (UInt256::ZERO, 256, 256, 0, 0),
+            (UInt256::MAX, 0, 0, 256, 256),
+            (UInt256::ONE, 255, 0, 0, 1),
+            (UInt256::new(1 << 127, 0, Endian::Big), 0, 255, 1, 0),
+            (UInt256::new(0, u128::MAX, Endian::Big), 128, 0, 0, 128),
+            (UInt256::new(u128::MAX, 0, Endian::Big), 0, 128, 128, 0),
+            (UInt256::new(u128::MAX, 0b0111, Endian::Big), 0, 0, 128, 3),
+            (UInt256::new(1, 1 << 127, Endian::Big), 127, 127, 0, 0),
+            (UInt256::MAX - UInt256::new(0, 1 << 127, Endian::Big), 0, 0, 128, 127),
+        ];
+        for (value, lz, tz, lo, to) in cases {
+            assert_eq!(value.leading_zeros(), lz, "{:?}", value);
+            assert_eq!(value.trailing_zeros(), tz, "{:?}", value);
+            assert_eq!(value.leading_ones(), lo, "{:?}", value);
+            assert_eq!(value.trailing_ones(), to, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_rotate() {
+        let a = UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Big);
+        for n in [0, 1, 63, 64, 100, 127, 128, 129, 200, 255, 256, 257, 1000, u32::MAX] {
+            let left = a.rotate_left(n);
+            assert_eq!(left.rotate_right(n), a, "n = {}", n);
+            assert_eq!(left, a.rotate_right(256 - n % 256), "n = {}", n);
+            assert_eq!(left.count_ones(), a.count_ones());
+        }
+        assert_eq!(a.rotate_left(0), a);
+        assert_eq!(a.rotate_left(256), a);
+        assert_eq!(a.rotate_left(128), UInt256::new(a.low, a.high, Endian::Big));
+
+        // Bits crossing the high/low boundary in both directions.
+        let boundary = UInt256::new(1, 1 << 127, Endian::Big);
+        assert_eq!(boundary.rotate_left(1), UInt256::new(0b11, 0, Endian::Big));
+        assert_eq!(boundary.rotate_right(1), UInt256::new(0, 0b11 << 126, Endian::Big));
+        assert_eq!(UInt256::ONE.rotate_right(1), UInt256::new(1 << 127, 0, Endian::Big));
+        assert_eq!(UInt256::MAX.rotate_left(77), UInt256::MAX);
+    }
+
+    #[test]
+    fn test_swap_bytes() {
+        let a = UInt256::from_be_bytes(&std::array::from_fn(|i| i as u8));
+        let swapped = a.swap_bytes();
+        assert_eq!(swapped.to_be_bytes().to_vec(), (0..32u8).rev().collect::<Vec<_>>());
+        assert_eq!(swapped.swap_bytes(), a);
+        assert_eq!(UInt256::ONE.swap_bytes(), UInt256::new(1 << 120, 0, Endian::Big));
+        assert_eq!(UInt256::MAX.swap_bytes(), UInt256::MAX);
+        assert_eq!(a.swap_bytes().endian(), Endian::Big);
+
+        // `to_be` and `to_le` only swap when the target has the other byte order.
+        let x = UInt256::from(0x0102_0304);
+        if cfg!(target_endian = "little") {
+            assert_eq!(x.to_le(), x);
+            assert_eq!(x.to_be(), x.swap_bytes());
+        } else {
+            assert_eq!(x.to_be(), x);
+            assert_eq!(x.to_le(), x.swap_bytes());
+        }
+    }
+
+    #[test]
+    fn test_set_bit_scans() {
+        assert_eq!(UInt256::ZERO.lowest_set_bit(), None);
+        assert_eq!(UInt256::ZERO.highest_set_bit(), None);
+        for low in [0, 1, 64, 127, 128, 200, 255] {
+            for high in [low, 127, 128, 255] {
+                if high < low {
+                    continue;
+                }
+                let value = UInt256::ZERO.with_bit(low, true).with_bit(high, true);
+                assert_eq!(value.lowest_set_bit(), Some(low));
+                assert_eq!(value.highest_set_bit(), Some(high));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parity() {
+        for n in 0..10usize {
+            let value = UInt256::new(u128::MAX, n as u128, Endian::Big);
+            assert_eq!(value.is_even(), n % 2 == 0);
+            assert_eq!(value.is_odd(), n % 2 == 1);
+        }
+        assert!(UInt256::new(1, 0, Endian::Big).is_even());
+    }
+
+    #[test]
+    fn test_single_bit_ops() {
+        let mut a = UInt256::ZERO;
+        for index in [0, 1, 127, 128, 200, 255] {
+            a.set_bit(index);
+            assert!(a.bit_at(index));
+            a.clear_bit(index);
+            assert!(!a.bit_at(index));
+            a.toggle_bit(index);
+            assert!(a.bit_at(index));
+            a.toggle_bit(index);
+            assert!(a.is_zero());
+            a.assign_bit(index, true);
+            assert_eq!(a, UInt256::ZERO.with_bit(index, true));
+            a.assign_bit(index, false);
+            assert!(a.is_zero());
+        }
+
+        let mut b = UInt256::MAX;
+        b.clear_bit(128);
+        b.assign_bit(127, false);
+        assert_eq!(b, UInt256::new(u128::MAX - 1, u128::MAX >> 1, Endian::Big));
+        assert_eq!(b.with_bit(128, true).with_bit(127, true), UInt256::MAX);
+        b.set_bit(5);
+        assert_eq!(b.count_zeros(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit index out of range")]
+    fn test_clear_bit_out_of_range() {
+        let mut a = UInt256::MAX;
+        a.clear_bit(256);
+    }
+
+    #[test]
+    fn test_bit_ranges() {
+        let a = UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Big);
+        assert_eq!(a.bits(..), a);
+        assert_eq!(a.bits(0..0), UInt256::ZERO);
+        assert_eq!(a.bits(256..), UInt256::ZERO);
+        assert_eq!(a.bits(0..4), UInt256::ZERO);
+        assert_eq!(a.bits(4..8), UInt256::ONE);
+        assert_eq!(a.bits(124..132), UInt256::from(0xf0));
+        assert_eq!(a.bits(60..68), UInt256::from(0xf));
+        assert_eq!(a.bits(128..), UInt256::new(0, a.high, Endian::Big));
+        assert_eq!(a.bits(..128), UInt256::new(0, a.low, Endian::Big));
+        assert_eq!(a.bits(252..=255), UInt256::ZERO);
+        assert_eq!(UInt256::MAX.bits(1..256), UInt256::MAX >> 1);
+
+        // Splice every field back into an empty word.
+        let mut b = UInt256::ZERO;
+        for (start, end) in [(0, 1), (1, 100), (100, 128), (128, 129), (129, 255), (255, 256)] {
+            b.set_bits(start..end, a.bits(start..end));
+        }
+        assert_eq!(b, a);
+
+        b.set_bits(120..136, UInt256::ZERO);
+        assert_eq!(b.bits(120..136), UInt256::ZERO);
+        assert_eq!(b.bits(..120), a.bits(..120));
+        assert_eq!(b.bits(136..), a.bits(136..));
+        b.set_bits(.., UInt256::MAX);
+        assert_eq!(b, UInt256::MAX);
+        b.set_bits(10..10, UInt256::ZERO);
+        assert_eq!(b, UInt256::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit range out of range")]
+    fn test_bits_out_of_range() {
+        let _ = UInt256::MAX.bits(200..257);
+    }
+
+    #[test]
+    #[should_panic(expected = "Value does not fit in the bit range")]
+    fn test_set_bits_value_too_wide() {
+        let mut a = UInt256::ZERO;
+        a.set_bits(8..16, UInt256::from(0x100));
+    }
+
+    #[test]
+    fn test_bit_iterators() {
+        let a = UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Big);
+        let le: Vec<bool> = a.iter_bits_le().collect();
+        let be: Vec<bool> = a.iter_bits_be().collect();
+        assert_eq!(le.len(), 256);
+        for i in 0..256 {
+            assert_eq!(le[i], a.bit_at(i));
+            assert_eq!(be[255 - i], a.bit_at(i));
+        }
+
+        // Rebuild the value from the most significant bit down.
+        let rebuilt = a.iter_bits_be().fold(UInt256::ZERO, |acc, bit| (acc << 1) | UInt256::from(bit as usize));
+        assert_eq!(rebuilt, a);
+
+        let mut iter = UInt256::ONE.iter_bits_le();
+        assert_eq!(iter.len(), 256);
+        assert_eq!(iter.next(), Some(true));
+        assert_eq!(iter.next_back(), Some(false));
+        assert_eq!(iter.len(), 254);
+        assert!(iter.by_ref().all(|bit| !bit));
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_bytes() {
+        let a = UInt256::from_be_bytes(&std::array::from_fn(|i| i as u8 + 1));
+        let be = a.to_be_bytes();
+        for i in 0..32 {
+            assert_eq!(a.byte(i), be[31 - i]);
+            assert_eq!(a[i], be[31 - i]);
+            assert_eq!(a.evm_byte(i), be[i]);
+        }
+        assert_eq!(a.evm_byte(32), 0);
+        assert_eq!(a.evm_byte(usize::MAX), 0);
+
+        let mut b = UInt256::ZERO;
+        for i in 0..32 {
+            b.set_byte(i, a.byte(i));
+        }
+        assert_eq!(b, a);
+        b.set_byte(15, 0);
+        b.set_byte(16, 0xff);
+        assert_eq!(b.bits(120..136), UInt256::from(0xff00));
+        assert_eq!(b.byte(14), a.byte(14));
+        assert_eq!(b.byte(17), a.byte(17));
+    }
+
+    #[test]
+    #[should_panic(expected = "Byte index out of range")]
+    fn test_index_out_of_range() {
+        let _ = UInt256::MAX[32];
+    }
+
+    #[test]
+    fn test_bit_extract_and_deposit() {
+        let mut next = xorshift(0x9e37_79b9_7f4a_7c15);
+        for round in 0..100 {
+            let value = UInt256::from_limbs_with([next(), next(), next(), next()], Endian::Big);
+            let mut mask = UInt256::from_limbs_with([next(), next(), next(), next()], Endian::Big);
+            // Also cover sparse masks and masks with empty limbs.
+            if round % 3 == 0 {
+                mask &= UInt256::from_limbs_with([next(), 0, next(), next()], Endian::Big);
+            }
+
+            // Compare against gathering and scattering one bit at a time.
+            let selected: Vec<usize> = (0..256).filter(|&i| mask.bit_at(i)).collect();
+            let mut extracted = UInt256::ZERO;
+            let mut deposited = UInt256::ZERO;
+            for (j, &i) in selected.iter().enumerate() {
+                extracted.assign_bit(j, value.bit_at(i));
+                deposited.assign_bit(i, value.bit_at(j));
+            }
+            assert_eq!(value.bit_extract(mask), extracted);
+            assert_eq!(value.bit_deposit(mask), deposited);
+            assert_eq!(value.bit_extract(mask).bit_deposit(mask), value & mask);
+        }
+
+        let value = UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Little);
+        assert_eq!(value.bit_extract(UInt256::MAX), value);
+        assert_eq!(value.bit_deposit(UInt256::MAX), value);
+        assert_eq!(value.bit_extract(UInt256::ZERO), UInt256::ZERO);
+        assert_eq!(value.bit_deposit(UInt256::ZERO), UInt256::ZERO);
+        assert_eq!(value.bit_extract(UInt256::ONE).endian(), Endian::Little);
+    }
+
+    /// The expected bit `i` of `value` shifted left (or right) by `shift`.
+    fn shifted_bit(value: UInt256, i: u32, shift: u32, left: bool) -> bool {
+        let source = if left { i.checked_sub(shift) } else { i.checked_add(shift) };
+        source.is_some_and(|j| j < 256 && value.bit_at(j as usize))
+    }
+
+    #[test]
+    fn test_shifts_bit_by_bit() {
+        let values = [
+            UInt256::ONE,
+            UInt256::MAX,
+            UInt256::new(1 << 127, 1, Endian::Big),
+            UInt256::new(
+                0x0123_4567_89ab_cdef_0f1e_2d3c_4b5a_6978,
+                0xfedc_ba98_7654_3210_8796_a5b4_c3d2_e1f0,
+                Endian::Little,
+            ),
+        ];
+        for value in values {
+            for shift in 0..=300 {
+                let left = value.unbounded_shl(shift);
+                let right = value.unbounded_shr(shift);
+                for i in 0..256 {
+                    let (expected_left, expected_right) =
+                        (shifted_bit(value, i, shift, true), shifted_bit(value, i, shift, false));
+                    assert_eq!(left.bit_at(i as usize), expected_left, "{:?} << {}", value, shift);
+                    assert_eq!(right.bit_at(i as usize), expected_right, "{:?} >> {}", value, shift);
+                }
+                assert_eq!(left.endian(), value.endian());
+                assert_eq!(right.endian(), value.endian());
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift_variants() {
+        let a = UInt256::new(0xabcd, 0x1234, Endian::Big);
+        for shift in [0, 1, 63, 64, 127, 128, 129, 255] {
+            assert_eq!(a << shift, a.unbounded_shl(shift));
+            assert_eq!(a >> shift, a.unbounded_shr(shift));
+            assert_eq!(a.checked_shl(shift), Some(a << shift));
+            assert_eq!(a.checked_shr(shift), Some(a >> shift));
+            assert_eq!(a.wrapping_shl(shift), a << shift);
+            assert_eq!(a.wrapping_shr(shift + 256), a >> shift);
+            assert_eq!(a.overflowing_shl(shift), (a << shift, false));
+            assert_eq!(a.overflowing_shr(shift + 512), (a >> shift, true));
+        }
+        for shift in [256, 257, 511, 512, u32::MAX] {
+            assert_eq!(a.checked_shl(shift), None);
+            assert_eq!(a.checked_shr(shift), None);
+            assert_eq!(a.unbounded_shl(shift), UInt256::ZERO);
+            assert_eq!(a.unbounded_shr(shift), UInt256::ZERO);
+            assert_eq!(a.wrapping_shl(shift), a.unbounded_shl(shift % 256));
+            assert!(a.overflowing_shr(shift).1);
+        }
+        assert_eq!(a << 0, a);
+        assert_eq!(a >> 0, a);
+        assert_eq!(UInt256::MAX << 255, UInt256::new(1 << 127, 0, Endian::Big));
+        assert_eq!(UInt256::MAX >> 255, UInt256::ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to shift left with overflow")]
+    fn test_shl_overflow() {
+        let _ = UInt256::ONE << 256;
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to shift right with overflow")]
+    fn test_shr_negative() {
+        let _ = UInt256::ONE >> -1;
+    }
+}
@@ -0,0 +1,246 @@
+//! A fixed set of 256 slots, stored as the bits of a [`UInt256`].
+//!
+//! Slot `i` is bit `i`, so the bitset methods agree with [`UInt256::bit_at`] and the
+//! other bit-level methods, and the bitwise operators work on sets as well.
+
+use std::iter::FusedIterator;
+
+use super::*;
+
+impl UInt256 {
+    /// Adds `index` to the set, returning `true` if it was not already present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let mut validators = UInt256::ZERO;
+    /// assert!(validators.insert(3));
+    /// assert!(!validators.insert(3));
+    /// validators.insert(200);
+    /// assert_eq!(validators.len(), 2);
+    /// assert_eq!(validators.iter_ones().collect::<Vec<_>>(), [3, 200]);
+    /// ```
+    pub fn insert(&mut self, index: usize) -> bool {
+        let present = self.bit_at(index);
+        self.set_bit(index);
+        !present
+    }
+
+    /// Removes `index` from the set, returning `true` if it was present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    pub fn remove(&mut self, index: usize) -> bool {
+        let present = self.bit_at(index);
+        self.clear_bit(index);
+        present
+    }
+
+    /// Returns `true` if `index` is in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than 255.
+    pub fn contains(&self, index: usize) -> bool {
+        self.bit_at(index)
+    }
+
+    /// Returns the number of indices in the set, i.e. the number of set bits.
+    pub const fn len(&self) -> usize {
+        self.count_ones() as usize
+    }
+
+    /// Returns `true` if the set is empty, i.e. `self` is zero.
+    pub fn is_empty(&self) -> bool {
+        self.is_zero()
+    }
+
+    /// Returns the indices in `self`, `other`, or both.
+    pub fn union(self, other: Self) -> Self {
+        self | other
+    }
+
+    /// Returns the indices in both `self` and `other`.
+    pub fn intersection(self, other: Self) -> Self {
+        self & other
+    }
+
+    /// Returns the indices in `self` but not in `other`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let a = UInt256::from_indices([1, 2, 3]).unwrap();
+    /// let b = UInt256::from_indices([3, 4]).unwrap();
+    /// assert_eq!(a.difference(b).iter_ones().collect::<Vec<_>>(), [1, 2]);
+    /// assert_eq!(a.intersection(b).iter_ones().collect::<Vec<_>>(), [3]);
+    /// assert_eq!(a.union(b).len(), 4);
+    /// ```
+    pub fn difference(self, other: Self) -> Self {
+        self & !other
+    }
+
+    /// Returns the indices in exactly one of `self` and `other`.
+    pub fn symmetric_difference(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    /// Returns `true` if every index in `self` is also in `other`.
+    pub fn is_subset(&self, other: &Self) -> bool {
+        (*self & !*other).is_zero()
+    }
+
+    /// Returns `true` if `self` and `other` have no index in common.
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        (*self & *other).is_zero()
+    }
+
+    /// Returns the set of the given indices, or `None` if an index is greater than 255.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let set = UInt256::from_indices([0, 2, 4]).unwrap();
+    /// assert_eq!(set, UInt256::from(0b10101));
+    /// assert_eq!(UInt256::from_indices([3, 256]), None);
+    /// ```
+    pub fn from_indices<I: IntoIterator<Item = usize>>(indices: I) -> Option<Self> {
+        let mut set = UInt256::ZERO;
+        for index in indices {
+            if index > 255 {
+                return None;
+            }
+            set.set_bit(index);
+        }
+        Some(set)
+    }
+
+    /// Returns an iterator over the indices in the set, i.e. the positions of the set
+    /// bits, in increasing order.
+    pub fn iter_ones(&self) -> Ones {
+        Ones { remaining: *self }
+    }
+}
+
+/// An iterator over the positions of the set bits of a [`UInt256`], in increasing
+/// order.
+///
+/// Created by [`UInt256::iter_ones`].
+#[derive(Debug, Clone)]
+pub struct Ones {
+    /// The bits not yielded yet.
+    remaining: UInt256,
+}
+
+impl Iterator for Ones {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let index = self.remaining.lowest_set_bit()?;
+        self.remaining.clear_bit(index);
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining.len();
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Ones {
+    fn next_back(&mut self) -> Option<usize> {
+        let index = self.remaining.highest_set_bit()?;
+        self.remaining.clear_bit(index);
+        Some(index)
+    }
+}
+
+impl ExactSizeIterator for Ones {}
+
+impl FusedIterator for Ones {}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_insert_remove_contains() {
+        let mut set = UInt256::ZERO;
+        assert!(set.is_empty());
+        for index in [0, 63, 64, 127, 128, 255] {
+            assert!(!set.contains(index));
+            assert!(set.insert(index));
+            assert!(!set.insert(index));
+            assert!(set.contains(index));
+        }
+        assert_eq!(set.len(), 6);
+        assert!(set.remove(128));
+        assert!(!set.remove(128));
+        assert!(!set.contains(128));
+        assert_eq!(set.len(), 5);
+        assert!(!set.is_empty());
+        assert_eq!(UInt256::MAX.len(), 256);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let evens = UInt256::from_indices((0..256).step_by(2)).unwrap();
+        let low = UInt256::from_indices(0..128).unwrap();
+        assert_eq!(low, UInt256::new(0, u128::MAX, Endian::Big));
+        assert_eq!(evens.union(low).len(), 192);
+        assert_eq!(evens.intersection(low).len(), 64);
+        assert_eq!(evens.difference(low).iter_ones().next(), Some(128));
+        assert_eq!(evens.symmetric_difference(low).len(), 128);
+        assert!(evens.intersection(low).is_subset(&evens));
+        assert!(!low.is_subset(&evens));
+        assert!(evens.is_disjoint(&(evens << 1)));
+        assert!(!evens.is_disjoint(&low));
+        assert!(UInt256::ZERO.is_subset(&UInt256::ZERO));
+    }
+
+    #[test]
+    fn test_iter_ones() {
+        let indices = [0, 1, 64, 127, 128, 129, 200, 255];
+        let set = UInt256::from_indices(indices).unwrap();
+        assert_eq!(set.iter_ones().collect::<Vec<_>>(), indices);
+        assert_eq!(set.iter_ones().rev().collect::<Vec<_>>(), indices.iter().rev().copied().collect::<Vec<_>>());
+
+        let mut iter = set.iter_ones();
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next_back(), Some(255));
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.by_ref().count(), 6);
+        assert_eq!(iter.next(), None);
+        assert_eq!(UInt256::ZERO.iter_ones().next(), None);
+        assert_eq!(UInt256::MAX.iter_ones().sum::<usize>(), 255 * 256 / 2);
+    }
+
+    #[test]
+    fn test_from_indices() {
+        assert_eq!(UInt256::from_indices([]), Some(UInt256::ZERO));
+        assert_eq!(UInt256::from_indices([255, 0, 255]), Some(UInt256::ONE | UInt256::ONE << 255));
+        assert_eq!(UInt256::from_indices(0..256), Some(UInt256::MAX));
+        assert_eq!(UInt256::from_indices([1, 256, 2]), None);
+        assert_eq!(UInt256::from_indices([usize::MAX]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit index out of range")]
+    fn test_insert_out_of_range() {
+        let mut set = UInt256::ZERO;
+        set.insert(256);
+    }
+}
@@ -0,0 +1,331 @@
+//! Byte-level views of [`UInt256`] values that do not allocate.
+
+use std::borrow::Borrow;
+use std::ops::Deref;
+
+use super::*;
+
+/// The 32 big-endian bytes of a [`UInt256`], held by value.
+///
+/// A `ByteView` derefs to `[u8; 32]` and implements `AsRef<[u8]>` and
+/// `Borrow<[u8; 32]>`, so it can be hashed, compared and used to look up keys of
+/// type `[u8; 32]` without a heap allocation.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::collections::HashMap;
+/// use uint256::UInt256;
+///
+/// let mut storage: HashMap<[u8; 32], &str> = HashMap::new();
+/// storage.insert(UInt256::from(7).to_be_array(), "seven");
+///
+/// let key = UInt256::from(7).byte_view();
+/// assert_eq!(storage.get(&*key), Some(&"seven"));
+/// assert_eq!(key.as_ref()[31], 7);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ByteView([u8; 32]);
+
+impl ByteView {
+    /// Returns the bytes as an array.
+    pub fn into_array(self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl UInt256 {
+    /// Returns the big-endian bytes of the value as a [`ByteView`], without allocating.
+    pub fn byte_view(&self) -> ByteView {
+        ByteView(self.to_be_array())
+    }
+}
+
+impl UInt256 {
+    /// Builds a value from 32 bytes in the byte order of the target platform, as found
+    /// in memory-mapped structures and FFI buffers.
+    ///
+    /// The result is tagged with [`Endian::Native`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let value = UInt256::from(0xabcd);
+    /// assert_eq!(UInt256::from_ne_bytes(value.to_ne_bytes()), value);
+    /// ```
+    pub fn from_ne_bytes(bytes: [u8; 32]) -> Self {
+        let value = match Endian::Native {
+            Endian::Little => UInt256::from_le_array(bytes),
+            Endian::Big => UInt256::from_be_bytes(&bytes),
+        };
+        UInt256 { endian: Endian::Native, ..value }
+    }
+
+    /// Returns the value as 32 bytes in the byte order of the target platform.
+    pub fn to_ne_bytes(&self) -> [u8; 32] {
+        match Endian::Native {
+            Endian::Little => self.to_le_array(),
+            Endian::Big => self.to_be_array(),
+        }
+    }
+}
+
+impl UInt256 {
+    /// Returns the number of bytes needed to represent the value, which is 0 for zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::ZERO.byte_len(), 0);
+    /// assert_eq!(UInt256::from(0xff).byte_len(), 1);
+    /// assert_eq!(UInt256::from(0x100).byte_len(), 2);
+    /// assert_eq!(UInt256::MAX.byte_len(), 32);
+    /// ```
+    pub const fn byte_len(&self) -> usize {
+        (256 - self.leading_zeros() as usize).div_ceil(8)
+    }
+
+    /// Returns the shortest big-endian encoding of the value, without leading zero
+    /// bytes, as required by RLP. Zero is encoded as no bytes at all.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(0x0400).to_be_bytes_trimmed_vec(), [0x04, 0x00]);
+    /// assert!(UInt256::ZERO.to_be_bytes_trimmed_vec().is_empty());
+    /// ```
+    pub fn to_be_bytes_trimmed_vec(&self) -> Vec<u8> {
+        self.to_be_array()[32 - self.byte_len()..].to_vec()
+    }
+
+    /// Returns the shortest little-endian encoding of the value, without trailing
+    /// zero bytes. Zero is encoded as no bytes at all.
+    pub fn to_le_bytes_trimmed_vec(&self) -> Vec<u8> {
+        self.to_le_array()[..self.byte_len()].to_vec()
+    }
+
+    /// Returns a one-byte length followed by the shortest big-endian encoding of the
+    /// value, so zero takes 1 byte and [`UInt256::MAX`] takes 33.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(0x0400).to_compact_bytes(), [0x02, 0x04, 0x00]);
+    /// assert_eq!(UInt256::ZERO.to_compact_bytes(), [0x00]);
+    /// ```
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let len = self.byte_len();
+        let mut out = Vec::with_capacity(1 + len);
+        out.push(len as u8);
+        out.extend_from_slice(&self.to_be_array()[32 - len..]);
+        out
+    }
+
+    /// Reads a value written by [`Self::to_compact_bytes`]. The slice must hold exactly
+    /// the length byte and the bytes it declares; leading zero bytes are accepted.
+    ///
+    /// Fails with [`ConversionError::TooManyBytes`] if the length byte is above 32, and
+    /// with [`ConversionError::LengthMismatch`] if the slice is empty or its length does
+    /// not match the length byte.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ConversionError, UInt256};
+    ///
+    /// assert_eq!(UInt256::from_compact_bytes(&[0x02, 0x04, 0x00]), Ok(UInt256::from(0x0400)));
+    /// assert_eq!(
+    ///     UInt256::from_compact_bytes(&[0x02, 0x04]),
+    ///     Err(ConversionError::LengthMismatch { expected: 3, len: 2 })
+    /// );
+    /// ```
+    pub fn from_compact_bytes(bytes: &[u8]) -> Result<Self, ConversionError> {
+        let (&len, rest) = bytes.split_first().ok_or(ConversionError::LengthMismatch { expected: 1, len: 0 })?;
+        let len = len as usize;
+        if len > 32 {
+            return Err(ConversionError::TooManyBytes { len });
+        }
+        if rest.len() != len {
+            return Err(ConversionError::LengthMismatch { expected: 1 + len, len: bytes.len() });
+        }
+        UInt256::from_be_slice(rest)
+    }
+}
+
+impl Deref for ByteView {
+    type Target = [u8; 32];
+
+    fn deref(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for ByteView {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Borrow<[u8; 32]> for ByteView {
+    fn borrow(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Borrow<[u8]> for ByteView {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<UInt256> for ByteView {
+    fn from(value: UInt256) -> Self {
+        value.byte_view()
+    }
+}
+
+impl From<ByteView> for [u8; 32] {
+    fn from(view: ByteView) -> Self {
+        view.0
+    }
+}
+
+impl From<ByteView> for UInt256 {
+    fn from(view: ByteView) -> Self {
+        UInt256::from(view.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_byte_view() {
+        let value = UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Little);
+        let view = value.byte_view();
+        assert_eq!(*view, *value.as_bytes());
+        assert_eq!(view.as_ref(), &value.to_be_array()[..]);
+        assert_eq!(view.len(), 32);
+        assert_eq!(view.into_array(), value.to_be_array());
+        assert_eq!(UInt256::from(view), value);
+        assert_eq!(ByteView::from(value), view);
+        assert_eq!(<[u8; 32]>::from(view), value.to_be_array());
+
+        // Ordering the views orders the values.
+        assert!(UInt256::ONE.byte_view() < UInt256::new(1, 0, Endian::Big).byte_view());
+    }
+
+    #[test]
+    fn test_byte_view_lookups() {
+        let keys: HashSet<ByteView> = (0..10usize).map(|n| UInt256::from(n).byte_view()).collect();
+        assert!(keys.contains(&UInt256::from(3).to_be_array()));
+        assert!(keys.contains(&UInt256::from(9).to_be_array()[..]));
+        assert!(!keys.contains(&UInt256::from(10).to_be_array()));
+    }
+
+    #[test]
+    fn test_native_endian_bytes() {
+        let value = UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Big);
+        let bytes = value.to_ne_bytes();
+        if cfg!(target_endian = "little") {
+            assert_eq!(bytes, value.to_le_array());
+        } else {
+            assert_eq!(bytes, value.to_be_array());
+        }
+        let read = UInt256::from_ne_bytes(bytes);
+        assert_eq!(read, value);
+        assert_eq!(read.endian(), Endian::Native);
+
+        // The low half of the value is laid out like a native `u128`.
+        let low_offset = if cfg!(target_endian = "little") { 0 } else { 16 };
+        assert_eq!(bytes[low_offset..low_offset + 16], value.low.to_ne_bytes());
+
+        let built = UInt256Builder::new().with_endian(Endian::Native).from_bytes(bytes).build();
+        assert_eq!(built, value);
+    }
+
+    #[test]
+    fn test_trimmed_bytes() {
+        assert_eq!(UInt256::ZERO.byte_len(), 0);
+        assert!(UInt256::ZERO.to_le_bytes_trimmed_vec().is_empty());
+        for len in 1..=32 {
+            // The smallest and largest values of `len` bytes.
+            let smallest = UInt256::ONE << (8 * (len - 1));
+            let largest = UInt256::MAX >> (256 - 8 * len);
+            for value in [smallest, largest] {
+                assert_eq!(value.byte_len(), len);
+                let be = value.to_be_bytes_trimmed_vec();
+                let le = value.to_le_bytes_trimmed_vec();
+                assert_eq!(be.len(), len);
+                assert_ne!(be[0], 0);
+                assert_eq!(le.iter().rev().copied().collect::<Vec<_>>(), be);
+                assert_eq!(UInt256::from_be_slice(&be), Ok(value));
+                assert_eq!(UInt256::from_le_slice(&le), Ok(value));
+            }
+        }
+        let value = UInt256::new(1, 0x00ff, Endian::Big);
+        assert_eq!(value.to_be_bytes_trimmed_vec()[..2], [0x01, 0x00]);
+        assert_eq!(value.to_le_bytes_trimmed_vec()[..2], [0xff, 0x00]);
+    }
+
+    #[test]
+    fn test_compact_bytes() {
+        assert_eq!(UInt256::ZERO.to_compact_bytes(), [0]);
+        assert_eq!(UInt256::from_compact_bytes(&[0]), Ok(UInt256::ZERO));
+        for len in 1..=32 {
+            let smallest = UInt256::ONE << (8 * (len - 1));
+            let largest = UInt256::MAX >> (256 - 8 * len);
+            for value in [smallest, largest] {
+                let bytes = value.to_compact_bytes();
+                assert_eq!(bytes.len(), 1 + len);
+                assert_eq!(bytes[0] as usize, len);
+                assert_eq!(bytes[1..], value.to_be_bytes_trimmed_vec());
+                assert_eq!(UInt256::from_compact_bytes(&bytes), Ok(value));
+            }
+        }
+        assert_eq!(UInt256::from_compact_bytes(&[2, 0, 7]), Ok(UInt256::from(7)));
+
+        let mismatch = |expected, len| Err(ConversionError::LengthMismatch { expected, len });
+        assert_eq!(UInt256::from_compact_bytes(&[]), mismatch(1, 0));
+        assert_eq!(UInt256::from_compact_bytes(&[1, 2, 3]), mismatch(2, 3));
+        assert_eq!(UInt256::from_compact_bytes(&[3, 2, 3]), mismatch(4, 3));
+        let mut long = vec![33];
+        long.extend_from_slice(&[0xff; 33]);
+        assert_eq!(UInt256::from_compact_bytes(&long), Err(ConversionError::TooManyBytes { len: 33 }));
+        assert_eq!(
+            ConversionError::LengthMismatch { expected: 4, len: 2 }.to_string(),
+            "length prefix declares 4 bytes, got 2"
+        );
+    }
+
+    #[test]
+    fn test_copy_to_slice() {
+        let value = UInt256::new(0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, Endian::Big);
+        let mut buffer = [0xaau8; 34];
+        value.copy_to_be_slice(&mut buffer[1..33]);
+        assert_eq!(buffer[1..33], value.to_be_array());
+        assert_eq!((buffer[0], buffer[33]), (0xaa, 0xaa));
+        value.copy_to_le_slice(&mut buffer[2..]);
+        assert_eq!(buffer[2..], value.to_le_array());
+        assert_eq!(value.to_le_bytes(), value.to_le_array());
+        assert_eq!(*value.as_bytes(), value.to_be_array());
+    }
+
+    #[test]
+    #[should_panic(expected = "destination slice must be 32 bytes long")]
+    fn test_copy_to_short_slice() {
+        UInt256::ONE.copy_to_be_slice(&mut [0u8; 31]);
+    }
+}
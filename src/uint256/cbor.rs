@@ -0,0 +1,249 @@
+//! CBOR encoding of [`UInt256`] values (RFC 8949).
+//!
+//! Values that fit in 64 bits are plain unsigned integers, and larger ones are
+//! unsigned bignums: tag 2 on a byte string of the big-endian bytes. This is the
+//! preferred serialization, so the output is also valid deterministic CBOR.
+
+use std::fmt;
+
+use super::*;
+
+/// The major types of the CBOR data model used here.
+const UNSIGNED: u8 = 0;
+const NEGATIVE: u8 = 1;
+const BYTES: u8 = 2;
+const TAG: u8 = 6;
+
+/// The tags of unsigned and negative bignums.
+const TAG_UNSIGNED_BIGNUM: u64 = 2;
+const TAG_NEGATIVE_BIGNUM: u64 = 3;
+
+/// The error returned when bytes are not the CBOR encoding of a [`UInt256`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CborError {
+    /// The input ends before the item does.
+    Truncated,
+    /// The item is neither an unsigned integer nor an unsigned bignum, or uses an
+    /// indefinite length.
+    UnexpectedItem {
+        /// The initial byte of the offending data item.
+        initial: u8,
+    },
+    /// The item is a negative integer or bignum.
+    Negative,
+    /// The bignum does not fit in 256 bits.
+    Overflow,
+    /// There are bytes after the item.
+    TrailingBytes {
+        /// The number of extra bytes.
+        len: usize,
+    },
+}
+
+impl fmt::Display for CborError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CborError::Truncated => write!(f, "CBOR item is truncated"),
+            CborError::UnexpectedItem { initial } => {
+                write!(f, "expected a CBOR unsigned integer or bignum, got initial byte {:#04x}", initial)
+            }
+            CborError::Negative => ConversionError::Negative.fmt(f),
+            CborError::Overflow => write!(f, "CBOR bignum is too large to fit in UInt256"),
+            CborError::TrailingBytes { len } => write!(f, "{} bytes after the CBOR item", len),
+        }
+    }
+}
+
+impl std::error::Error for CborError {}
+
+/// Appends the shortest head of the given major type and argument.
+fn write_head(out: &mut Vec<u8>, major: u8, arg: u64) {
+    let major = major << 5;
+    match arg {
+        0..=23 => out.push(major | arg as u8),
+        24..=0xff => out.extend([major | 24, arg as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend((arg as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend((arg as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend(arg.to_be_bytes());
+        }
+    }
+}
+
+/// Reads a head, returning its major type, its argument and the bytes after it.
+fn read_head(bytes: &[u8]) -> Result<(u8, u64, &[u8]), CborError> {
+    let (&initial, rest) = bytes.split_first().ok_or(CborError::Truncated)?;
+    let len = match initial & 0x1f {
+        0..=23 => return Ok((initial >> 5, (initial & 0x1f) as u64, rest)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        // Reserved values, and the indefinite lengths.
+        _ => return Err(CborError::UnexpectedItem { initial }),
+    };
+    let arg = rest.get(..len).ok_or(CborError::Truncated)?;
+    let arg = arg.iter().fold(0u64, |acc, &b| acc << 8 | b as u64);
+    Ok((initial >> 5, arg, &rest[len..]))
+}
+
+impl UInt256 {
+    /// Returns the CBOR encoding of the value: an unsigned integer if it fits in 64 bits,
+    /// and otherwise a tag 2 bignum of its shortest big-endian bytes.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(1000).to_cbor(), [0x19, 0x03, 0xe8]);
+    /// let two_pow_64 = UInt256::from(u64::MAX as usize) + UInt256::ONE;
+    /// assert_eq!(two_pow_64.to_cbor(), [0xc2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]);
+    /// ```
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(2 + self.byte_len());
+        if self.high == 0 && self.low <= u64::MAX as u128 {
+            write_head(&mut out, UNSIGNED, self.low as u64);
+        } else {
+            let len = self.byte_len();
+            write_head(&mut out, TAG, TAG_UNSIGNED_BIGNUM);
+            write_head(&mut out, BYTES, len as u64);
+            out.extend_from_slice(&self.to_be_array()[32 - len..]);
+        }
+        out
+    }
+
+    /// Decodes a value from CBOR, as produced by [`Self::to_cbor`].
+    ///
+    /// `bytes` must hold exactly one unsigned integer or tag 2 bignum. As other decoders
+    /// do, longer heads than needed and leading zeros of a bignum are accepted, and so a
+    /// bignum for a value that fits in 64 bits is too. Indefinite-length byte strings are
+    /// not supported.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{CborError, UInt256};
+    ///
+    /// assert_eq!(UInt256::from_cbor(&[0x19, 0x03, 0xe8]), Ok(UInt256::from(1000)));
+    /// assert_eq!(UInt256::from_cbor(&[0xc2, 0x42, 0x03, 0xe8]), Ok(UInt256::from(1000)));
+    /// assert_eq!(UInt256::from_cbor(&[0x20]), Err(CborError::Negative));
+    /// ```
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, CborError> {
+        let initial = *bytes.first().ok_or(CborError::Truncated)?;
+        let (value, rest) = match read_head(bytes)? {
+            (UNSIGNED, arg, rest) => (UInt256::new(0, arg as u128, Endian::Big), rest),
+            (NEGATIVE, _, _) | (TAG, TAG_NEGATIVE_BIGNUM, _) => return Err(CborError::Negative),
+            (TAG, TAG_UNSIGNED_BIGNUM, rest) => {
+                let string_initial = *rest.first().ok_or(CborError::Truncated)?;
+                let (BYTES, len, rest) = read_head(rest)? else {
+                    return Err(CborError::UnexpectedItem { initial: string_initial });
+                };
+                let len = usize::try_from(len).map_err(|_| CborError::Truncated)?;
+                let string = rest.get(..len).ok_or(CborError::Truncated)?;
+                let digits = &string[string.iter().take_while(|&&b| b == 0).count()..];
+                if digits.len() > 32 {
+                    return Err(CborError::Overflow);
+                }
+                let mut padded = [0u8; 32];
+                padded[32 - digits.len()..].copy_from_slice(digits);
+                (UInt256::from_be_bytes(&padded), &rest[len..])
+            }
+            _ => return Err(CborError::UnexpectedItem { initial }),
+        };
+        if !rest.is_empty() {
+            return Err(CborError::TrailingBytes { len: rest.len() });
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{random_values, SEED};
+
+    #[test]
+    fn test_cbor_rfc_examples() {
+        // From Appendix A of RFC 8949.
+        let cases: [(u64, &[u8]); 8] = [
+            (0, &[0x00]),
+            (23, &[0x17]),
+            (24, &[0x18, 0x18]),
+            (100, &[0x18, 0x64]),
+            (1000, &[0x19, 0x03, 0xe8]),
+            (1_000_000, &[0x1a, 0x00, 0x0f, 0x42, 0x40]),
+            (1_000_000_000_000, &[0x1b, 0x00, 0x00, 0x00, 0xe8, 0xd4, 0xa5, 0x10, 0x00]),
+            (u64::MAX, &[0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff]),
+        ];
+        for (n, encoded) in cases {
+            let value = UInt256::new(0, n as u128, Endian::Big);
+            assert_eq!(value.to_cbor(), encoded, "{}", n);
+            assert_eq!(UInt256::from_cbor(encoded), Ok(value));
+        }
+        let two_pow_64 = UInt256::new(0, 1 << 64, Endian::Big);
+        let encoded = [0xc2, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(two_pow_64.to_cbor(), encoded);
+        assert_eq!(UInt256::from_cbor(&encoded), Ok(two_pow_64));
+
+        let negative = [0xc3, 0x49, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(UInt256::from_cbor(&negative), Err(CborError::Negative));
+        assert_eq!(UInt256::from_cbor(&[0x38, 0x63]), Err(CborError::Negative));
+        assert_eq!(CborError::Negative.to_string(), ConversionError::Negative.to_string());
+    }
+
+    #[test]
+    fn test_cbor_max() {
+        let mut encoded = vec![0xc2, 0x58, 0x20];
+        encoded.extend([0xff; 32]);
+        assert_eq!(UInt256::MAX.to_cbor(), encoded);
+        assert_eq!(UInt256::from_cbor(&encoded), Ok(UInt256::MAX));
+    }
+
+    #[test]
+    fn test_cbor_round_trip() {
+        for value in random_values(SEED, 200) {
+            let encoded = value.to_cbor();
+            assert_eq!(UInt256::from_cbor(&encoded), Ok(value), "{:x?}", encoded);
+        }
+    }
+
+    #[test]
+    fn test_cbor_lenient_forms() {
+        // A longer head than needed, and a bignum with leading zeros.
+        assert_eq!(UInt256::from_cbor(&[0x19, 0x00, 0x01]), Ok(UInt256::ONE));
+        assert_eq!(UInt256::from_cbor(&[0xc2, 0x43, 0x00, 0x00, 0x01]), Ok(UInt256::ONE));
+        assert_eq!(UInt256::from_cbor(&[0xc2, 0x40]), Ok(UInt256::ZERO));
+        let mut padded = vec![0xc2, 0x58, 0x21, 0x00];
+        padded.extend([0xff; 32]);
+        assert_eq!(UInt256::from_cbor(&padded), Ok(UInt256::MAX));
+    }
+
+    #[test]
+    fn test_cbor_errors() {
+        assert_eq!(UInt256::from_cbor(&[]), Err(CborError::Truncated));
+        assert_eq!(UInt256::from_cbor(&[0x19, 0x03]), Err(CborError::Truncated));
+        assert_eq!(UInt256::from_cbor(&[0xc2]), Err(CborError::Truncated));
+        assert_eq!(UInt256::from_cbor(&[0xc2, 0x42, 0x03]), Err(CborError::Truncated));
+        assert_eq!(UInt256::from_cbor(&[0x01, 0x02]), Err(CborError::TrailingBytes { len: 1 }));
+        assert_eq!(UInt256::from_cbor(&[0x1c]), Err(CborError::UnexpectedItem { initial: 0x1c }));
+        assert_eq!(UInt256::from_cbor(&[0x60]), Err(CborError::UnexpectedItem { initial: 0x60 }));
+        assert_eq!(UInt256::from_cbor(&[0xc1, 0x00]), Err(CborError::UnexpectedItem { initial: 0xc1 }));
+        assert_eq!(UInt256::from_cbor(&[0xc2, 0x01]), Err(CborError::UnexpectedItem { initial: 0x01 }));
+        // An indefinite-length byte string.
+        assert_eq!(UInt256::from_cbor(&[0xc2, 0x5f, 0xff]), Err(CborError::UnexpectedItem { initial: 0x5f }));
+
+        let mut too_large = vec![0xc2, 0x58, 0x21, 0x01];
+        too_large.extend([0x00; 32]);
+        assert_eq!(UInt256::from_cbor(&too_large), Err(CborError::Overflow));
+    }
+}
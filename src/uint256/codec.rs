@@ -0,0 +1,264 @@
+//! Binary encodings of [`UInt256`] for the serialization frameworks of other crates,
+//! each enabled by the feature of the same name:
+//!
+//! - `borsh`: `BorshSerialize` and `BorshDeserialize` as 32 little-endian bytes, the
+//!   layout Solana and NEAR programs use for 256-bit integers.
+//! - `rkyv`: `Archive`, `Serialize` and `Deserialize`, archived as the 32 little-endian
+//!   bytes `[u8; 32]`. The archive has no alignment requirement and reads the same on
+//!   every platform, and [`UInt256::from_le_array`] turns it back into a value.
+//! - `scale`: `Encode`, `Decode`, `MaxEncodedLen` and `TypeInfo` of the SCALE codec used
+//!   by Substrate, as a fixed 32-byte little-endian value described as `[u8; 32]`.
+//! - `ssz`: `ssz::Encode` and `ssz::Decode` of [`ethereum_ssz`](https://docs.rs/ethereum_ssz),
+//!   as the fixed-size `uint256` of the consensus specs, and [`UInt256::hash_tree_root`].
+//!
+//! Decoding cannot fail on the value itself, since every 32 bytes are a valid value,
+//! and gives big-endian values.
+
+use super::*;
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshSerialize for UInt256 {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.to_le_array())
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl borsh::BorshDeserialize for UInt256 {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let mut bytes = [0u8; 32];
+        reader.read_exact(&mut bytes)?;
+        Ok(UInt256 { endian: Endian::Big, ..UInt256::from_le_array(bytes) })
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl rkyv::Archive for UInt256 {
+    type Archived = [u8; 32];
+    type Resolver = ();
+
+    fn resolve(&self, _: (), out: rkyv::Place<[u8; 32]>) {
+        self.to_le_array().resolve([(); 32], out);
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<S: rkyv::rancor::Fallible + ?Sized> rkyv::Serialize<S> for UInt256 {
+    fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<D: rkyv::rancor::Fallible + ?Sized> rkyv::Deserialize<UInt256, D> for [u8; 32] {
+    fn deserialize(&self, _: &mut D) -> Result<UInt256, D::Error> {
+        Ok(UInt256 { endian: Endian::Big, ..UInt256::from_le_array(*self) })
+    }
+}
+
+#[cfg(feature = "scale")]
+impl parity_scale_codec::Encode for UInt256 {
+    fn size_hint(&self) -> usize {
+        32
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.to_le_array());
+    }
+
+    fn encoded_size(&self) -> usize {
+        32
+    }
+}
+
+#[cfg(feature = "scale")]
+impl parity_scale_codec::EncodeLike for UInt256 {}
+
+#[cfg(feature = "scale")]
+impl parity_scale_codec::Decode for UInt256 {
+    fn decode<I: parity_scale_codec::Input>(input: &mut I) -> Result<Self, parity_scale_codec::Error> {
+        let mut bytes = [0u8; 32];
+        input.read(&mut bytes)?;
+        Ok(UInt256 { endian: Endian::Big, ..UInt256::from_le_array(bytes) })
+    }
+}
+
+#[cfg(feature = "scale")]
+impl parity_scale_codec::MaxEncodedLen for UInt256 {
+    fn max_encoded_len() -> usize {
+        32
+    }
+}
+
+#[cfg(feature = "scale")]
+impl scale_info::TypeInfo for UInt256 {
+    type Identity = Self;
+
+    fn type_info() -> scale_info::Type {
+        scale_info::Type::builder()
+            .path(scale_info::Path::new("UInt256", "uint256"))
+            .composite(scale_info::build::Fields::unnamed().field(|f| f.ty::<[u8; 32]>().type_name("[u8; 32]")))
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl ssz::Encode for UInt256 {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        32
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.to_le_array());
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        32
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl ssz::Decode for UInt256 {
+    fn is_ssz_fixed_len() -> bool {
+        true
+    }
+
+    fn ssz_fixed_len() -> usize {
+        32
+    }
+
+    fn from_ssz_bytes(bytes: &[u8]) -> Result<Self, ssz::DecodeError> {
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| ssz::DecodeError::InvalidByteLength { len: bytes.len(), expected: 32 })?;
+        Ok(UInt256 { endian: Endian::Big, ..UInt256::from_le_array(bytes) })
+    }
+}
+
+#[cfg(feature = "ssz")]
+impl UInt256 {
+    /// Returns the SSZ `hash_tree_root` of the value as a `uint256`.
+    ///
+    /// A basic type that fills a whole 32-byte chunk is its own root, so this is the
+    /// little-endian bytes, and also the leaf the value contributes to the Merkle tree
+    /// of a container.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let root = UInt256::from(0x0102).hash_tree_root();
+    /// assert_eq!(root[..3], [0x02, 0x01, 0x00]);
+    /// ```
+    pub fn hash_tree_root(&self) -> [u8; 32] {
+        self.to_le_array()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::edge_values;
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_borsh() {
+        for value in edge_values() {
+            let encoded = borsh::to_vec(&value).unwrap();
+            assert_eq!(encoded, value.to_le_array());
+            let decoded: UInt256 = borsh::from_slice(&encoded).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(decoded.endian, Endian::Big);
+        }
+        // The same bytes as a pair of little-endian u128s, low half first.
+        let value = UInt256::new(7, 5, Endian::Big);
+        assert_eq!(borsh::to_vec(&value).unwrap(), borsh::to_vec(&(5u128, 7u128)).unwrap());
+
+        assert!(borsh::from_slice::<UInt256>(&[0; 31]).is_err());
+        assert!(borsh::from_slice::<UInt256>(&[0; 33]).is_err());
+    }
+
+    #[cfg(feature = "rkyv")]
+    #[test]
+    fn test_rkyv() {
+        use rkyv::rancor::Error;
+
+        #[derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize, Debug, PartialEq)]
+        struct Account {
+            id: u32,
+            balance: UInt256,
+        }
+
+        for value in edge_values() {
+            let bytes = rkyv::to_bytes::<Error>(&value).unwrap();
+            assert_eq!(bytes.as_slice(), value.to_le_array());
+            let archived = rkyv::access::<[u8; 32], Error>(&bytes).unwrap();
+            assert_eq!(UInt256::from_le_array(*archived), value);
+            let decoded = rkyv::deserialize::<UInt256, Error>(archived).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(decoded.endian, Endian::Big);
+
+            let account = Account { id: 7, balance: value };
+            let bytes = rkyv::to_bytes::<Error>(&account).unwrap();
+            let archived = rkyv::access::<ArchivedAccount, Error>(&bytes).unwrap();
+            assert_eq!(UInt256::from_le_array(archived.balance), value);
+            assert_eq!(rkyv::deserialize::<Account, Error>(archived).unwrap(), account);
+        }
+    }
+
+    #[cfg(feature = "scale")]
+    #[test]
+    fn test_scale() {
+        use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+        use scale_info::{TypeDef, TypeInfo};
+
+        for value in edge_values() {
+            let encoded = value.encode();
+            assert_eq!(encoded, value.to_le_array());
+            assert_eq!(value.encoded_size(), encoded.len());
+            let decoded = UInt256::decode(&mut &encoded[..]).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(decoded.endian, Endian::Big);
+        }
+        // A fixed width, so no compact length prefix even inside a tuple.
+        let pair = (1u8, UInt256::MAX).encode();
+        assert_eq!(pair.len(), 33);
+        assert_eq!(<(u8, UInt256)>::decode(&mut &pair[..]).unwrap(), (1, UInt256::MAX));
+        assert_eq!(UInt256::max_encoded_len(), 32);
+        assert!(UInt256::decode(&mut &[0u8; 31][..]).is_err());
+
+        let info = UInt256::type_info();
+        assert_eq!(info.path.segments, ["uint256", "UInt256"]);
+        let TypeDef::Composite(composite) = info.type_def else { panic!("not a composite: {:?}", info.type_def) };
+        assert_eq!(composite.fields.len(), 1);
+        assert_eq!(composite.fields[0].ty, scale_info::meta_type::<[u8; 32]>());
+    }
+
+    #[cfg(feature = "ssz")]
+    #[test]
+    fn test_ssz() {
+        use ssz::{Decode, DecodeError, Encode};
+
+        for value in edge_values() {
+            let encoded = value.as_ssz_bytes();
+            assert_eq!(encoded, value.to_le_array());
+            assert_eq!(value.hash_tree_root(), value.to_le_array());
+            let decoded = UInt256::from_ssz_bytes(&encoded).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(decoded.endian, Endian::Big);
+        }
+        // A list of fixed-size items is their concatenation.
+        let list = edge_values().to_vec();
+        let encoded = list.as_ssz_bytes();
+        assert_eq!(encoded.len(), 32 * list.len());
+        assert_eq!(Vec::<UInt256>::from_ssz_bytes(&encoded).unwrap(), list);
+
+        let err = DecodeError::InvalidByteLength { len: 31, expected: 32 };
+        assert_eq!(UInt256::from_ssz_bytes(&[0; 31]), Err(err));
+    }
+}
@@ -0,0 +1,532 @@
+//! Conversions between [`UInt256`] and other numeric types.
+
+use std::fmt;
+
+use super::*;
+
+/// The error returned when a value cannot be converted to or from a [`UInt256`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConversionError {
+    /// The value is negative, and a `UInt256` cannot represent it.
+    Negative,
+    /// The value does not fit in the target type.
+    Overflow,
+    /// The floating point value is NaN.
+    NotANumber,
+    /// The floating point value is not a whole number.
+    Fractional,
+    /// The byte slice is longer than the 32 bytes of a `UInt256`.
+    TooManyBytes {
+        /// The length of the slice.
+        len: usize,
+    },
+    /// The byte slice is not exactly 32 bytes long, where a fixed-width encoding needs it.
+    WrongLength {
+        /// The length of the slice.
+        len: usize,
+    },
+    /// The byte slice does not have the length its own length prefix declares.
+    LengthMismatch {
+        /// The length the prefix declares, including the prefix itself.
+        expected: usize,
+        /// The length of the slice.
+        len: usize,
+    },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConversionError::Negative => write!(f, "negative value cannot be converted to UInt256"),
+            ConversionError::Overflow => write!(f, "value is too large for the target type"),
+            ConversionError::NotANumber => write!(f, "NaN cannot be converted to UInt256"),
+            ConversionError::Fractional => write!(f, "value has a fractional part"),
+            ConversionError::TooManyBytes { len } => write!(f, "expected at most 32 bytes, got {}", len),
+            ConversionError::WrongLength { len } => write!(f, "expected 32 bytes, got {}", len),
+            ConversionError::LengthMismatch { expected, len } => {
+                write!(f, "length prefix declares {} bytes, got {}", expected, len)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Implements `TryFrom` for signed primitives, failing for negative values.
+macro_rules! impl_try_from_signed {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<$t> for UInt256 {
+                type Error = ConversionError;
+
+                fn try_from(value: $t) -> Result<Self, Self::Error> {
+                    if value < 0 {
+                        return Err(ConversionError::Negative);
+                    }
+                    Ok(UInt256 { high: 0, low: value as u128, endian: Endian::Big })
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Implements `TryFrom<UInt256>` for unsigned primitives, failing for values that do
+/// not fit.
+macro_rules! impl_try_into_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl TryFrom<UInt256> for $t {
+                type Error = ConversionError;
+
+                fn try_from(value: UInt256) -> Result<Self, Self::Error> {
+                    if value.high != 0 {
+                        return Err(ConversionError::Overflow);
+                    }
+                    <$t>::try_from(value.low).map_err(|_| ConversionError::Overflow)
+                }
+            }
+        )*
+    };
+}
+
+impl_try_into_unsigned!(u8, u16, u32, u64, u128);
+
+impl UInt256 {
+    /// Returns the value as a `u32`, or `None` if it does not fit.
+    pub fn to_u32(&self) -> Option<u32> {
+        u32::try_from(*self).ok()
+    }
+
+    /// Returns the value as a `u64`, or `None` if it does not fit.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ConversionError, UInt256};
+    ///
+    /// assert_eq!(UInt256::from(21_000).to_u64(), Some(21_000));
+    /// assert_eq!(UInt256::MAX.to_u64(), None);
+    /// assert_eq!(u8::try_from(UInt256::from(256)), Err(ConversionError::Overflow));
+    /// ```
+    pub fn to_u64(&self) -> Option<u64> {
+        u64::try_from(*self).ok()
+    }
+
+    /// Returns the value as a `u128`, or `None` if it does not fit.
+    pub fn to_u128(&self) -> Option<u128> {
+        u128::try_from(*self).ok()
+    }
+}
+
+impl UInt256 {
+    /// Returns the nearest `f64` to the value, rounding ties to even.
+    ///
+    /// Values above 2^53 are generally not exactly representable and lose precision.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(1_000_000).to_f64(), 1e6);
+    /// assert_eq!(UInt256::MAX.to_f64(), 2f64.powi(256));
+    /// ```
+    pub fn to_f64(&self) -> f64 {
+        let bits = 256 - self.leading_zeros();
+        if bits <= 64 {
+            return self.low as u64 as f64;
+        }
+        // Keep the top 64 bits, and fold the rest into the lowest one so that the
+        // conversion of the `u64` still rounds as if it saw every bit.
+        let shift = bits - 64;
+        let mut top = self.unbounded_shr(shift).low as u64;
+        if !self.bits(..shift as usize).is_zero() {
+            top |= 1;
+        }
+        top as f64 * 2f64.powi(shift as i32)
+    }
+
+    /// Returns the nearest `f32` to the value, rounding ties to even, or infinity if
+    /// it is out of the range of `f32`.
+    pub fn to_f32(&self) -> f32 {
+        if self.high == 0 {
+            self.low as f32
+        } else {
+            f32::INFINITY
+        }
+    }
+}
+
+impl UInt256 {
+    /// Reads `self` as a two's complement signed 256-bit integer, like an EVM
+    /// `int256`, and returns its sign and magnitude.
+    ///
+    /// Values with the top bit set are negative. The magnitude of the most negative
+    /// value, -2^255, is 2^255.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Sign, UInt256};
+    ///
+    /// assert_eq!(UInt256::MAX.interpret_as_signed(), (Sign::Negative, UInt256::ONE));
+    /// assert_eq!(UInt256::from(5).interpret_as_signed(), (Sign::Positive, UInt256::from(5)));
+    /// ```
+    pub fn interpret_as_signed(&self) -> (Sign, Self) {
+        if self.high >> 127 == 1 {
+            (Sign::Negative, self.wrapping_neg())
+        } else {
+            (Sign::Positive, *self)
+        }
+    }
+
+    /// Encodes a sign and magnitude as a two's complement signed 256-bit integer.
+    ///
+    /// This is the inverse of [`Self::interpret_as_signed`]. Returns `None` if the
+    /// value is outside of `[-2^255, 2^255 - 1]`. A negative zero is encoded as zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Sign, UInt256};
+    ///
+    /// let minus_one = UInt256::from_signed_magnitude(Sign::Negative, UInt256::ONE);
+    /// assert_eq!(minus_one, Some(UInt256::MAX));
+    /// assert_eq!(UInt256::from_signed_magnitude(Sign::Positive, UInt256::MAX), None);
+    /// ```
+    pub fn from_signed_magnitude(sign: Sign, magnitude: Self) -> Option<Self> {
+        let min_magnitude = UInt256::ONE << 255;
+        match sign {
+            Sign::Positive if magnitude < min_magnitude => Some(magnitude),
+            Sign::Negative if magnitude <= min_magnitude => Some(magnitude.wrapping_neg()),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a whole, non-negative number below 2^256.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::{ConversionError, UInt256};
+///
+/// assert_eq!(UInt256::try_from(1e18), Ok(UInt256::from(1_000_000_000_000_000_000)));
+/// assert_eq!(UInt256::try_from(0.5), Err(ConversionError::Fractional));
+/// assert_eq!(UInt256::try_from(f64::NAN), Err(ConversionError::NotANumber));
+/// ```
+impl TryFrom<f64> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if value.is_nan() {
+            return Err(ConversionError::NotANumber);
+        }
+        if value < 0.0 {
+            return Err(ConversionError::Negative);
+        }
+        if value >= 2f64.powi(256) {
+            return Err(ConversionError::Overflow);
+        }
+        if value.fract() != 0.0 {
+            return Err(ConversionError::Fractional);
+        }
+        if value == 0.0 {
+            return Ok(UInt256::ZERO);
+        }
+        // A whole number of at least 1 is normal, so it is `mantissa * 2^exponent`
+        // with the implicit leading bit of the mantissa set.
+        let bits = value.to_bits();
+        let exponent = ((bits >> 52) & 0x7ff) as i32 - 1075;
+        let mantissa: u64 = (bits & ((1 << 52) - 1)) | 1 << 52;
+        let mantissa = UInt256::from_parts(0, mantissa as u128);
+        if exponent >= 0 {
+            Ok(mantissa << exponent)
+        } else {
+            Ok(mantissa >> -exponent)
+        }
+    }
+}
+
+/// Converts a whole, non-negative number, like the conversion from `f64`.
+impl TryFrom<f32> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        UInt256::try_from(value as f64)
+    }
+}
+
+/// Reads the bytes in big-endian order, like [`UInt256::from_be_bytes`].
+impl From<[u8; 32]> for UInt256 {
+    fn from(bytes: [u8; 32]) -> Self {
+        UInt256::from_be_bytes(&bytes)
+    }
+}
+
+/// Writes the bytes in big-endian order, like [`UInt256::to_be_array`].
+impl From<UInt256> for [u8; 32] {
+    fn from(value: UInt256) -> Self {
+        value.to_be_array()
+    }
+}
+
+impl UInt256 {
+    /// Builds a value from up to 32 bytes in big-endian order.
+    ///
+    /// Shorter slices are treated as if padded with leading zeros.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ConversionError, UInt256};
+    ///
+    /// assert_eq!(UInt256::from_be_slice(&[0x12, 0x34]), Ok(UInt256::from(0x1234)));
+    /// assert_eq!(UInt256::from_be_slice(&[]), Ok(UInt256::ZERO));
+    /// assert_eq!(UInt256::from_be_slice(&[0; 33]), Err(ConversionError::TooManyBytes { len: 33 }));
+    /// ```
+    pub fn from_be_slice(bytes: &[u8]) -> Result<Self, ConversionError> {
+        if bytes.len() > 32 {
+            return Err(ConversionError::TooManyBytes { len: bytes.len() });
+        }
+        let mut padded = [0u8; 32];
+        padded[32 - bytes.len()..].copy_from_slice(bytes);
+        Ok(UInt256::from_be_bytes(&padded))
+    }
+
+    /// Builds a value from up to 32 bytes in little-endian order.
+    ///
+    /// Shorter slices are treated as if padded with trailing zeros.
+    pub fn from_le_slice(bytes: &[u8]) -> Result<Self, ConversionError> {
+        if bytes.len() > 32 {
+            return Err(ConversionError::TooManyBytes { len: bytes.len() });
+        }
+        Ok(UInt256::from_le_bytes(bytes))
+    }
+}
+
+/// Reads up to 32 bytes in big-endian order, like [`UInt256::from_be_slice`].
+impl TryFrom<&[u8]> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        UInt256::from_be_slice(bytes)
+    }
+}
+
+/// Reads up to 32 bytes in big-endian order, like [`UInt256::from_be_slice`].
+impl TryFrom<Vec<u8>> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        UInt256::from_be_slice(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_try_from_signed() {
+        assert_eq!(UInt256::try_from(0i8), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::try_from(i16::MAX), Ok(UInt256::from(i16::MAX as usize)));
+        assert_eq!(UInt256::try_from(42i32), Ok(UInt256::from(42)));
+        assert_eq!(UInt256::try_from(i64::MAX), Ok(UInt256::from_parts(0, i64::MAX as u128)));
+        assert_eq!(UInt256::try_from(i128::MAX), Ok(UInt256::new(0, i128::MAX as u128, Endian::Big)));
+        assert_eq!(UInt256::try_from(7isize), Ok(UInt256::from(7)));
+
+        assert_eq!(UInt256::try_from(-1i8), Err(ConversionError::Negative));
+        assert_eq!(UInt256::try_from(i32::MIN), Err(ConversionError::Negative));
+        assert_eq!(UInt256::try_from(i128::MIN), Err(ConversionError::Negative));
+        assert_eq!(UInt256::try_from(-1isize), Err(ConversionError::Negative));
+        assert_eq!(
+            ConversionError::Negative.to_string(),
+            "negative value cannot be converted to UInt256"
+        );
+    }
+
+    #[test]
+    fn test_try_into_unsigned() {
+        assert_eq!(u8::try_from(UInt256::from(255)), Ok(255));
+        assert_eq!(u8::try_from(UInt256::from(256)), Err(ConversionError::Overflow));
+        assert_eq!(u16::try_from(UInt256::from(u16::MAX as usize)), Ok(u16::MAX));
+        assert_eq!(u16::try_from(UInt256::from(1 << 16)), Err(ConversionError::Overflow));
+        assert_eq!(u32::try_from(UInt256::ZERO), Ok(0));
+        assert_eq!(u64::try_from(UInt256::from(usize::MAX)), Ok(usize::MAX as u64));
+        assert_eq!(u128::try_from(UInt256::new(0, u128::MAX, Endian::Little)), Ok(u128::MAX));
+        assert_eq!(u128::try_from(UInt256::new(1, 0, Endian::Big)), Err(ConversionError::Overflow));
+        assert_eq!(u8::try_from(UInt256::new(1, 5, Endian::Big)), Err(ConversionError::Overflow));
+
+        let value = UInt256::new(0, (1 << 64) + 1, Endian::Big);
+        assert_eq!(value.to_u32(), None);
+        assert_eq!(value.to_u64(), None);
+        assert_eq!(value.to_u128(), Some((1 << 64) + 1));
+        assert_eq!(UInt256::from(7).to_u32(), Some(7));
+        assert_eq!(UInt256::MAX.to_u128(), None);
+    }
+
+    #[test]
+    fn test_byte_arrays() {
+        let bytes: [u8; 32] = std::array::from_fn(|i| i as u8 + 1);
+        let value = UInt256::from(bytes);
+        assert_eq!(value, UInt256::from_be_bytes(&bytes));
+        assert_eq!(value.endian(), Endian::Big);
+        assert_eq!(<[u8; 32]>::from(value), bytes);
+        assert_eq!(value.to_be_array(), *value.to_be_bytes());
+
+        let le = UInt256::from_le_array(bytes);
+        assert_eq!(le, UInt256::from_le_bytes(&bytes));
+        assert_eq!(le.endian(), Endian::Little);
+        assert_eq!(le.to_le_array(), bytes);
+        assert_eq!(le.to_le_array().to_vec(), le.to_le_bytes());
+
+        let mut reversed = bytes;
+        reversed.reverse();
+        assert_eq!(UInt256::from_le_array(reversed), value);
+        assert_eq!(value.to_le_array(), reversed);
+    }
+
+    #[test]
+    fn test_byte_slices() {
+        let bytes: Vec<u8> = (1..=32).collect();
+        let full = UInt256::from_be_bytes(&bytes.clone().try_into().unwrap());
+        for len in 0..=32 {
+            let be = UInt256::from_be_slice(&bytes[32 - len..]).unwrap();
+            assert_eq!(be, full.bits(..8 * len), "{} bytes", len);
+            assert_eq!(be.endian(), Endian::Big);
+            let le = UInt256::from_le_slice(&bytes[..len]).unwrap();
+            assert_eq!(le.to_le_array()[..len], bytes[..len]);
+            assert_eq!(le.endian(), Endian::Little);
+        }
+
+        assert_eq!(UInt256::try_from(&bytes[..]), Ok(full));
+        assert_eq!(UInt256::try_from(bytes.clone()), Ok(full));
+        assert_eq!(UInt256::try_from(vec![0x01, 0x00]), Ok(UInt256::from(0x100)));
+
+        let long = vec![0u8; 40];
+        assert_eq!(UInt256::from_be_slice(&long), Err(ConversionError::TooManyBytes { len: 40 }));
+        assert_eq!(UInt256::from_le_slice(&long), Err(ConversionError::TooManyBytes { len: 40 }));
+        assert_eq!(UInt256::try_from(long), Err(ConversionError::TooManyBytes { len: 40 }));
+        assert_eq!(
+            ConversionError::TooManyBytes { len: 33 }.to_string(),
+            "expected at most 32 bytes, got 33"
+        );
+    }
+
+    #[test]
+    fn test_limbs_and_words() {
+        let value = UInt256::new(
+            0x0123_4567_89ab_cdef_0011_2233_4455_6677,
+            0x8899_aabb_ccdd_eeff_fedc_ba98_7654_3210,
+            Endian::Big,
+        );
+        let limbs = value.to_limbs();
+        assert_eq!(
+            limbs,
+            [0xfedc_ba98_7654_3210, 0x8899_aabb_ccdd_eeff, 0x0011_2233_4455_6677, 0x0123_4567_89ab_cdef]
+        );
+        assert_eq!(UInt256::from_limbs(limbs), value);
+
+        let words = value.as_words();
+        assert_eq!(words[0], 0x7654_3210);
+        assert_eq!(words[1], 0xfedc_ba98);
+        assert_eq!(words[7], 0x0123_4567);
+        assert_eq!(UInt256::from_words(words), value);
+        assert_eq!(UInt256::MAX.as_words(), [u32::MAX; 8]);
+        assert_eq!(UInt256::from_limbs([0; 4]), UInt256::ZERO);
+    }
+
+    #[test]
+    fn test_to_float() {
+        assert_eq!(UInt256::ZERO.to_f64(), 0.0);
+        assert_eq!(UInt256::from_parts(0, u64::MAX as u128).to_f64(), u64::MAX as f64);
+        assert_eq!(UInt256::new(0, u128::MAX, Endian::Big).to_f64(), u128::MAX as f64);
+        for shift in [53, 64, 100, 127, 128, 200, 255] {
+            assert_eq!((UInt256::ONE << shift).to_f64(), 2f64.powi(shift));
+        }
+
+        // Rounding just above 2^100: halfway cases go to even, anything above rounds up.
+        let base = UInt256::ONE << 100;
+        let ulp = UInt256::ONE << 48;
+        let half = UInt256::ONE << 47;
+        assert_eq!((base + half).to_f64(), 2f64.powi(100));
+        assert_eq!((base + half + UInt256::ONE).to_f64(), 2f64.powi(100) + 2f64.powi(48));
+        assert_eq!((base + ulp + half).to_f64(), 2f64.powi(100) + 2f64.powi(49));
+        assert_eq!((base + half - UInt256::ONE).to_f64(), 2f64.powi(100));
+
+        assert_eq!(UInt256::from(1 << 24).to_f32(), 16_777_216.0);
+        assert_eq!(UInt256::new(0, u128::MAX, Endian::Big).to_f32(), u128::MAX as f32);
+        assert_eq!(UInt256::new(1, 0, Endian::Big).to_f32(), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_try_from_float() {
+        assert_eq!(UInt256::try_from(0.0), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::try_from(-0.0), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::try_from(1.0), Ok(UInt256::ONE));
+        assert_eq!(UInt256::try_from(12345.0f32), Ok(UInt256::from(12345)));
+        assert_eq!(UInt256::try_from(2f64.powi(255)), Ok(UInt256::ONE << 255));
+        let largest = UInt256::MAX - ((UInt256::ONE << 203) - UInt256::ONE);
+        assert_eq!(UInt256::try_from(2f64.powi(256) - 2f64.powi(203)), Ok(largest));
+        assert_eq!(UInt256::try_from(u128::MAX as f64), Ok(UInt256::new(1, 0, Endian::Big)));
+
+        assert_eq!(UInt256::try_from(f64::NAN), Err(ConversionError::NotANumber));
+        assert_eq!(UInt256::try_from(-1.0), Err(ConversionError::Negative));
+        assert_eq!(UInt256::try_from(-0.5f32), Err(ConversionError::Negative));
+        assert_eq!(UInt256::try_from(f64::NEG_INFINITY), Err(ConversionError::Negative));
+        assert_eq!(UInt256::try_from(f64::INFINITY), Err(ConversionError::Overflow));
+        assert_eq!(UInt256::try_from(2f64.powi(256)), Err(ConversionError::Overflow));
+        assert_eq!(UInt256::try_from(1.5), Err(ConversionError::Fractional));
+        assert_eq!(UInt256::try_from(f64::MIN_POSITIVE), Err(ConversionError::Fractional));
+
+        // Every value that fits round-trips through `f64` when it is exactly representable.
+        for shift in 0..=203 {
+            let value = UInt256::from_parts(0, (1 << 53) - 1) << shift;
+            assert_eq!(UInt256::try_from(value.to_f64()), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_signed_reinterpretation() {
+        let min = UInt256::ONE << 255;
+        let max = min - UInt256::ONE;
+        // (two's complement word, sign, magnitude)
+        let cases = [
+            (UInt256::ZERO, Sign::Positive, UInt256::ZERO),
+            (UInt256::ONE, Sign::Positive, UInt256::ONE),
+            (max, Sign::Positive, max),
+            (min, Sign::Negative, min),
+            (min + UInt256::ONE, Sign::Negative, max),
+            (UInt256::MAX - UInt256::from(41), Sign::Negative, UInt256::from(42)),
+            (UInt256::MAX, Sign::Negative, UInt256::ONE),
+        ];
+        for (word, sign, magnitude) in cases {
+            assert_eq!(word.interpret_as_signed(), (sign, magnitude), "{:?}", word);
+            assert_eq!(UInt256::from_signed_magnitude(sign, magnitude), Some(word));
+        }
+
+        assert_eq!(UInt256::from_signed_magnitude(Sign::Negative, UInt256::ZERO), Some(UInt256::ZERO));
+        assert_eq!(UInt256::from_signed_magnitude(Sign::Positive, min), None);
+        assert_eq!(UInt256::from_signed_magnitude(Sign::Negative, min + UInt256::ONE), None);
+        assert_eq!(UInt256::from_signed_magnitude(Sign::Negative, UInt256::MAX), None);
+    }
+
+    #[test]
+    fn test_parts() {
+        let value = UInt256::new(u128::MAX, 7, Endian::Little);
+        assert_eq!(value.into_parts(), (u128::MAX, 7));
+        assert_eq!(value.high(), u128::MAX);
+        assert_eq!(value.low(), 7);
+        assert_eq!(UInt256::from_parts(u128::MAX, 7), value);
+        assert_eq!(UInt256::from_parts(0, 0), UInt256::ZERO);
+        assert_eq!(UInt256::from_parts(0, 1).endian(), Endian::Big);
+
+        const TWO_128: UInt256 = UInt256::from_parts(1, 0);
+        assert_eq!(TWO_128, UInt256::ONE << 128);
+    }
+}
@@ -0,0 +1,326 @@
+//! Constant-time comparisons and selection for secret [`UInt256`] values.
+//!
+//! The regular `PartialEq` and `Ord` implementations return as soon as the result is
+//! known, so their running time depends on the values compared. The functions here
+//! always look at every limb and never branch on the data.
+
+use std::hint::black_box;
+use std::ops::{BitAnd, BitOr, Not};
+
+use super::*;
+
+/// The result of a constant-time comparison, either 0 (false) or 1 (true).
+///
+/// Unlike `bool`, a `Choice` is meant to be combined with the bitwise operators and
+/// only turned into a `bool` once the secret-dependent computation is done.
+///
+/// # Which APIs are constant time
+///
+/// Branch-free with respect to the values of their operands:
+///
+/// - [`UInt256::ct_eq`], [`UInt256::ct_lt`], [`UInt256::conditional_select`] and
+///   [`UInt256::conditional_swap`].
+/// - [`UInt256::overflowing_add`], [`UInt256::wrapping_add`], [`UInt256::overflowing_sub`],
+///   [`UInt256::wrapping_sub`], [`UInt256::wrapping_neg`], [`UInt256::widening_mul`] and
+///   [`UInt256::wrapping_mul`].
+/// - [`MontgomeryContext::to_montgomery`], [`MontgomeryContext::from_montgomery`] and
+///   [`MontgomeryContext::mul`].
+/// - [`BarrettReducer::reduce`] and [`BarrettReducer::mul_mod`].
+/// - [`MontgomeryContext::pow`], but only with the `ct` feature enabled. Without it the
+///   running time depends on the exponent.
+///
+/// The moduli of [`MontgomeryContext`] and [`BarrettReducer`] are treated as public.
+///
+/// Everything else is variable time, notably the comparison operators, the operators
+/// that panic on overflow, division and remainder, the `mod_*` methods (which divide),
+/// `gcd`, `mod_inv`, primality testing, parsing and formatting.
+///
+/// # The `ct` feature
+///
+/// Enabling `ct` makes [`MontgomeryContext::pow`] constant time and implements the
+/// [`subtle`](https://docs.rs/subtle) traits `ConstantTimeEq`, `ConstantTimeGreater`,
+/// `ConstantTimeLess` and `ConditionallySelectable` for [`UInt256`]. [`Choice`] converts
+/// to and from `subtle::Choice`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Choice(u8);
+
+impl Choice {
+    /// Returns the value as a `u8`, which is 0 or 1.
+    pub fn unwrap_u8(&self) -> u8 {
+        self.0
+    }
+
+    /// Builds a `Choice` from the lowest bit of `bit`, which must be 0 or 1.
+    fn from_bit(bit: u64) -> Self {
+        // Keep the optimizer from turning mask arithmetic on the result into branches.
+        Choice(black_box(bit as u8 & 1))
+    }
+
+    /// Returns all ones if the choice is 1 and all zeros if it is 0.
+    fn mask(self) -> u128 {
+        0u128.wrapping_sub(black_box(self.0) as u128)
+    }
+}
+
+impl From<u8> for Choice {
+    /// Converts 0 or 1 into a `Choice`. Other values are not allowed.
+    fn from(value: u8) -> Self {
+        debug_assert!(value <= 1, "Choice must be 0 or 1");
+        Choice::from_bit(value as u64)
+    }
+}
+
+impl From<Choice> for bool {
+    fn from(choice: Choice) -> bool {
+        choice.0 != 0
+    }
+}
+
+impl Not for Choice {
+    type Output = Choice;
+
+    fn not(self) -> Choice {
+        Choice::from_bit(self.0 as u64 ^ 1)
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Choice;
+
+    fn bitand(self, rhs: Choice) -> Choice {
+        Choice::from_bit((self.0 & rhs.0) as u64)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Choice;
+
+    fn bitor(self, rhs: Choice) -> Choice {
+        Choice::from_bit((self.0 | rhs.0) as u64)
+    }
+}
+
+impl UInt256 {
+    /// Returns whether `self` equals `other`, in constant time.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let secret = UInt256::from(42);
+    /// assert!(bool::from(secret.ct_eq(&UInt256::from(42))));
+    /// assert!(!bool::from(secret.ct_eq(&UInt256::from(43))));
+    /// ```
+    pub fn ct_eq(&self, other: &Self) -> Choice {
+        let a = self.to_limbs();
+        let b = other.to_limbs();
+        let diff = a.iter().zip(&b).fold(0u64, |acc, (x, y)| acc | (x ^ y));
+        // The top bit of `diff | -diff` is set exactly when `diff` is non-zero.
+        Choice::from_bit(((diff | diff.wrapping_neg()) >> 63) ^ 1)
+    }
+
+    /// Returns whether `self` is less than `other`, in constant time.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let a = UInt256::from(7);
+    /// assert!(bool::from(a.ct_lt(&UInt256::MAX)));
+    /// assert!(!bool::from(a.ct_lt(&a)));
+    /// ```
+    pub fn ct_lt(&self, other: &Self) -> Choice {
+        // self < other exactly when `self - other` borrows out of the top limb.
+        let a = self.to_limbs();
+        let b = other.to_limbs();
+        let mut borrow = 0u64;
+        for (x, y) in a.iter().zip(&b) {
+            let (d, b1) = x.overflowing_sub(*y);
+            let (_, b2) = d.overflowing_sub(borrow);
+            borrow = (b1 | b2) as u64;
+        }
+        Choice::from_bit(borrow)
+    }
+
+    /// Returns `a` if `choice` is 0 and `b` if it is 1, without branching on `choice`.
+    ///
+    /// The result keeps the endianness of `a`, since endianness is not secret.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Choice, UInt256};
+    ///
+    /// let a = UInt256::from(1);
+    /// let b = UInt256::from(2);
+    /// assert_eq!(UInt256::conditional_select(&a, &b, Choice::from(0)), a);
+    /// assert_eq!(UInt256::conditional_select(&a, &b, Choice::from(1)), b);
+    /// ```
+    pub fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mask = choice.mask();
+        UInt256 {
+            high: a.high ^ (mask & (a.high ^ b.high)),
+            low: a.low ^ (mask & (a.low ^ b.low)),
+            endian: a.endian,
+        }
+    }
+
+    /// Swaps the values of `a` and `b` if `choice` is 1, without branching on `choice`.
+    ///
+    /// Both keep their own endianness.
+    ///
+    /// ## Examples
+    ///
+    /// A Montgomery ladder computes `base^exponent mod m` with the same sequence of
+    /// operations for every exponent of the same bit length:
+    ///
+    /// ```rust
+    /// use uint256::{Choice, UInt256};
+    ///
+    /// let m = UInt256::from(1_000_000_007);
+    /// let base = UInt256::from(12345);
+    /// let exponent: u8 = 0b1011_0110;
+    ///
+    /// let (mut r0, mut r1) = (UInt256::ONE, base);
+    /// for i in (0..8).rev() {
+    ///     let bit = Choice::from((exponent >> i) & 1);
+    ///     UInt256::conditional_swap(&mut r0, &mut r1, bit);
+    ///     r1 = r0.mod_mul(r1, m);
+    ///     r0 = r0.mod_mul(r0, m);
+    ///     UInt256::conditional_swap(&mut r0, &mut r1, bit);
+    /// }
+    /// assert_eq!(r0, base.mod_pow(UInt256::from(exponent as usize), m));
+    /// ```
+    pub fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        let mask = choice.mask();
+        let high = mask & (a.high ^ b.high);
+        let low = mask & (a.low ^ b.low);
+        a.high ^= high;
+        a.low ^= low;
+        b.high ^= high;
+        b.low ^= low;
+    }
+}
+
+#[cfg(feature = "ct")]
+impl From<subtle::Choice> for Choice {
+    fn from(choice: subtle::Choice) -> Self {
+        Choice::from_bit(choice.unwrap_u8() as u64)
+    }
+}
+
+#[cfg(feature = "ct")]
+impl From<Choice> for subtle::Choice {
+    fn from(choice: Choice) -> Self {
+        subtle::Choice::from(choice.0)
+    }
+}
+
+#[cfg(feature = "ct")]
+impl subtle::ConstantTimeEq for UInt256 {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        UInt256::ct_eq(self, other).into()
+    }
+}
+
+#[cfg(feature = "ct")]
+impl subtle::ConstantTimeGreater for UInt256 {
+    fn ct_gt(&self, other: &Self) -> subtle::Choice {
+        UInt256::ct_lt(other, self).into()
+    }
+}
+
+#[cfg(feature = "ct")]
+impl subtle::ConstantTimeLess for UInt256 {}
+
+#[cfg(feature = "ct")]
+impl subtle::ConditionallySelectable for UInt256 {
+    fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+        UInt256::conditional_select(a, b, choice.into())
+    }
+
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: subtle::Choice) {
+        UInt256::conditional_swap(a, b, choice.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::edge_values;
+
+    #[test]
+    fn test_ct_eq_matches_eq() {
+        for a in edge_values() {
+            for b in edge_values() {
+                assert_eq!(bool::from(a.ct_eq(&b)), a == b, "{:?} == {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ct_lt_matches_lt() {
+        for a in edge_values() {
+            for b in edge_values() {
+                assert_eq!(bool::from(a.ct_lt(&b)), a < b, "{:?} < {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditional_select() {
+        for a in edge_values() {
+            for b in edge_values() {
+                assert_eq!(UInt256::conditional_select(&a, &b, Choice::from(0)), a);
+                assert_eq!(UInt256::conditional_select(&a, &b, Choice::from(1)), b);
+                let selected = UInt256::conditional_select(&a, &b, Choice::from(1));
+                assert_eq!(selected.endian, a.endian);
+            }
+        }
+    }
+
+    #[test]
+    fn test_conditional_swap() {
+        for a in edge_values() {
+            for b in edge_values() {
+                let (mut x, mut y) = (a, b);
+                UInt256::conditional_swap(&mut x, &mut y, Choice::from(0));
+                assert_eq!((x, y), (a, b));
+                UInt256::conditional_swap(&mut x, &mut y, Choice::from(1));
+                assert_eq!((x, y), (b, a));
+            }
+        }
+    }
+
+    #[test]
+    fn test_choice_ops() {
+        let yes = Choice::from(1);
+        let no = Choice::from(0);
+        assert_eq!((!yes).unwrap_u8(), 0);
+        assert_eq!((!no).unwrap_u8(), 1);
+        assert_eq!(yes & no, no);
+        assert_eq!(yes | no, yes);
+        assert!(bool::from(yes & yes));
+    }
+
+    #[cfg(feature = "ct")]
+    #[test]
+    fn test_subtle_traits() {
+        use subtle::{ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater, ConstantTimeLess};
+
+        for a in edge_values() {
+            for b in edge_values() {
+                assert_eq!(bool::from(ConstantTimeEq::ct_eq(&a, &b)), a == b);
+                assert_eq!(bool::from(ConstantTimeGreater::ct_gt(&a, &b)), a > b);
+                assert_eq!(bool::from(ConstantTimeLess::ct_lt(&a, &b)), a < b);
+                let choice = subtle::Choice::from(1);
+                assert_eq!(<UInt256 as ConditionallySelectable>::conditional_select(&a, &b, choice), b);
+            }
+        }
+        assert_eq!(Choice::from(subtle::Choice::from(1)).unwrap_u8(), 1);
+        assert_eq!(subtle::Choice::from(Choice::from(0)).unwrap_u8(), 0);
+    }
+}
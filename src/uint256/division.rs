@@ -0,0 +1,509 @@
+//! Limb-based long division and the operations built on top of it.
+
+use super::*;
+
+/// Divides the little-endian 64-bit limbs of `u` (at most 8 limbs) by the non-zero
+/// divisor `v` and returns the quotient and the remainder limbs.
+///
+/// # Panics
+///
+/// Panics if `v` is zero.
+pub(crate) fn div_rem_limbs(u: &[u64], v: &[u64; 4]) -> ([u64; 8], [u64; 4]) {
+    NormalizedDivisor::new(v).div_rem(u)
+}
+
+/// A divisor prepared for Knuth's Algorithm D (TAOCP Vol. 2, 4.3.1).
+///
+/// The divisor is normalized so that its top bit is set, which keeps each estimated
+/// quotient digit within two of the true value, and the estimate itself is computed
+/// with the reciprocal of the top limb instead of a hardware division.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NormalizedDivisor {
+    /// The divisor limbs shifted left by `shift` bits.
+    limbs: [u64; 4],
+    /// The number of significant limbs.
+    len: usize,
+    /// The number of leading zeros of the most significant limb of the original divisor.
+    shift: u32,
+    /// The reciprocal of the most significant normalized limb, or of the whole
+    /// divisor if it fits in a single limb.
+    top: LimbReciprocal,
+}
+
+impl NormalizedDivisor {
+    /// # Panics
+    ///
+    /// Panics if `v` is zero.
+    pub(crate) fn new(v: &[u64; 4]) -> Self {
+        let len = significant_limbs(v);
+        if len == 0 {
+            panic!("division by zero");
+        }
+        if len == 1 {
+            return NormalizedDivisor { limbs: *v, len, shift: 0, top: LimbReciprocal::new(v[0]) };
+        }
+
+        let shift = v[len - 1].leading_zeros();
+        let mut limbs = [0u64; 4];
+        shl_limbs(&v[..len], shift, &mut limbs[..len]);
+        NormalizedDivisor { limbs, len, shift, top: LimbReciprocal::new(limbs[len - 1]) }
+    }
+
+    /// Divides the little-endian limbs `u` (at most 8 limbs) by the divisor.
+    pub(crate) fn div_rem(&self, u: &[u64]) -> ([u64; 8], [u64; 4]) {
+        assert!(u.len() <= 8, "dividend is wider than 512 bits");
+
+        let n = self.len;
+        let m = significant_limbs(u);
+
+        let mut quotient = [0u64; 8];
+        let mut remainder = [0u64; 4];
+
+        if m < n {
+            remainder[..m].copy_from_slice(&u[..m]);
+            return (quotient, remainder);
+        }
+
+        if n == 1 {
+            remainder[0] = self.top.div_rem(&u[..m], &mut quotient[..m]);
+            return (quotient, remainder);
+        }
+
+        let vn = &self.limbs;
+        let mut un = [0u64; 9];
+        un[m] = shl_limbs(&u[..m], self.shift, &mut un[..m]);
+
+        let v_top = vn[n - 1];
+        let v_next = vn[n - 2];
+
+        for j in (0..=m - n).rev() {
+            // Estimate the quotient digit from the top two limbs of the current window.
+            let (mut qhat, mut rhat, mut rhat_overflow) = if un[j + n] >= v_top {
+                // The window's top limb can only equal the divisor's here, so the
+                // estimate saturates at `2^64 - 1`.
+                let (rhat, overflow) = un[j + n - 1].overflowing_add(v_top);
+                (u64::MAX, rhat, overflow)
+            } else {
+                let (qhat, rhat) = self.top.div_2by1(un[j + n], un[j + n - 1]);
+                (qhat, rhat, false)
+            };
+            while !rhat_overflow
+                && qhat as u128 * v_next as u128 > ((rhat as u128) << 64 | un[j + n - 2] as u128)
+            {
+                qhat -= 1;
+                (rhat, rhat_overflow) = rhat.overflowing_add(v_top);
+            }
+
+            // Multiply and subtract `qhat * vn` from the current window.
+            let mut borrow: i128 = 0;
+            for i in 0..n {
+                let p = qhat as u128 * vn[i] as u128;
+                let t = un[i + j] as i128 - borrow - (p & u64::MAX as u128) as i128;
+                un[i + j] = t as u64;
+                borrow = (p >> 64) as i128 - (t >> 64);
+            }
+            let t = un[j + n] as i128 - borrow;
+            un[j + n] = t as u64;
+
+            quotient[j] = qhat;
+            if t < 0 {
+                // The estimate was one too large; add the divisor back.
+                quotient[j] = quotient[j].wrapping_sub(1);
+                let mut carry = 0u128;
+                for i in 0..n {
+                    let t = un[i + j] as u128 + vn[i] as u128 + carry;
+                    un[i + j] = t as u64;
+                    carry = t >> 64;
+                }
+                un[j + n] = un[j + n].wrapping_add(carry as u64);
+            }
+        }
+
+        // Unnormalize the remainder.
+        for i in 0..n {
+            remainder[i] = if self.shift == 0 {
+                un[i]
+            } else {
+                un[i] >> self.shift | un[i + 1] << (64 - self.shift)
+            };
+        }
+
+        (quotient, remainder)
+    }
+}
+
+/// Returns the number of limbs up to and including the most significant non-zero limb.
+fn significant_limbs(limbs: &[u64]) -> usize {
+    limbs.iter().rposition(|&limb| limb != 0).map_or(0, |i| i + 1)
+}
+
+/// Shifts `src` left by `shift` (< 64) bits into `dst` and returns the bits shifted out.
+fn shl_limbs(src: &[u64], shift: u32, dst: &mut [u64]) -> u64 {
+    if shift == 0 {
+        dst.copy_from_slice(src);
+        return 0;
+    }
+    let mut carry = 0;
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = s << shift | carry;
+        carry = s >> (64 - shift);
+    }
+    carry
+}
+
+/// Divides the 512-bit value `high:low` by `divisor`, returning the 512-bit quotient
+/// as a (low, high) pair along with the remainder.
+pub(crate) fn div_rem_wide(low: UInt256, high: UInt256, divisor: UInt256) -> ((UInt256, UInt256), UInt256) {
+    let mut u = [0u64; 8];
+    u[..4].copy_from_slice(&low.to_limbs());
+    u[4..].copy_from_slice(&high.to_limbs());
+
+    let (q, r) = div_rem_limbs(&u, &divisor.to_limbs());
+    let endian = low.endian;
+    (
+        (
+            UInt256::from_limbs_with([q[0], q[1], q[2], q[3]], endian),
+            UInt256::from_limbs_with([q[4], q[5], q[6], q[7]], endian),
+        ),
+        UInt256::from_limbs_with(r, endian),
+    )
+}
+
+/// A single-limb divisor normalized so its top bit is set, along with its reciprocal.
+///
+/// Dividing by it replaces each hardware 128-by-64 division with two multiplications,
+/// following Möller and Granlund, "Improved division by invariant integers" (2011).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LimbReciprocal {
+    /// The normalized divisor `d << shift`.
+    divisor: u64,
+    /// The number of leading zeros of the original divisor.
+    shift: u32,
+    /// `floor((2^128 - 1) / divisor) - 2^64`.
+    reciprocal: u64,
+}
+
+impl LimbReciprocal {
+    /// # Panics
+    ///
+    /// Panics if `d` is zero.
+    pub(crate) fn new(d: u64) -> Self {
+        if d == 0 {
+            panic!("division by zero");
+        }
+        let shift = d.leading_zeros();
+        let divisor = d << shift;
+        // The quotient is in [2^64, 2^65), so truncating drops exactly the 2^64 term.
+        let reciprocal = (u128::MAX / divisor as u128) as u64;
+        LimbReciprocal { divisor, shift, reciprocal }
+    }
+
+    /// Divides the two-limb value `high:low` by the normalized divisor. Requires `high < divisor`.
+    fn div_2by1(&self, high: u64, low: u64) -> (u64, u64) {
+        let d = self.divisor;
+        let q = (self.reciprocal as u128 * high as u128)
+            .wrapping_add((high as u128) << 64 | low as u128);
+        let mut q1 = ((q >> 64) as u64).wrapping_add(1);
+        let q0 = q as u64;
+        let mut r = low.wrapping_sub(q1.wrapping_mul(d));
+        if r > q0 {
+            q1 = q1.wrapping_sub(1);
+            r = r.wrapping_add(d);
+        }
+        if r >= d {
+            q1 += 1;
+            r -= d;
+        }
+        (q1, r)
+    }
+
+    /// Divides the little-endian limbs `u` (at most 8 limbs) by the original divisor,
+    /// writing the quotient limbs into `q`. Returns the remainder.
+    pub(crate) fn div_rem(&self, u: &[u64], q: &mut [u64]) -> u64 {
+        let mut un = [0u64; 8];
+        let un = &mut un[..u.len()];
+        let mut r = shl_limbs(u, self.shift, un);
+        for i in (0..u.len()).rev() {
+            (q[i], r) = self.div_2by1(r, un[i]);
+        }
+        r >> self.shift
+    }
+}
+
+/// A divisor prepared once for dividing many values by it.
+///
+/// The normalization of the divisor and the reciprocal used to estimate quotient
+/// digits are computed up front, so hot loops dividing by the same modulus only
+/// pay for the division itself.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::{PreparedDivisor, UInt256};
+///
+/// let divisor = PreparedDivisor::new(UInt256::from(1_000));
+/// for value in [UInt256::from(1_234), UInt256::from(98_765)] {
+///     let (q, r) = divisor.div_rem_with(value);
+///     assert_eq!((q, r), value.div_rem(UInt256::from(1_000)));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PreparedDivisor {
+    divisor: UInt256,
+    normalized: NormalizedDivisor,
+}
+
+impl PreparedDivisor {
+    /// Prepare `divisor` for repeated division.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn new(divisor: UInt256) -> Self {
+        PreparedDivisor {
+            divisor,
+            normalized: NormalizedDivisor::new(&divisor.to_limbs()),
+        }
+    }
+
+    /// Returns the divisor this was prepared from.
+    pub fn divisor(&self) -> UInt256 {
+        self.divisor
+    }
+
+    /// Divides `dividend` by the prepared divisor, returning the quotient and the remainder.
+    pub fn div_rem_with(&self, dividend: UInt256) -> (UInt256, UInt256) {
+        let (q, r) = self.normalized.div_rem(&dividend.to_limbs());
+        (
+            UInt256::from_limbs_with([q[0], q[1], q[2], q[3]], dividend.endian),
+            UInt256::from_limbs_with(r, dividend.endian),
+        )
+    }
+}
+
+impl UInt256 {
+    /// Divides `self` by a 64-bit divisor, returning the quotient and the remainder.
+    ///
+    /// This is much faster than the general division when the divisor is small,
+    /// e.g. when repeatedly dividing by 10 to produce decimal digits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `d` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let (q, r) = UInt256::from(1234).div_rem_u64(10);
+    /// assert_eq!(q, UInt256::from(123));
+    /// assert_eq!(r, 4);
+    /// ```
+    pub fn div_rem_u64(self, d: u64) -> (Self, u64) {
+        let mut q = [0u64; 4];
+        let r = LimbReciprocal::new(d).div_rem(&self.to_limbs(), &mut q);
+        (Self::from_limbs_with(q, self.endian), r)
+    }
+
+    /// Calculates `self * b / c` rounded down, using a 512-bit intermediate product
+    /// so that `self * b` may exceed 256 bits.
+    ///
+    /// Returns `None` if the final result does not fit in 256 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// // MAX * 3 overflows, but MAX * 3 / 4 does not.
+    /// let a = UInt256::MAX.mul_div(UInt256::from(3), UInt256::from(4)).unwrap();
+    /// assert_eq!(a, UInt256::MAX / UInt256::from(4) * UInt256::from(3) + UInt256::from(2));
+    /// ```
+    pub fn mul_div(self, b: Self, c: Self) -> Option<Self> {
+        let (product_low, product_high) = self.widening_mul(b);
+        let ((quotient, overflow), _) = div_rem_wide(product_low, product_high, c);
+        if overflow.is_zero() {
+            Some(quotient)
+        } else {
+            None
+        }
+    }
+
+    /// Calculates `self * b / c` rounded up, using a 512-bit intermediate product.
+    ///
+    /// Returns `None` if the final result does not fit in 256 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `c` is zero.
+    pub fn mul_div_ceil(self, b: Self, c: Self) -> Option<Self> {
+        let (product_low, product_high) = self.widening_mul(b);
+        let ((quotient, overflow), remainder) = div_rem_wide(product_low, product_high, c);
+        if !overflow.is_zero() {
+            return None;
+        }
+        if remainder.is_zero() {
+            Some(quotient)
+        } else if quotient == UInt256::MAX {
+            None
+        } else {
+            Some(quotient + UInt256::ONE)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{xorshift, SEED};
+
+    #[test]
+    fn test_div_rem_limbs_single_limb() {
+        let (q, r) = div_rem_limbs(&[100, 0, 0, 0], &[7, 0, 0, 0]);
+        assert_eq!(q[0], 14);
+        assert_eq!(r, [2, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_div_rem_limbs_multi_limb() {
+        // (2^256 - 1) / (2^128 - 1) = 2^128 + 1
+        let (q, r) = div_rem_limbs(&[u64::MAX; 4], &[u64::MAX, u64::MAX, 0, 0]);
+        assert_eq!(q, [1, 0, 1, 0, 0, 0, 0, 0]);
+        assert_eq!(r, [0; 4]);
+
+        // 2^192 / (2^128 + 1)
+        let (q, r) = div_rem_limbs(&[0, 0, 0, 1], &[1, 0, 1, 0]);
+        assert_eq!(q, [u64::MAX, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(r, [1, u64::MAX, 0, 0]);
+    }
+
+    #[test]
+    fn test_div_rem_invariants() {
+        // Check `q * b + r == a` and `r < b` with xorshift-generated operands.
+        let mut next = xorshift(SEED);
+        for round in 0..200 {
+            let mut b = [next(), next(), next(), next()];
+            // Vary the width of the divisor between one and four limbs.
+            for limb in b.iter_mut().skip(round % 4 + 1) {
+                *limb = 0;
+            }
+            let a = UInt256::from_limbs_with([next(), next(), next(), next()], Endian::Big);
+            let b = UInt256::from_limbs_with(b, Endian::Big);
+
+            let (q, r) = a.div_rem(b);
+            assert!(r < b);
+            assert_eq!(q * b + r, a);
+        }
+    }
+
+    #[test]
+    fn test_div_rem_wide_saturated_estimate() {
+        // Divisors and dividends whose top limbs are all ones exercise the
+        // saturated quotient-digit estimate and the add-back step.
+        let values = [
+            UInt256::MAX,
+            UInt256::from_limbs_with([0, 0, 0, u64::MAX], Endian::Big),
+            UInt256::from_limbs_with([1, 0, u64::MAX, u64::MAX], Endian::Big),
+            UInt256::from_limbs_with([u64::MAX, 0, 0, 1 << 63], Endian::Big),
+            UInt256::from_limbs_with([u64::MAX, u64::MAX, 1, 0], Endian::Big),
+        ];
+        for a in values {
+            for b in values {
+                let (low, high) = a.widening_mul(b);
+                let ((q_low, q_high), r) = div_rem_wide(low, high, b);
+                assert_eq!((q_low, q_high, r), (a, UInt256::ZERO, UInt256::ZERO));
+            }
+        }
+    }
+
+    #[test]
+    fn test_prepared_divisor() {
+        let divisors = [
+            UInt256::from(7),
+            UInt256::new(0, u128::MAX, Endian::Big),
+            UInt256::new(12345, 67890, Endian::Big),
+            UInt256::MAX,
+        ];
+        let dividends = [UInt256::ZERO, UInt256::from(6), UInt256::new(99, 1, Endian::Big), UInt256::MAX];
+        for d in divisors {
+            let prepared = PreparedDivisor::new(d);
+            assert_eq!(prepared.divisor(), d);
+            for a in dividends {
+                assert_eq!(prepared.div_rem_with(a), a.div_rem(d));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_prepared_divisor_zero() {
+        let _ = PreparedDivisor::new(UInt256::ZERO);
+    }
+
+    #[test]
+    fn test_div_rem_u64() {
+        let (q, r) = UInt256::MAX.div_rem_u64(10);
+        let (expected_q, expected_r) = UInt256::MAX.div_rem(UInt256::from(10));
+        assert_eq!(q, expected_q);
+        assert_eq!(UInt256::from(r as usize), expected_r);
+
+        assert_eq!(UInt256::MAX.div_rem_u64(1), (UInt256::MAX, 0));
+        assert_eq!(UInt256::MAX.div_rem_u64(u64::MAX).1, 0);
+        assert_eq!(UInt256::from(7).div_rem_u64(u64::MAX), (UInt256::ZERO, 7));
+    }
+
+    #[test]
+    fn test_div_rem_u64_matches_long_division() {
+        let divisors = [3, 10, 58, 1 << 32, (1 << 63) + 1, u64::MAX - 1];
+        let value = [0x0123456789abcdef, 0xfedcba9876543210, 0xdeadbeefcafebabe, 0x8000000000000001];
+        for d in divisors {
+            let (q, r) = UInt256::from_limbs_with(value, Endian::Big).div_rem_u64(d);
+            let (expected_q, expected_r) = div_rem_limbs(&value, &[d, 0, 0, 0]);
+            assert_eq!(q.to_limbs(), [expected_q[0], expected_q[1], expected_q[2], expected_q[3]]);
+            assert_eq!(r, expected_r[0]);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_div_rem_u64_by_zero() {
+        let _ = UInt256::ONE.div_rem_u64(0);
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let a = UInt256::from(1_000_000);
+        assert_eq!(a.mul_div(UInt256::from(3), UInt256::from(7)), Some(UInt256::from(428_571)));
+        assert_eq!(UInt256::MAX.mul_div(UInt256::MAX, UInt256::MAX), Some(UInt256::MAX));
+
+        let half = UInt256::new(1 << 127, 0, Endian::Big);
+        assert_eq!(half.mul_div(UInt256::from(4), UInt256::from(8)), Some(UInt256::new(1 << 126, 0, Endian::Big)));
+    }
+
+    #[test]
+    fn test_mul_div_overflow() {
+        assert_eq!(UInt256::MAX.mul_div(UInt256::from(2), UInt256::ONE), None);
+        assert_eq!(UInt256::MAX.mul_div(UInt256::MAX, UInt256::from(2)), None);
+    }
+
+    #[test]
+    fn test_mul_div_ceil() {
+        let a = UInt256::from(1_000_000);
+        assert_eq!(a.mul_div_ceil(UInt256::from(3), UInt256::from(7)), Some(UInt256::from(428_572)));
+        assert_eq!(a.mul_div_ceil(UInt256::from(7), UInt256::from(7)), Some(a));
+        assert_eq!(UInt256::MAX.mul_div_ceil(UInt256::MAX, UInt256::MAX), Some(UInt256::MAX));
+        assert_eq!(UInt256::MAX.mul_div_ceil(UInt256::from(3), UInt256::from(2)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_mul_div_by_zero() {
+        let _ = UInt256::ONE.mul_div(UInt256::ONE, UInt256::ZERO);
+    }
+}
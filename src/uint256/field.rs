@@ -0,0 +1,281 @@
+//! Elements of a prime field with a modulus fixed at compile time.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{AddAssign, DivAssign, MulAssign, Neg, SubAssign};
+
+use super::moduli::Modulus;
+use super::*;
+
+/// An element of the field of integers modulo `M::MODULUS`.
+///
+/// The value is always kept reduced, and the arithmetic operators reduce their results,
+/// using the reduction of the [`Modulus`]. Division multiplies by the inverse, which
+/// only exists for every non-zero element if the modulus is prime.
+///
+/// Runtime moduli are out of scope: the modulus is the type `M`, so an element stores no
+/// modulus and there is no `Fp` for a value only known at run time. For such a modulus, use
+/// [`MontgomeryContext`] or [`BarrettReducer`] directly.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::moduli::Secp256k1P;
+/// use uint256::{Fp, UInt256};
+///
+/// type F = Fp<Secp256k1P>;
+///
+/// let a = F::new(UInt256::from(3));
+/// let b = F::new(UInt256::from(5));
+/// assert_eq!((a - b).value(), (-F::new(UInt256::from(2))).value());
+/// assert_eq!(a / b * b, a);
+/// ```
+pub struct Fp<M> {
+    value: UInt256,
+    modulus: PhantomData<M>,
+}
+
+impl<M: Modulus> Fp<M> {
+    /// The additive identity.
+    pub const ZERO: Self = Fp { value: UInt256::ZERO, modulus: PhantomData };
+
+    /// The multiplicative identity.
+    pub const ONE: Self = Fp { value: UInt256::ONE, modulus: PhantomData };
+
+    /// Creates the element congruent to `value`, which does not need to be reduced.
+    pub fn new(value: UInt256) -> Self {
+        Self::from_reduced(M::reduce((value, UInt256::ZERO)))
+    }
+
+    fn from_reduced(value: UInt256) -> Self {
+        Fp { value, modulus: PhantomData }
+    }
+
+    /// Returns the reduced value, in `[0, M::MODULUS)`.
+    pub fn value(&self) -> UInt256 {
+        self.value
+    }
+
+    /// Returns `true` if this is the zero element.
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero (or for any element that
+    /// shares a factor with a composite modulus).
+    pub fn inverse(&self) -> Option<Self> {
+        self.value.mod_inv(M::MODULUS).map(Self::from_reduced)
+    }
+
+    /// Raises the element to the power `exponent`.
+    pub fn pow(&self, exponent: UInt256) -> Self {
+        let mut result = Self::ONE;
+        let bits = 256 - exponent.leading_zeros() as usize;
+        for i in (0..bits).rev() {
+            result = result * result;
+            if exponent.bit_at(i) {
+                result *= *self;
+            }
+        }
+        result
+    }
+}
+
+impl<M> Clone for Fp<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for Fp<M> {}
+
+impl<M> PartialEq for Fp<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<M> Eq for Fp<M> {}
+
+impl<M> Hash for Fp<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<M> fmt::Debug for Fp<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Fp").field(&self.value).finish()
+    }
+}
+
+impl<M> fmt::Display for Fp<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+impl<M: Modulus> Default for Fp<M> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<M: Modulus> From<UInt256> for Fp<M> {
+    fn from(value: UInt256) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<M> From<Fp<M>> for UInt256 {
+    fn from(element: Fp<M>) -> Self {
+        element.value
+    }
+}
+
+impl<M: Modulus> Add for Fp<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        // The sum can carry out of 256 bits for moduli above 2^255.
+        let (sum, carry) = self.value.overflowing_add(rhs.value);
+        let (difference, borrow) = sum.overflowing_sub(M::MODULUS);
+        let keep = Choice::from((borrow & !carry) as u8);
+        Self::from_reduced(UInt256::conditional_select(&difference, &sum, keep))
+    }
+}
+
+impl<M: Modulus> Sub for Fp<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let (difference, borrow) = self.value.overflowing_sub(rhs.value);
+        let wrapped = difference.wrapping_add(M::MODULUS);
+        Self::from_reduced(UInt256::conditional_select(&difference, &wrapped, Choice::from(borrow as u8)))
+    }
+}
+
+impl<M: Modulus> Mul for Fp<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::from_reduced(M::mul_mod(self.value, rhs.value))
+    }
+}
+
+impl<M: Modulus> Div for Fp<M> {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// Panics if `rhs` has no inverse, e.g. if it is zero.
+    fn div(self, rhs: Self) -> Self {
+        let inverse = rhs.inverse().expect("division by zero");
+        Self::from_reduced(M::mul_mod(self.value, inverse.value))
+    }
+}
+
+impl<M: Modulus> Neg for Fp<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<M: Modulus> AddAssign for Fp<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<M: Modulus> SubAssign for Fp<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<M: Modulus> MulAssign for Fp<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<M: Modulus> DivAssign for Fp<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::moduli::{Bn254R, Ed25519L, Secp256k1P, SECP256K1_P};
+
+    struct Seven;
+
+    impl Modulus for Seven {
+        const MODULUS: UInt256 = UInt256 { high: 0, low: 7, endian: Endian::Big };
+    }
+
+    fn f7(value: usize) -> Fp<Seven> {
+        Fp::new(UInt256::from(value))
+    }
+
+    #[test]
+    fn test_small_field() {
+        assert_eq!(f7(5) + f7(4), f7(2));
+        assert_eq!(f7(2) - f7(5), f7(4));
+        assert_eq!(f7(3) * f7(5), f7(1));
+        assert_eq!(f7(1) / f7(3), f7(5));
+        assert_eq!(-f7(2), f7(5));
+        assert_eq!(-f7(0), f7(0));
+        assert_eq!(f7(10).value(), UInt256::from(3));
+        assert_eq!(f7(3).pow(UInt256::from(6)), Fp::ONE);
+        assert_eq!(f7(0).inverse(), None);
+
+        let mut a = f7(3);
+        a += f7(6);
+        a *= f7(4);
+        a -= f7(2);
+        a /= f7(2);
+        assert_eq!(a, f7(3));
+    }
+
+    #[test]
+    fn test_large_field() {
+        type F = Fp<Secp256k1P>;
+        let minus_one = F::new(SECP256K1_P - UInt256::ONE);
+        assert_eq!(minus_one + minus_one, F::new(SECP256K1_P - UInt256::from(2)));
+        assert_eq!(minus_one * minus_one, F::ONE);
+        assert_eq!(F::ZERO - F::ONE, minus_one);
+        assert_eq!(F::new(UInt256::MAX).value(), UInt256::MAX % SECP256K1_P);
+
+        let a = F::new(UInt256::new(0xdeadbeef, 0xcafebabe, Endian::Big));
+        assert_eq!(a / a, F::ONE);
+        assert_eq!(a * a.inverse().unwrap(), F::ONE);
+        // Fermat's little theorem.
+        assert_eq!(a.pow(SECP256K1_P - UInt256::ONE), F::ONE);
+    }
+
+    #[test]
+    fn test_other_moduli() {
+        fn check<M: Modulus>() {
+            let a = Fp::<M>::new(UInt256::MAX);
+            let b = Fp::<M>::new(UInt256::new(1 << 100, 12345, Endian::Big));
+            assert_eq!((a + b) - b, a);
+            assert_eq!((a * b) / b, a);
+            assert_eq!(a + (-a), Fp::ZERO);
+        }
+        check::<Ed25519L>();
+        check::<Bn254R>();
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_division_by_zero() {
+        let _ = f7(1) / f7(7);
+    }
+}
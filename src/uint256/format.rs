@@ -0,0 +1,831 @@
+//! Formatting [`UInt256`] values as text.
+//!
+//! `Display` prints the decimal value, `LowerHex` and `UpperHex` print hex, and
+//! `Octal` and `Binary` print the remaining radices of Rust integer literals, and
+//! `LowerExp` and `UpperExp` print scientific notation.
+//!
+//! They print the number and ignore the [`Endian`] tag of the value. The byte order of
+//! [`UInt256::to_hex_be`] and [`UInt256::to_hex_le`] is in their names.
+//!
+//! All of them honor the formatter's width, fill, alignment and flags the way the
+//! primitive integers do, e.g. `{:>80}`, `{:+}`, `{:064x}` and `{:#x}`.
+
+use std::fmt;
+
+use super::*;
+
+/// The number of decimal digits of `UInt256::MAX`.
+const MAX_DEC_DIGITS: usize = 78;
+
+/// The digits of radices up to 36, in lower case.
+const LOWER_DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// The digits of radices up to 36, in upper case.
+const UPPER_DIGITS: &[u8; 36] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Writes the digits of `value` in radix `2^bits` to the end of `buf`, without leading
+/// zeros, and returns them.
+fn pow2_digits<'a>(value: UInt256, bits: u32, digits: &[u8; 36], buf: &'a mut [u8; 256]) -> &'a str {
+    let mask = (1 << bits) - 1;
+    let mut pos = buf.len();
+    let mut rest = value;
+    loop {
+        pos -= 1;
+        buf[pos] = digits[(rest.low & mask) as usize];
+        rest >>= bits;
+        if rest.is_zero() {
+            break;
+        }
+    }
+    std::str::from_utf8(&buf[pos..]).expect("digits are ASCII")
+}
+
+/// Writes the digits of `value` in `radix` to the end of `buf`, without leading zeros,
+/// and returns them. `buf` must be long enough for every digit.
+fn radix_digits(value: UInt256, radix: u32, buf: &mut [u8]) -> &str {
+    alphabet_digits(value, &LOWER_DIGITS[..radix as usize], buf)
+}
+
+/// Writes the digits of `value` to the end of `buf` in the radix given by the length of
+/// `alphabet`, which holds the digit for each value. Leading zeros are omitted, and
+/// `buf` must be long enough for every digit.
+pub(crate) fn alphabet_digits<'a>(value: UInt256, alphabet: &[u8], buf: &'a mut [u8]) -> &'a str {
+    std::str::from_utf8(digit_bytes(value, alphabet, buf)).expect("digits are ASCII")
+}
+
+/// Like [`alphabet_digits`], but for any bytes as digits.
+fn digit_bytes<'a>(value: UInt256, alphabet: &[u8], buf: &'a mut [u8]) -> &'a [u8] {
+    // Peel off as many digits as fit in a `u64` at a time with the fast division by a
+    // `u64`, e.g. 19 decimal digits.
+    let radix = alphabet.len() as u64;
+    let chunk_digits = u64::MAX.ilog(radix) as usize;
+    let chunk_radix = radix.pow(chunk_digits as u32);
+    let mut pos = buf.len();
+    let mut rest = value;
+    loop {
+        let (quotient, mut chunk) = rest.div_rem_u64(chunk_radix);
+        rest = quotient;
+        // Every chunk but the leading one is padded with zeros.
+        let digits = match rest.is_zero() {
+            true => chunk.checked_ilog(radix).map_or(1, |log| log as usize + 1),
+            false => chunk_digits,
+        };
+        for _ in 0..digits {
+            pos -= 1;
+            buf[pos] = alphabet[(chunk % radix) as usize];
+            chunk /= radix;
+        }
+        if rest.is_zero() {
+            break;
+        }
+    }
+    &buf[pos..]
+}
+
+/// Returns two lower-case hex digits for each byte, in order.
+fn bytes_to_hex(bytes: [u8; 32]) -> String {
+    let mut hex = String::with_capacity(64);
+    for byte in bytes {
+        hex.push(LOWER_DIGITS[(byte >> 4) as usize] as char);
+        hex.push(LOWER_DIGITS[(byte & 0xf) as usize] as char);
+    }
+    hex
+}
+
+impl UInt256 {
+    /// Returns the digits of the value in the given `radix`, from 2 to 36, without
+    /// leading zeros.
+    ///
+    /// Digits above 9 are the lower-case letters `a` to `z`. The string parses back with
+    /// [`Self::from_str_radix`] and [`Endian::Big`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Endian, UInt256};
+    ///
+    /// let value = UInt256::from(330);
+    /// assert_eq!(value.to_string_radix(3), "110020");
+    /// assert_eq!(value.to_string_radix(36), "96");
+    /// assert_eq!(UInt256::from_str_radix(&value.to_string_radix(7), 7, Endian::Big), Ok(value));
+    /// ```
+    pub fn to_string_radix(self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be between 2 and 36");
+        let mut buf = [0u8; 256];
+        if radix.is_power_of_two() {
+            pow2_digits(self, radix.trailing_zeros(), LOWER_DIGITS, &mut buf).to_owned()
+        } else {
+            radix_digits(self, radix, &mut buf).to_owned()
+        }
+    }
+
+    /// Returns the digits of the value in the given `radix`, from 2 to 256, least
+    /// significant first and without trailing zeros, so zero is `[0]`.
+    ///
+    /// Each digit is a number below `radix`, not a character. [`Self::from_radix_digits_le`]
+    /// is the inverse.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 256.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(1_234_567).to_radix_digits_le(100), [67, 45, 23, 1]);
+    /// assert_eq!(UInt256::from(2047).to_radix_digits_le(2), [1; 11]);
+    /// assert_eq!(UInt256::ZERO.to_radix_digits_le(10), [0]);
+    /// ```
+    pub fn to_radix_digits_le(&self, radix: u32) -> Vec<u8> {
+        assert!((2..=256).contains(&radix), "radix must be between 2 and 256");
+        let identity: [u8; 256] = std::array::from_fn(|i| i as u8);
+        let mut buf = [0u8; 256];
+        let mut digits = digit_bytes(*self, &identity[..radix as usize], &mut buf).to_vec();
+        digits.reverse();
+        digits
+    }
+
+    /// Writes all 64 lower-case hex digits of the value into `buf`, after `0x` if `prefix`
+    /// is set, and returns them without allocating.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let mut buf = [0u8; 66];
+    /// let hex = UInt256::from(330).write_hex(&mut buf, true);
+    /// assert_eq!(hex, "0x000000000000000000000000000000000000000000000000000000000000014a");
+    /// assert_eq!(UInt256::MAX.write_hex(&mut buf, false), "f".repeat(64));
+    /// ```
+    pub fn write_hex<'a>(&self, buf: &'a mut [u8; 66], prefix: bool) -> &'a str {
+        let start = if prefix { 0 } else { 2 };
+        buf[..2].copy_from_slice(b"0x");
+        for (pair, byte) in buf[2..].chunks_exact_mut(2).zip(self.to_be_array()) {
+            pair[0] = LOWER_DIGITS[(byte >> 4) as usize];
+            pair[1] = LOWER_DIGITS[(byte & 0xf) as usize];
+        }
+        std::str::from_utf8(&buf[start..]).expect("hex digits are ASCII")
+    }
+
+    /// Writes the decimal digits of the value into the end of `buf`, without leading zeros,
+    /// and returns them without allocating.
+    ///
+    /// 78 digits are enough for [`UInt256::MAX`]. This is what `Display` prints, minus
+    /// the padding.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let mut buf = [0u8; 78];
+    /// assert_eq!(UInt256::from(1_000_000_007).write_decimal(&mut buf), "1000000007");
+    /// assert_eq!(UInt256::ZERO.write_decimal(&mut buf), "0");
+    /// ```
+    pub fn write_decimal<'a>(&self, buf: &'a mut [u8; 78]) -> &'a str {
+        radix_digits(*self, 10, buf)
+    }
+
+    /// Returns the 64 lower-case hex digits of the value's big-endian bytes, without a
+    /// prefix. This is the number itself in hex, padded with leading zeros.
+    ///
+    /// Like all formatting, it depends only on the value and not on its [`Endian`] tag.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let value = UInt256::from(0x1234);
+    /// assert_eq!(value.to_hex_be(), format!("{}1234", "0".repeat(60)));
+    /// ```
+    pub fn to_hex_be(&self) -> String {
+        bytes_to_hex(self.to_be_array())
+    }
+
+    /// Returns the 64 lower-case hex digits of the value's little-endian bytes, without a
+    /// prefix, e.g. to compare with a hex dump of little-endian data.
+    ///
+    /// Like all formatting, it depends only on the value and not on its [`Endian`] tag.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let value = UInt256::from(0x1234);
+    /// assert_eq!(value.to_hex_le(), format!("3412{}", "0".repeat(60)));
+    ///
+    /// let bytes = value.to_le_array();
+    /// assert_eq!(UInt256::from_le_bytes(&bytes).to_hex_le(), value.to_hex_le());
+    /// ```
+    pub fn to_hex_le(&self) -> String {
+        bytes_to_hex(self.to_le_array())
+    }
+
+    /// Returns the decimal digits of the value with `separator` between every `group`
+    /// digits, counted from the right.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `group` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let value = UInt256::from(1_234_567_890);
+    /// assert_eq!(value.format_grouped(',', 3), "1,234,567,890");
+    /// assert_eq!(value.format_grouped('_', 3), "1_234_567_890");
+    /// assert_eq!(value.format_grouped(' ', 4), "12 3456 7890");
+    /// ```
+    pub fn format_grouped(&self, separator: char, group: usize) -> String {
+        assert!(group > 0, "group must not be zero");
+        let mut buf = [0u8; MAX_DEC_DIGITS];
+        let digits = self.write_decimal(&mut buf);
+        let separators = (digits.len() - 1) / group;
+        let mut grouped = String::with_capacity(digits.len() + separators * separator.len_utf8());
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(group) {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    /// Formats the value as a decimal amount of `10^decimals` units, the inverse of
+    /// [`Self::parse_units`].
+    ///
+    /// Trailing zeros of the fraction are dropped but one fractional digit is always
+    /// kept, as in `"1.0"`, unless `decimals` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let wei = UInt256::from(1_500_000_000_000_000_000);
+    /// assert_eq!(wei.format_units(18), "1.5");
+    /// assert_eq!(UInt256::from(1_000_000).format_units(6), "1.0");
+    /// assert_eq!(UInt256::from(25).format_units(6), "0.000025");
+    /// assert_eq!(UInt256::parse_units(&wei.format_units(18), 18), Ok(wei));
+    /// ```
+    pub fn format_units(&self, decimals: u32) -> String {
+        let mut buf = [0u8; MAX_DEC_DIGITS];
+        let digits = self.write_decimal(&mut buf);
+        let decimals = decimals as usize;
+        if decimals == 0 {
+            return digits.to_owned();
+        }
+
+        let split = digits.len().saturating_sub(decimals);
+        let integer = if split == 0 { "0" } else { &digits[..split] };
+        let fraction = digits[split..].trim_end_matches('0');
+        let leading_zeros = decimals.saturating_sub(digits.len());
+        let mut amount = String::with_capacity(integer.len() + 1 + leading_zeros + fraction.len().max(1));
+        amount.push_str(integer);
+        amount.push('.');
+        if fraction.is_empty() {
+            amount.push('0');
+        } else {
+            amount.extend(std::iter::repeat_n('0', leading_zeros));
+            amount.push_str(fraction);
+        }
+        amount
+    }
+}
+
+/// Prints the value in decimal.
+///
+/// Formatting shows the number, never a byte layout, so a value prints the same whatever
+/// its [`Endian`] tag. In hex, `{:x}` reads like [`UInt256::to_hex_be`] without the leading
+/// zeros. Use [`UInt256::to_hex_le`] to see the little-endian bytes.
+///
+/// Before hex formatting moved to [`fmt::LowerHex`] and [`fmt::UpperHex`], `Display`
+/// printed the `0x`-prefixed, 64-digit hex value. Use `{:#066x}` for that output.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::UInt256;
+///
+/// assert_eq!(UInt256::from(330).to_string(), "330");
+/// let n = UInt256::from(42);
+/// assert_eq!(format!("{:>6}|{:<6}|{:06}", n, n, n), "    42|42    |000042");
+/// assert_eq!(
+///     UInt256::MAX.to_string(),
+///     "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+/// );
+/// ```
+impl fmt::Display for UInt256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0u8; MAX_DEC_DIGITS];
+        f.pad_integral(true, "", self.write_decimal(&mut buf))
+    }
+}
+
+/// Prints the value in lower-case hex, with a `0x` prefix for `{:#x}`.
+///
+/// Leading zeros are omitted unless the width asks for them, so `{:#066x}` prints all
+/// 64 digits after the prefix.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::UInt256;
+///
+/// let value = UInt256::from(330);
+/// assert_eq!(format!("{:x}", value), "14a");
+/// assert_eq!(format!("{:#x}", value), "0x14a");
+/// assert_eq!(format!("{:08x}", value), "0000014a");
+/// assert_eq!(format!("{:#066x}", value), "0x000000000000000000000000000000000000000000000000000000000000014a");
+/// ```
+impl fmt::LowerHex for UInt256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0u8; 256];
+        f.pad_integral(true, "0x", pow2_digits(*self, 4, LOWER_DIGITS, &mut buf))
+    }
+}
+
+/// Prints the value in upper-case hex, with a `0x` prefix for `{:#X}`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::UInt256;
+///
+/// assert_eq!(format!("{:X}", UInt256::from(0xbeef)), "BEEF");
+/// assert_eq!(format!("{:#010X}", UInt256::from(0xbeef)), "0x0000BEEF");
+/// ```
+impl fmt::UpperHex for UInt256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0u8; 256];
+        f.pad_integral(true, "0x", pow2_digits(*self, 4, UPPER_DIGITS, &mut buf))
+    }
+}
+
+/// Prints the value in octal, with a `0o` prefix for `{:#o}`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::UInt256;
+///
+/// assert_eq!(format!("{:o}", UInt256::from(0o755)), "755");
+/// assert_eq!(format!("{:#o}", UInt256::from(8)), "0o10");
+/// ```
+impl fmt::Octal for UInt256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0u8; 256];
+        f.pad_integral(true, "0o", pow2_digits(*self, 3, LOWER_DIGITS, &mut buf))
+    }
+}
+
+/// Prints the value in binary, with a `0b` prefix for `{:#b}`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::UInt256;
+///
+/// let mask = UInt256::from(0b1011) << 200;
+/// assert_eq!(format!("{:b}", mask), format!("1011{}", "0".repeat(200)));
+/// assert_eq!(format!("{:#010b}", UInt256::from(5)), "0b00000101");
+/// ```
+impl fmt::Binary for UInt256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = [0u8; 256];
+        f.pad_integral(true, "0b", pow2_digits(*self, 1, LOWER_DIGITS, &mut buf))
+    }
+}
+
+/// Formats `value` in scientific notation with `exponent_marker` (`e` or `E`), like the
+/// primitive integers: without a precision all significant digits are printed, and with
+/// one the mantissa is rounded half to even.
+fn fmt_exp(value: UInt256, f: &mut fmt::Formatter, exponent_marker: char) -> fmt::Result {
+    let mut buf = [0u8; MAX_DEC_DIGITS];
+    let digits = value.write_decimal(&mut buf);
+    let mut exponent = digits.len() - 1;
+
+    let mut mantissa = match f.precision() {
+        None => digits.trim_end_matches('0').to_owned(),
+        Some(precision) if precision + 1 >= digits.len() => {
+            let mut mantissa = digits.to_owned();
+            mantissa.extend(std::iter::repeat_n('0', precision + 1 - digits.len()));
+            mantissa
+        }
+        Some(precision) => {
+            let (kept, dropped) = digits.split_at(precision + 1);
+            let last_is_odd = (kept.as_bytes()[precision] - b'0') % 2 == 1;
+            let round_up = match dropped.as_bytes()[0] {
+                b'6'..=b'9' => true,
+                b'5' => last_is_odd || dropped.bytes().skip(1).any(|b| b != b'0'),
+                _ => false,
+            };
+            let mut mantissa = kept.as_bytes().to_vec();
+            if round_up {
+                // Propagate the carry; all nines become a one with a larger exponent.
+                match mantissa.iter().rposition(|&b| b != b'9') {
+                    Some(i) => {
+                        mantissa[i] += 1;
+                        mantissa[i + 1..].fill(b'0');
+                    }
+                    None => {
+                        mantissa.fill(b'0');
+                        mantissa[0] = b'1';
+                        exponent += 1;
+                    }
+                }
+            }
+            String::from_utf8(mantissa).expect("decimal digits are ASCII")
+        }
+    };
+    if mantissa.is_empty() {
+        // Zero without a precision.
+        mantissa.push('0');
+    }
+
+    let mut formatted = String::with_capacity(mantissa.len() + 5);
+    formatted.push_str(&mantissa[..1]);
+    if mantissa.len() > 1 {
+        formatted.push('.');
+        formatted.push_str(&mantissa[1..]);
+    }
+    formatted.push(exponent_marker);
+    formatted.push_str(&exponent.to_string());
+    f.pad_integral(true, "", &formatted)
+}
+
+/// Prints the value in scientific notation, such as `1.157920892e77` for `{:.9e}`.
+///
+/// Without a precision all significant digits are printed. With one, the mantissa is
+/// rounded half to even, as for the primitive integers.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::UInt256;
+///
+/// assert_eq!(format!("{:e}", UInt256::from(1234)), "1.234e3");
+/// assert_eq!(format!("{:e}", UInt256::from(1000)), "1e3");
+/// assert_eq!(format!("{:.9e}", UInt256::MAX), "1.157920892e77");
+/// assert_eq!(format!("{:.2e}", UInt256::from(5)), "5.00e0");
+/// ```
+impl fmt::LowerExp for UInt256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_exp(*self, f, 'e')
+    }
+}
+
+/// Prints the value in scientific notation with an upper-case `E`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::UInt256;
+///
+/// assert_eq!(format!("{:.3E}", UInt256::MAX), "1.158E77");
+/// ```
+impl fmt::UpperExp for UInt256 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_exp(*self, f, 'E')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{random_values, xorshift, SEED};
+
+    #[test]
+    fn test_display_decimal() {
+        assert_eq!(UInt256::ZERO.to_string(), "0");
+        assert_eq!(UInt256::from(7).to_string(), "7");
+        assert_eq!(UInt256::from(10_000_000_000_000_000_000).to_string(), "10000000000000000000");
+        assert_eq!(UInt256::new(0, 1 << 64, Endian::Big).to_string(), "18446744073709551616");
+        assert_eq!(UInt256::new(1, 0, Endian::Little).to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(
+            UInt256::MAX.to_string(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+        );
+        // A leading chunk followed by chunks with inner zeros.
+        let digits = "1000000000000000000000000000000000000000000000000000000000000000000000000007";
+        assert_eq!(UInt256::from_dec_str(digits).unwrap().to_string(), digits);
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        for value in random_values(SEED, 200) {
+            assert_eq!(UInt256::from_dec_str(&value.to_string()), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_hex() {
+        let value = UInt256::new(
+            0xff4567890abcdef1234567890ac203d5,
+            0x1234567890abcdef1234567890abcdef,
+            Endian::Big,
+        );
+        assert_eq!(
+            format!("{:x}", value),
+            "ff4567890abcdef1234567890ac203d51234567890abcdef1234567890abcdef",
+        );
+        assert_eq!(
+            format!("{:#X}", value),
+            "0xFF4567890ABCDEF1234567890AC203D51234567890ABCDEF1234567890ABCDEF",
+        );
+        assert_eq!(format!("{:x}", UInt256::ZERO), "0");
+        assert_eq!(format!("{:#x}", UInt256::ZERO), "0x0");
+        assert_eq!(format!("{:x}", UInt256::new(1, 0, Endian::Big)), format!("1{}", "0".repeat(32)));
+        assert_eq!(format!("{:X}", UInt256::MAX), "F".repeat(64));
+    }
+
+    #[test]
+    fn test_hex_padding() {
+        let value = UInt256::from(0xabc);
+        assert_eq!(format!("{:06x}", value), "000abc");
+        assert_eq!(format!("{:#06x}", value), "0x0abc");
+        assert_eq!(format!("{:#06X}", value), "0x0ABC");
+        // The width is a minimum, so it never truncates.
+        assert_eq!(format!("{:#02x}", value), "0xabc");
+        assert_eq!(format!("{:#066x}", value), format!("0x{}abc", "0".repeat(61)));
+        assert_eq!(format!("{:#066x}", UInt256::MAX), format!("0x{}", "f".repeat(64)));
+        assert_eq!(format!("{:#070x}", UInt256::MAX), format!("0x0000{}", "f".repeat(64)));
+    }
+
+    #[test]
+    fn test_octal() {
+        assert_eq!(format!("{:o}", UInt256::ZERO), "0");
+        assert_eq!(format!("{:#o}", UInt256::from(0o1234567)), "0o1234567");
+        assert_eq!(format!("{:#08o}", UInt256::from(0o17)), "0o000017");
+        // 256 bits are one bit more than 85 octal digits.
+        assert_eq!(format!("{:o}", UInt256::MAX), format!("1{}", "7".repeat(85)));
+        assert_eq!(format!("{:o}", UInt256::new(1, 0, Endian::Big)), format!("4{}", "0".repeat(42)));
+    }
+
+    #[test]
+    fn test_binary() {
+        assert_eq!(format!("{:b}", UInt256::ZERO), "0");
+        assert_eq!(format!("{:#b}", UInt256::from(0b1010_1111)), "0b10101111");
+        assert_eq!(format!("{:b}", UInt256::MAX), "1".repeat(256));
+        assert_eq!(format!("{:#0258b}", UInt256::ONE), format!("0b{}1", "0".repeat(255)));
+
+        let value = UInt256::new(0x8000_0000_0000_0000_0000_0000_0000_0001, 0xf0, Endian::Big);
+        let binary = format!("{:b}", value);
+        assert_eq!(binary.len(), 256);
+        for (i, digit) in binary.bytes().rev().enumerate() {
+            assert_eq!(digit == b'1', value.bit_at(i), "bit {}", i);
+        }
+    }
+
+    #[test]
+    fn test_width_fill_and_alignment() {
+        let value = UInt256::from(330);
+        assert_eq!(format!("{:8}", value), "     330");
+        assert_eq!(format!("{:<8}|", value), "330     |");
+        assert_eq!(format!("{:^9}", value), "   330   ");
+        assert_eq!(format!("{:*>8}", value), "*****330");
+        assert_eq!(format!("{:_<8x}", value), "14a_____");
+        assert_eq!(format!("{:>#10x}", value), "     0x14a");
+        assert_eq!(format!("{:^#12b}", UInt256::from(5)), "   0b101    ");
+        assert_eq!(format!("{:>6o}", UInt256::from(8)), "    10");
+        // The width is the minimum, and a wider value is printed whole.
+        assert_eq!(format!("{:>80}", UInt256::MAX).len(), 80);
+        assert_eq!(format!("{:>80}", UInt256::MAX).trim_start(), UInt256::MAX.to_string());
+        assert_eq!(format!("{:4}", UInt256::MAX), UInt256::MAX.to_string());
+        assert_eq!(format!("{:>width$}", value, width = 5), "  330");
+    }
+
+    #[test]
+    fn test_flags_match_primitives() {
+        // Every case formats the same as the equivalent `u128`.
+        for n in [0u128, 7, 330, u64::MAX as u128, u128::MAX] {
+            let value = UInt256::new(0, n, Endian::Big);
+            assert_eq!(format!("{:+}", value), format!("{:+}", n));
+            assert_eq!(format!("{:+08}", value), format!("{:+08}", n));
+            assert_eq!(format!("{:064x}", value), format!("{:064x}", n));
+            assert_eq!(format!("{:#064X}", value), format!("{:#064X}", n));
+            assert_eq!(format!("{:#o}", value), format!("{:#o}", n));
+            assert_eq!(format!("{:>#140b}", value), format!("{:>#140b}", n));
+            assert_eq!(format!("{:-^50}", value), format!("{:-^50}", n));
+            // Zero padding ignores the fill and alignment, like for the primitives.
+            assert_eq!(format!("{:x<#050x}", value), format!("{:x<#050x}", n));
+        }
+    }
+
+    #[test]
+    fn test_field_element_formatting() {
+        use crate::uint256::moduli::Secp256k1P;
+
+        let element = Fp::<Secp256k1P>::new(UInt256::from(330));
+        assert_eq!(format!("{:>6}", element), "   330");
+    }
+
+    #[test]
+    fn test_to_string_radix() {
+        assert_eq!(UInt256::from(330).to_string_radix(10), "330");
+        assert_eq!(UInt256::from(330).to_string_radix(16), "14a");
+        assert_eq!(UInt256::from(35).to_string_radix(36), "z");
+        assert_eq!(UInt256::MAX.to_string_radix(36), "6dp5qcb22im238nr3wvp0ic7q99w035jmy2iw7i6n43d37jtof");
+        assert_eq!(UInt256::MAX.to_string_radix(2), "1".repeat(256));
+        assert_eq!(UInt256::MAX.to_string_radix(10), UInt256::MAX.to_string());
+        for radix in 2..=36 {
+            assert_eq!(UInt256::ZERO.to_string_radix(radix), "0");
+            assert_eq!(UInt256::from(radix as usize).to_string_radix(radix), "10");
+            let largest_digit = (LOWER_DIGITS[radix as usize - 1] as char).to_string();
+            assert_eq!(UInt256::from(radix as usize - 1).to_string_radix(radix), largest_digit);
+        }
+    }
+
+    #[test]
+    fn test_to_string_radix_round_trip() {
+        let mut values = random_values(SEED, 35 * 20);
+        for radix in 2..=36 {
+            for value in values.by_ref().take(20) {
+                let digits = value.to_string_radix(radix);
+                assert_eq!(UInt256::from_str_radix(&digits, radix, Endian::Big), Ok(value), "radix {}", radix);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be between 2 and 36")]
+    fn test_to_string_radix_invalid_radix() {
+        let _ = UInt256::ONE.to_string_radix(1);
+    }
+
+    #[test]
+    fn test_to_radix_digits_le() {
+        assert_eq!(UInt256::MAX.to_radix_digits_le(256), [255; 32]);
+        assert_eq!(UInt256::MAX.to_radix_digits_le(2), [1; 256]);
+        assert_eq!(UInt256::from(0x0100).to_radix_digits_le(256), [0, 1]);
+        let digits: Vec<u8> = UInt256::MAX.to_string().bytes().rev().map(|b| b - b'0').collect();
+        assert_eq!(UInt256::MAX.to_radix_digits_le(10), digits);
+
+        for value in random_values(SEED, 100) {
+            let mut trimmed = value.to_le_bytes().to_vec();
+            while trimmed.len() > 1 && trimmed.last() == Some(&0) {
+                trimmed.pop();
+            }
+            assert_eq!(value.to_radix_digits_le(256), trimmed);
+            for radix in [3, 10, 58, 255] {
+                let digits = value.to_radix_digits_le(radix);
+                assert!(digits.iter().all(|&d| (d as u32) < radix));
+                assert_eq!(UInt256::from_radix_digits_le(&digits, radix), Ok(value), "{}", radix);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be between 2 and 256")]
+    fn test_to_radix_digits_le_invalid_radix() {
+        let _ = UInt256::ONE.to_radix_digits_le(257);
+    }
+
+    #[test]
+    fn test_format_grouped() {
+        assert_eq!(UInt256::ZERO.format_grouped(',', 3), "0");
+        assert_eq!(UInt256::from(999).format_grouped(',', 3), "999");
+        assert_eq!(UInt256::from(1000).format_grouped(',', 3), "1,000");
+        assert_eq!(UInt256::from(123_456).format_grouped(',', 3), "123,456");
+        assert_eq!(UInt256::from(123_456).format_grouped('.', 1), "1.2.3.4.5.6");
+        assert_eq!(UInt256::from(123_456).format_grouped(',', 6), "123456");
+        assert_eq!(UInt256::from(123_456).format_grouped(',', 100), "123456");
+        assert_eq!(UInt256::from(1_000_000).format_grouped('\u{202f}', 3), "1\u{202f}000\u{202f}000");
+
+        let grouped = UInt256::MAX.format_grouped('_', 3);
+        assert_eq!(
+            grouped,
+            "115_792_089_237_316_195_423_570_985_008_687_907_853_269_984_665_640_564_039_457_584_007_913_129_639_935",
+        );
+        assert_eq!(grouped.parse::<UInt256>(), Ok(UInt256::MAX));
+    }
+
+    #[test]
+    #[should_panic(expected = "group must not be zero")]
+    fn test_format_grouped_zero_group() {
+        let _ = UInt256::ONE.format_grouped(',', 0);
+    }
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(UInt256::ZERO.format_units(18), "0.0");
+        assert_eq!(UInt256::ZERO.format_units(0), "0");
+        assert_eq!(UInt256::from(12345).format_units(0), "12345");
+        assert_eq!(UInt256::from(12345).format_units(2), "123.45");
+        assert_eq!(UInt256::from(12300).format_units(2), "123.0");
+        assert_eq!(UInt256::from(12345).format_units(5), "0.12345");
+        assert_eq!(UInt256::from(12345).format_units(7), "0.0012345");
+        assert_eq!(UInt256::ONE.format_units(18), "0.000000000000000001");
+        assert_eq!(
+            UInt256::MAX.format_units(18),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935",
+        );
+        assert_eq!(UInt256::MAX.format_units(100).len(), 102);
+    }
+
+    #[test]
+    fn test_units_round_trip() {
+        let mut values = random_values(SEED, 8 * 20);
+        for decimals in [0, 1, 6, 8, 18, 77, 78, 90] {
+            for value in values.by_ref().take(20) {
+                let amount = value.format_units(decimals);
+                assert_eq!(UInt256::parse_units(&amount, decimals), Ok(value), "{}", amount);
+            }
+        }
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(format!("{:e}", UInt256::ZERO), "0e0");
+        assert_eq!(format!("{:.3e}", UInt256::ZERO), "0.000e0");
+        assert_eq!(format!("{:E}", UInt256::from(120)), "1.2E2");
+        assert_eq!(format!("{:.0e}", UInt256::from(15)), "2e1");
+        assert_eq!(format!("{:.0e}", UInt256::from(25)), "2e1");
+        assert_eq!(format!("{:.1e}", UInt256::from(995)), "1.0e3");
+        assert_eq!(format!("{:.1e}", UInt256::from(1251)), "1.3e3");
+        assert_eq!(
+            format!("{:e}", UInt256::MAX),
+            "1.15792089237316195423570985008687907853269984665640564039457584007913129639935e77",
+        );
+        let digits = UInt256::MAX.to_string();
+        assert_eq!(format!("{:.80e}", UInt256::MAX), format!("1.{}000e77", &digits[1..]));
+        assert_eq!(format!("{:.0e}", UInt256::MAX), "1e77");
+        // Rounding up carries through every nine into a larger exponent.
+        assert_eq!(format!("{:.0e}", UInt256::from_dec_str(&"9".repeat(77)).unwrap()), "1e77");
+    }
+
+    #[test]
+    fn test_exp_matches_primitives() {
+        let mut next = xorshift(SEED);
+        let mut values = vec![0u128, 1, 5, 10, 125, 135, 1250, 999, 994, u128::MAX];
+        values.extend((0..200).map(|_| ((next() as u128) << 64 | next() as u128) >> (next() % 128)));
+        for n in values {
+            let value = UInt256::new(0, n, Endian::Big);
+            assert_eq!(format!("{:e}", value), format!("{:e}", n));
+            assert_eq!(format!("{:E}", value), format!("{:E}", n));
+            for precision in [0, 1, 2, 5, 20, 45] {
+                assert_eq!(format!("{:.*e}", precision, value), format!("{:.*e}", precision, n), "{}", n);
+            }
+            assert_eq!(format!("{:>20.3e}", value), format!("{:>20.3e}", n));
+            assert_eq!(format!("{:+012e}", value), format!("{:+012e}", n));
+        }
+    }
+
+    #[test]
+    fn test_write_hex() {
+        let mut buf = [0u8; 66];
+        assert_eq!(UInt256::ZERO.write_hex(&mut buf, false), "0".repeat(64));
+        assert_eq!(UInt256::ZERO.write_hex(&mut buf, true), format!("0x{}", "0".repeat(64)));
+
+        for value in random_values(SEED, 100) {
+            assert_eq!(value.write_hex(&mut buf, true), format!("{:#066x}", value));
+            assert_eq!(value.write_hex(&mut buf, false), format!("{:064x}", value));
+        }
+    }
+
+    #[test]
+    fn test_to_hex_be_and_le() {
+        assert_eq!(UInt256::ZERO.to_hex_be(), "0".repeat(64));
+        assert_eq!(UInt256::MAX.to_hex_le(), "f".repeat(64));
+        let value = UInt256::new(0x0011_2233_4455_6677_8899_aabb_ccdd_eeff, 0x0123_4567_89ab_cdef, Endian::Big);
+        let be = "00112233445566778899aabbccddeeff00000000000000000123456789abcdef";
+        assert_eq!(value.to_hex_be(), be);
+        let le: String = be.as_bytes().chunks(2).rev().map(|pair| std::str::from_utf8(pair).unwrap()).collect();
+        assert_eq!(value.to_hex_le(), le);
+
+        // The tag does not change the output.
+        let tagged = UInt256::from_le_array(value.to_le_array());
+        assert_eq!(tagged.endian, Endian::Little);
+        assert_eq!((tagged.to_hex_be(), tagged.to_hex_le()), (value.to_hex_be(), value.to_hex_le()));
+        assert_eq!(tagged.to_string(), value.to_string());
+        assert_eq!(format!("{:064x}", tagged), value.to_hex_be());
+    }
+
+    #[test]
+    fn test_write_decimal() {
+        let mut buf = [0u8; 78];
+        assert_eq!(UInt256::MAX.write_decimal(&mut buf), UInt256::MAX.to_string());
+        // Around the chunks of 19 digits.
+        for exponent in [18, 19, 20, 37, 38] {
+            let power = 10u128.pow(exponent);
+            for n in [power - 1, power, power + 1] {
+                assert_eq!(UInt256::new(0, n, Endian::Big).write_decimal(&mut buf), n.to_string());
+            }
+        }
+        let ten_pow_57 = UInt256::new(0, 10u128.pow(19), Endian::Big) * UInt256::new(0, 10u128.pow(38), Endian::Big);
+        assert_eq!(ten_pow_57.write_decimal(&mut buf), format!("1{}", "0".repeat(57)));
+        assert_eq!((ten_pow_57 - UInt256::ONE).write_decimal(&mut buf), "9".repeat(57));
+    }
+}
@@ -0,0 +1,215 @@
+//! Hex encoding and decoding of [`UInt256`] values with explicit options.
+//!
+//! [`encode`] spells out the case, the `0x` prefix and the padding instead of relying on
+//! format strings, and [`decode`] accepts what it produces while rejecting anything
+//! longer than 64 digits.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use uint256::hex::{self, Case, Pad, Prefix};
+//! use uint256::UInt256;
+//!
+//! let value = UInt256::from(0xbeef);
+//! assert_eq!(hex::encode(value, Case::Lower, Prefix::Yes, Pad::Minimal), "0xbeef");
+//! assert_eq!(hex::encode(value, Case::Upper, Prefix::No, Pad::Minimal), "BEEF");
+//!
+//! let full = hex::encode(value, Case::Lower, Prefix::Yes, Pad::Full);
+//! assert_eq!(full.len(), 66);
+//! assert_eq!(hex::decode(&full), Ok(value));
+//! assert_eq!(hex::decode("BeEf"), Ok(value));
+//! ```
+//!
+//! [`UInt256::to_quantity_hex`] and [`UInt256::from_quantity_hex`] implement the stricter
+//! "quantity" encoding of the Ethereum JSON-RPC API.
+
+use super::parse::parse_digits;
+use super::*;
+
+/// The case of the hex digits `a` to `f`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Case {
+    /// `0123456789abcdef`.
+    Lower,
+    /// `0123456789ABCDEF`.
+    Upper,
+}
+
+/// Whether the digits follow a `0x` prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prefix {
+    /// Starts with `0x`, in lower case for either [`Case`].
+    Yes,
+    /// Only the digits.
+    No,
+}
+
+/// How many digits are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pad {
+    /// All 64 digits, with leading zeros.
+    Full,
+    /// No leading zeros, and a single `0` for zero.
+    Minimal,
+}
+
+/// Encodes `value` in hex with the given options.
+pub fn encode(value: UInt256, case: Case, prefix: Prefix, pad: Pad) -> String {
+    match (case, prefix, pad) {
+        (Case::Lower, Prefix::Yes, Pad::Full) => format!("{:#066x}", value),
+        (Case::Lower, Prefix::Yes, Pad::Minimal) => format!("{:#x}", value),
+        (Case::Lower, Prefix::No, Pad::Full) => format!("{:064x}", value),
+        (Case::Lower, Prefix::No, Pad::Minimal) => format!("{:x}", value),
+        (Case::Upper, Prefix::Yes, Pad::Full) => format!("0x{:064X}", value),
+        (Case::Upper, Prefix::Yes, Pad::Minimal) => format!("0x{:X}", value),
+        (Case::Upper, Prefix::No, Pad::Full) => format!("{:064X}", value),
+        (Case::Upper, Prefix::No, Pad::Minimal) => format!("{:X}", value),
+    }
+}
+
+/// Decodes 1 to 64 hex digits in either case, optionally after a `0x` or `0X` prefix.
+///
+/// Unlike [`UInt256::from_str_radix`], whitespace is not trimmed, and more than 64 digits
+/// are a [`ParseErrorKind::InvalidLength`] error even if they are leading zeros.
+pub fn decode(s: &str) -> Result<UInt256, ParseUInt256Error> {
+    let digits = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")).unwrap_or(s);
+    let offset = s.len() - digits.len();
+    if digits.len() > 64 {
+        return Err(ParseUInt256Error::new(ParseErrorKind::InvalidLength, offset + 64));
+    }
+    parse_digits(digits, offset, 16, false)
+}
+
+impl UInt256 {
+    /// Encodes the value as an Ethereum JSON-RPC quantity: `0x` followed by the lower-case
+    /// hex digits without leading zeros, and `0x0` for zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(1024).to_quantity_hex(), "0x400");
+    /// assert_eq!(UInt256::ZERO.to_quantity_hex(), "0x0");
+    /// ```
+    pub fn to_quantity_hex(&self) -> String {
+        format!("{:#x}", self)
+    }
+
+    /// Decodes an Ethereum JSON-RPC quantity, as produced by [`Self::to_quantity_hex`].
+    ///
+    /// The JSON-RPC rules are enforced: the lower-case `0x` prefix is required, there
+    /// must be at least one digit, and only zero itself may start with `0`. Digits may be
+    /// in either case.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ParseErrorKind, UInt256};
+    ///
+    /// assert_eq!(UInt256::from_quantity_hex("0x41"), Ok(UInt256::from(65)));
+    /// assert_eq!(UInt256::from_quantity_hex("0x0"), Ok(UInt256::ZERO));
+    ///
+    /// for invalid in ["0x", "0x0400", "ff"] {
+    ///     assert!(UInt256::from_quantity_hex(invalid).is_err());
+    /// }
+    /// assert_eq!(UInt256::from_quantity_hex("0x01").unwrap_err().kind(), ParseErrorKind::LeadingZero);
+    /// ```
+    pub fn from_quantity_hex(s: &str) -> Result<Self, ParseUInt256Error> {
+        if s.is_empty() {
+            return Err(ParseUInt256Error::new(ParseErrorKind::Empty, 0));
+        }
+        let digits = s.strip_prefix("0x").ok_or(ParseUInt256Error::new(ParseErrorKind::BadPrefix, 0))?;
+        if digits.len() > 1 && digits.starts_with('0') {
+            return Err(ParseUInt256Error::new(ParseErrorKind::LeadingZero, 2));
+        }
+        parse_digits(digits, 2, 16, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let value = UInt256::from(0xabc);
+        let zeros = "0".repeat(61);
+        assert_eq!(encode(value, Case::Lower, Prefix::Yes, Pad::Full), format!("0x{}abc", zeros));
+        assert_eq!(encode(value, Case::Lower, Prefix::Yes, Pad::Minimal), "0xabc");
+        assert_eq!(encode(value, Case::Lower, Prefix::No, Pad::Full), format!("{}abc", zeros));
+        assert_eq!(encode(value, Case::Lower, Prefix::No, Pad::Minimal), "abc");
+        assert_eq!(encode(value, Case::Upper, Prefix::Yes, Pad::Full), format!("0x{}ABC", zeros));
+        assert_eq!(encode(value, Case::Upper, Prefix::Yes, Pad::Minimal), "0xABC");
+        assert_eq!(encode(value, Case::Upper, Prefix::No, Pad::Full), format!("{}ABC", zeros));
+        assert_eq!(encode(value, Case::Upper, Prefix::No, Pad::Minimal), "ABC");
+
+        assert_eq!(encode(UInt256::ZERO, Case::Lower, Prefix::Yes, Pad::Minimal), "0x0");
+        assert_eq!(encode(UInt256::MAX, Case::Upper, Prefix::No, Pad::Minimal), "F".repeat(64));
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("0"), Ok(UInt256::ZERO));
+        assert_eq!(decode("0x0"), Ok(UInt256::ZERO));
+        assert_eq!(decode("0XaBc"), Ok(UInt256::from(0xabc)));
+        assert_eq!(decode(&"f".repeat(64)), Ok(UInt256::MAX));
+        assert_eq!(decode(&format!("0x{}", "F".repeat(64))), Ok(UInt256::MAX));
+
+        for case in [Case::Lower, Case::Upper] {
+            for prefix in [Prefix::Yes, Prefix::No] {
+                for pad in [Pad::Full, Pad::Minimal] {
+                    for value in [UInt256::ZERO, UInt256::from(0x1234), UInt256::new(1, 0, Endian::Big), UInt256::MAX] {
+                        assert_eq!(decode(&encode(value, case, prefix, pad)), Ok(value));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let err = |kind, offset| Err(ParseUInt256Error::new(kind, offset));
+        assert_eq!(decode(""), err(ParseErrorKind::Empty, 0));
+        assert_eq!(decode("0x"), err(ParseErrorKind::Empty, 2));
+        assert_eq!(decode("0xg"), err(ParseErrorKind::InvalidDigit, 2));
+        assert_eq!(decode(" 0x1"), err(ParseErrorKind::InvalidDigit, 0));
+        assert_eq!(decode("0x1 "), err(ParseErrorKind::InvalidDigit, 3));
+        assert_eq!(decode("0x0x1"), err(ParseErrorKind::InvalidDigit, 3));
+        assert_eq!(decode("ab_cd"), err(ParseErrorKind::InvalidDigit, 2));
+        assert_eq!(decode(&"0".repeat(65)), err(ParseErrorKind::InvalidLength, 64));
+        assert_eq!(decode(&format!("0x{}", "f".repeat(65))), err(ParseErrorKind::InvalidLength, 66));
+        assert_eq!(decode(&"0".repeat(65)).unwrap_err().to_string(), "too many digits at offset 64");
+    }
+
+    #[test]
+    fn test_quantity_hex() {
+        assert_eq!(UInt256::ZERO.to_quantity_hex(), "0x0");
+        assert_eq!(UInt256::from(0x41).to_quantity_hex(), "0x41");
+        assert_eq!(UInt256::from(0x400).to_quantity_hex(), "0x400");
+        assert_eq!(UInt256::MAX.to_quantity_hex(), format!("0x{}", "f".repeat(64)));
+
+        assert_eq!(UInt256::from_quantity_hex("0x0"), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::from_quantity_hex("0x400"), Ok(UInt256::from(0x400)));
+        assert_eq!(UInt256::from_quantity_hex("0xABC"), Ok(UInt256::from(0xabc)));
+        for value in [UInt256::ONE, UInt256::from(0xff), UInt256::new(1, 0, Endian::Big), UInt256::MAX] {
+            assert_eq!(UInt256::from_quantity_hex(&value.to_quantity_hex()), Ok(value));
+        }
+    }
+
+    #[test]
+    fn test_quantity_hex_errors() {
+        let err = |kind, offset| Err(ParseUInt256Error::new(kind, offset));
+        assert_eq!(UInt256::from_quantity_hex(""), err(ParseErrorKind::Empty, 0));
+        assert_eq!(UInt256::from_quantity_hex("0x"), err(ParseErrorKind::Empty, 2));
+        assert_eq!(UInt256::from_quantity_hex("400"), err(ParseErrorKind::BadPrefix, 0));
+        assert_eq!(UInt256::from_quantity_hex("0X400"), err(ParseErrorKind::BadPrefix, 0));
+        assert_eq!(UInt256::from_quantity_hex("0x00"), err(ParseErrorKind::LeadingZero, 2));
+        assert_eq!(UInt256::from_quantity_hex("0x0400"), err(ParseErrorKind::LeadingZero, 2));
+        assert_eq!(UInt256::from_quantity_hex("0x4g"), err(ParseErrorKind::InvalidDigit, 3));
+        assert_eq!(UInt256::from_quantity_hex(" 0x4"), err(ParseErrorKind::BadPrefix, 0));
+        assert_eq!(UInt256::from_quantity_hex(&format!("0x1{}", "0".repeat(64))), err(ParseErrorKind::Overflow, 66));
+        assert_eq!(UInt256::from_quantity_hex("0x01").unwrap_err().to_string(), "leading zero at offset 2");
+    }
+}
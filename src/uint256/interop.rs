@@ -0,0 +1,104 @@
+//! Conversions between [`UInt256`] and the 256-bit integers of other crates, each
+//! enabled by the feature of the same name:
+//!
+//! - `primitive-types`: [`primitive_types::U256`], as used by the parity and ethers crates.
+//! - `ethnum`: [`ethnum::U256`].
+//! - `ruint`: [`ruint::Uint<256, 4>`](ruint::Uint), also known as `ruint::aliases::U256`.
+//!   Its inherent `Uint::from` shadows `From::from`, so convert to it with `into`.
+//!
+//! All of them hold the same range of values, so the conversions cannot fail. The
+//! values converted to a [`UInt256`] are big endian, like [`UInt256::from_limbs`].
+
+use super::*;
+
+#[cfg(feature = "primitive-types")]
+impl From<primitive_types::U256> for UInt256 {
+    fn from(value: primitive_types::U256) -> Self {
+        // Both store little-endian 64-bit limbs.
+        UInt256::from_limbs(value.0)
+    }
+}
+
+#[cfg(feature = "primitive-types")]
+impl From<UInt256> for primitive_types::U256 {
+    fn from(value: UInt256) -> Self {
+        primitive_types::U256(value.to_limbs())
+    }
+}
+
+#[cfg(feature = "ethnum")]
+impl From<ethnum::U256> for UInt256 {
+    fn from(value: ethnum::U256) -> Self {
+        let (high, low) = value.into_words();
+        UInt256 { high, low, endian: Endian::Big }
+    }
+}
+
+#[cfg(feature = "ethnum")]
+impl From<UInt256> for ethnum::U256 {
+    fn from(value: UInt256) -> Self {
+        ethnum::U256::from_words(value.high, value.low)
+    }
+}
+
+#[cfg(feature = "ruint")]
+impl From<ruint::Uint<256, 4>> for UInt256 {
+    fn from(value: ruint::Uint<256, 4>) -> Self {
+        UInt256::from_limbs(*value.as_limbs())
+    }
+}
+
+#[cfg(feature = "ruint")]
+impl From<UInt256> for ruint::Uint<256, 4> {
+    fn from(value: UInt256) -> Self {
+        ruint::Uint::from_limbs(value.to_limbs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::edge_values;
+
+    #[cfg(feature = "primitive-types")]
+    #[test]
+    fn test_primitive_types() {
+        use primitive_types::U256;
+
+        for value in edge_values() {
+            let other = U256::from(value);
+            assert_eq!(other.to_big_endian(), value.to_be_array());
+            assert_eq!(UInt256::from(other), value);
+        }
+        assert_eq!(U256::from(UInt256::from(42)), U256::from(42u64));
+    }
+
+    #[cfg(feature = "ethnum")]
+    #[test]
+    fn test_ethnum() {
+        use ethnum::U256;
+
+        for value in edge_values() {
+            let other = U256::from(value);
+            assert_eq!(other.to_be_bytes(), value.to_be_array());
+            assert_eq!(UInt256::from(other), value);
+        }
+        assert_eq!(U256::from(UInt256::from(42)), U256::new(42));
+    }
+
+    #[cfg(feature = "ruint")]
+    #[test]
+    fn test_ruint() {
+        use ruint::aliases::U256;
+
+        for value in edge_values() {
+            // The inherent `Uint::from` shadows `From::from`, so convert with `into`.
+            let other: U256 = value.into();
+            assert_eq!(other.to_be_bytes::<32>(), value.to_be_array());
+            assert_eq!(UInt256::from(other), value);
+        }
+        let small: U256 = UInt256::from(42).into();
+        assert_eq!(small, U256::from(42u64));
+    }
+}
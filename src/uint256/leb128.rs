@@ -0,0 +1,141 @@
+//! Unsigned LEB128 encoding of [`UInt256`] values, 7 bits per byte with the lowest
+//! bits first, so small values take few bytes.
+
+use std::io::{self, Read, Write};
+
+use super::*;
+
+/// The most bytes a 256-bit value takes, the last holding only its top 4 bits.
+const MAX_BYTES: usize = 37;
+
+impl UInt256 {
+    /// Writes the value to `writer` in unsigned LEB128 and returns the number of bytes
+    /// written, from 1 for values below 128 to 37.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let mut out = Vec::new();
+    /// assert_eq!(UInt256::from(624_485).write_leb128(&mut out).unwrap(), 3);
+    /// assert_eq!(out, [0xe5, 0x8e, 0x26]);
+    /// ```
+    pub fn write_leb128<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<usize> {
+        let mut buf = [0u8; MAX_BYTES];
+        let mut rest = *self;
+        let mut len = 0;
+        loop {
+            let byte = (rest.low & 0x7f) as u8;
+            rest >>= 7;
+            if rest.is_zero() {
+                buf[len] = byte;
+                len += 1;
+                break;
+            }
+            buf[len] = byte | 0x80;
+            len += 1;
+        }
+        writer.write_all(&buf[..len])?;
+        Ok(len)
+    }
+
+    /// Reads a value in unsigned LEB128 from `reader`, as written by
+    /// [`Self::write_leb128`], consuming exactly its bytes.
+    ///
+    /// Encodings padded with `0x80` bytes are accepted up to 37 bytes. Fails with
+    /// [`io::ErrorKind::InvalidData`] if the value does not fit in 256 bits, and with
+    /// [`io::ErrorKind::UnexpectedEof`] if the input ends before the last byte.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let mut input = &[0xe5, 0x8e, 0x26, 0x01][..];
+    /// assert_eq!(UInt256::read_leb128(&mut input).unwrap(), UInt256::from(624_485));
+    /// assert_eq!(input, [0x01]);
+    /// ```
+    pub fn read_leb128<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self> {
+        let mut value = UInt256::ZERO;
+        for i in 0..MAX_BYTES {
+            let mut byte = [0u8];
+            reader.read_exact(&mut byte)?;
+            let bits = byte[0] & 0x7f;
+            if i == MAX_BYTES - 1 && (bits >> 4 != 0 || byte[0] & 0x80 != 0) {
+                break;
+            }
+            value |= UInt256::new(0, bits as u128, Endian::Big) << (7 * i as u32);
+            if byte[0] & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(io::Error::new(io::ErrorKind::InvalidData, "LEB128 value does not fit in UInt256"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{random_values, SEED};
+
+    fn encode(value: UInt256) -> Vec<u8> {
+        let mut out = Vec::new();
+        let len = value.write_leb128(&mut out).unwrap();
+        assert_eq!(len, out.len());
+        out
+    }
+
+    #[test]
+    fn test_leb128_vectors() {
+        assert_eq!(encode(UInt256::ZERO), [0x00]);
+        assert_eq!(encode(UInt256::from(0x7f)), [0x7f]);
+        assert_eq!(encode(UInt256::from(0x80)), [0x80, 0x01]);
+        assert_eq!(encode(UInt256::from(300)), [0xac, 0x02]);
+        assert_eq!(encode(UInt256::from(624_485)), [0xe5, 0x8e, 0x26]);
+
+        let mut max = vec![0xff; 36];
+        max.push(0x0f);
+        assert_eq!(encode(UInt256::MAX), max);
+        assert_eq!(UInt256::read_leb128(&mut &max[..]).unwrap(), UInt256::MAX);
+    }
+
+    #[test]
+    fn test_leb128_round_trip() {
+        let mut stream = Vec::new();
+        let mut values = Vec::new();
+        for value in random_values(SEED, 200) {
+            let len = value.write_leb128(&mut stream).unwrap();
+            assert_eq!(len, (256 - value.leading_zeros() as usize).div_ceil(7).max(1));
+            values.push(value);
+        }
+        let mut input = &stream[..];
+        for value in values {
+            assert_eq!(UInt256::read_leb128(&mut input).unwrap(), value);
+        }
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn test_leb128_errors() {
+        // Padding is accepted.
+        assert_eq!(UInt256::read_leb128(&mut &[0x81, 0x80, 0x00][..]).unwrap(), UInt256::ONE);
+
+        let err = UInt256::read_leb128(&mut &[0x80, 0x80][..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        let err = UInt256::read_leb128(&mut &[][..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        // A 257th bit, and a 38th byte.
+        let mut too_large = vec![0xff; 36];
+        too_large.push(0x1f);
+        let err = UInt256::read_leb128(&mut &too_large[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        let mut too_long = vec![0x80; 37];
+        too_long.push(0x00);
+        let err = UInt256::read_leb128(&mut &too_long[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "LEB128 value does not fit in UInt256");
+    }
+}
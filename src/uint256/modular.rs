@@ -0,0 +1,426 @@
+//! Modular arithmetic on [`UInt256`] values.
+//!
+//! Intermediate results are computed at full width (257 bits for sums, 512 bits for
+//! products) before reduction, so any operands are accepted, not only reduced ones.
+//! Following the EVM `ADDMOD` and `MULMOD` opcodes, a modulus of zero yields zero.
+
+use super::division::{div_rem_wide, NormalizedDivisor};
+use super::*;
+
+impl UInt256 {
+    /// Calculates `(self + rhs) % modulus` without overflowing on the intermediate sum.
+    ///
+    /// Returns zero if `modulus` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let m = UInt256::from(10);
+    /// assert_eq!(UInt256::MAX.mod_add(UInt256::from(2), m), UInt256::from(7));
+    /// ```
+    pub fn mod_add(self, rhs: Self, modulus: Self) -> Self {
+        if modulus.is_zero() {
+            return UInt256 { endian: self.endian, ..UInt256::ZERO };
+        }
+        match self.overflowing_add(rhs) {
+            (sum, false) => sum % modulus,
+            // The carry is the 257th bit of the sum.
+            (sum, true) => div_rem_wide(sum, UInt256::ONE, modulus).1,
+        }
+    }
+
+    /// Calculates `(self - rhs) mod modulus`, always returning a value in `[0, modulus)`.
+    ///
+    /// Returns zero if `modulus` is zero.
+    pub fn mod_sub(self, rhs: Self, modulus: Self) -> Self {
+        if modulus.is_zero() {
+            return UInt256 { endian: self.endian, ..UInt256::ZERO };
+        }
+        let a = self % modulus;
+        let b = rhs % modulus;
+        if a >= b {
+            a - b
+        } else {
+            // `modulus - b` is in `(0, modulus)`, so the sum cannot overflow.
+            a + (modulus - b)
+        }
+    }
+
+    /// Calculates `(self * rhs) % modulus` through a 512-bit intermediate product.
+    ///
+    /// Returns zero if `modulus` is zero.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let m = UInt256::from(1_000_000_007);
+    /// assert_eq!(UInt256::MAX.mod_mul(UInt256::MAX, m), UInt256::from(832_694_962));
+    /// ```
+    pub fn mod_mul(self, rhs: Self, modulus: Self) -> Self {
+        if modulus.is_zero() {
+            return UInt256 { endian: self.endian, ..UInt256::ZERO };
+        }
+        let (low, high) = self.widening_mul(rhs);
+        div_rem_wide(low, high, modulus).1
+    }
+
+    /// Calculates `self.pow(exponent) % modulus`, reducing every 512-bit intermediate
+    /// product.
+    ///
+    /// Uses square-and-multiply for short exponents and [`Self::mod_pow_window`] with a
+    /// window size chosen from the length of the exponent for longer ones.
+    ///
+    /// Returns zero if `modulus` is zero. `0.mod_pow(0, m)` is `1 % m`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let m = UInt256::from(497);
+    /// assert_eq!(UInt256::from(4).mod_pow(UInt256::from(13), m), UInt256::from(445));
+    /// ```
+    pub fn mod_pow(self, exponent: Self, modulus: Self) -> Self {
+        let window_bits = match 256 - exponent.leading_zeros() {
+            0..=16 => 1,
+            17..=64 => 3,
+            65..=160 => 4,
+            _ => 5,
+        };
+        self.mod_pow_window(exponent, modulus, window_bits)
+    }
+
+    /// Calculates `self.pow(exponent) % modulus` with sliding-window exponentiation.
+    ///
+    /// The odd powers `self^1, self^3, ..., self^(2^window_bits - 1)` are precomputed, so
+    /// each run of up to `window_bits` exponent bits costs a single multiplication
+    /// instead of one per set bit. A window of 1 is plain square-and-multiply.
+    ///
+    /// Returns zero if `modulus` is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `window_bits` is not between 1 and 8.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let m = UInt256::from(1_000_000_007);
+    /// let e = UInt256::MAX - UInt256::from(12345);
+    /// let base = UInt256::from(3);
+    /// assert_eq!(base.mod_pow_window(e, m, 6), base.mod_pow_window(e, m, 1));
+    /// ```
+    pub fn mod_pow_window(self, exponent: Self, modulus: Self, window_bits: u32) -> Self {
+        assert!((1..=8).contains(&window_bits), "window_bits must be between 1 and 8");
+        if modulus.is_zero() {
+            return UInt256 { endian: self.endian, ..UInt256::ZERO };
+        }
+        let divisor = NormalizedDivisor::new(&modulus.to_limbs());
+        let base = reduce(self, UInt256::ZERO, &divisor);
+        let mut result = reduce(UInt256::ONE, UInt256::ZERO, &divisor);
+
+        // table[k] = base^(2k + 1)
+        let mut table = vec![base; 1 << (window_bits - 1)];
+        let square = mul_reduce(base, base, &divisor);
+        for k in 1..table.len() {
+            table[k] = mul_reduce(table[k - 1], square, &divisor);
+        }
+
+        let window_bits = window_bits as usize;
+        let mut i = (256 - exponent.leading_zeros() as usize) as isize - 1;
+        while i >= 0 {
+            let top = i as usize;
+            if !exponent.bit_at(top) {
+                result = mul_reduce(result, result, &divisor);
+                i -= 1;
+                continue;
+            }
+            // The longest window of at most `window_bits` bits that starts at a set bit
+            // and also ends at one, so that its value is odd.
+            let mut bottom = (top + 1).saturating_sub(window_bits);
+            while !exponent.bit_at(bottom) {
+                bottom += 1;
+            }
+            let mut window = 0usize;
+            for j in (bottom..=top).rev() {
+                result = mul_reduce(result, result, &divisor);
+                window = window << 1 | exponent.bit_at(j) as usize;
+            }
+            result = mul_reduce(result, table[window >> 1], &divisor);
+            i = bottom as isize - 1;
+        }
+        UInt256 { endian: self.endian, ..result }
+    }
+
+    /// Returns a square root of `self` modulo the prime `prime_modulus`, i.e. a value `r`
+    /// such that `r * r ≡ self (mod p)`, or `None` if `self` is not a quadratic residue.
+    ///
+    /// The other root is `p - r`. Uses the direct formula `self^((p + 1) / 4)` when
+    /// `p ≡ 3 (mod 4)` and the Tonelli–Shanks algorithm otherwise.
+    ///
+    /// The modulus should be prime. An even modulus other than 2 gives `None`, and an odd
+    /// composite one gives `None` or a root that is checked to actually square to `self`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let p = UInt256::from(13);
+    /// let r = UInt256::from(10).mod_sqrt(p).unwrap();
+    /// assert!(r == UInt256::from(6) || r == UInt256::from(7));
+    /// assert_eq!(UInt256::from(5).mod_sqrt(p), None);
+    /// ```
+    pub fn mod_sqrt(self, prime_modulus: Self) -> Option<Self> {
+        let p = prime_modulus;
+        if p.is_zero() {
+            return None;
+        }
+        let a = self % p;
+        if a.is_zero() || p == UInt256::from(2) {
+            return Some(a);
+        }
+        if p.low & 1 == 0 {
+            return None;
+        }
+
+        if a.jacobi(p) != 1 {
+            return None;
+        }
+
+        let root = if p.low & 3 == 3 {
+            // (p + 1) / 4 without overflowing p + 1.
+            a.mod_pow((p >> 2u32) + UInt256::ONE, p)
+        } else {
+            tonelli_shanks(a, p)?
+        };
+
+        if root.mod_mul(root, p) == a {
+            Some(UInt256 { endian: self.endian, ..root })
+        } else {
+            None
+        }
+    }
+}
+
+/// Tonelli–Shanks for an odd prime `p` and a quadratic residue `a`, or `None` if it
+/// finds that `p` is not prime.
+fn tonelli_shanks(a: UInt256, p: UInt256) -> Option<UInt256> {
+    let p_minus_one = p - UInt256::ONE;
+
+    // Write p - 1 = q * 2^s with q odd.
+    let s = p_minus_one.trailing_zeros();
+    let q = p_minus_one >> s;
+
+    // Find a quadratic non-residue z. Half of all values qualify, and under the GRH a
+    // prime has one below 2 ln(p)^2 (Bach), which is less than 2 * bits^2. A composite
+    // such as a perfect square may have none, so give up there instead of counting to p.
+    let bits = 256 - p.leading_zeros();
+    let limit = UInt256::from(2 * (bits * bits) as usize).min(p);
+    let mut z = UInt256::from(2);
+    while z.jacobi(p) != -1 {
+        z += UInt256::ONE;
+        if z >= limit {
+            return None;
+        }
+    }
+
+    let mut m = s;
+    let mut c = z.mod_pow(q, p);
+    let mut t = a.mod_pow(q, p);
+    let mut r = a.mod_pow((q >> 1u32) + UInt256::ONE, p);
+
+    while t != UInt256::ONE {
+        // Find the least i with t^(2^i) = 1.
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != UInt256::ONE {
+            t2 = t2.mod_mul(t2, p);
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+
+        let mut b = c;
+        for _ in 0..m - i - 1 {
+            b = b.mod_mul(b, p);
+        }
+        m = i;
+        c = b.mod_mul(b, p);
+        t = t.mod_mul(c, p);
+        r = r.mod_mul(b, p);
+    }
+    Some(r)
+}
+
+/// Reduces the 512-bit value `high:low` by a prepared modulus.
+fn reduce(low: UInt256, high: UInt256, modulus: &NormalizedDivisor) -> UInt256 {
+    let mut u = [0u64; 8];
+    u[..4].copy_from_slice(&low.to_limbs());
+    u[4..].copy_from_slice(&high.to_limbs());
+    let (_, r) = modulus.div_rem(&u);
+    UInt256::from_limbs_with(r, low.endian)
+}
+
+/// Calculates `(a * b) % modulus` with a prepared modulus.
+fn mul_reduce(a: UInt256, b: UInt256, modulus: &NormalizedDivisor) -> UInt256 {
+    let (low, high) = a.widening_mul(b);
+    reduce(low, high, modulus)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_mod_add() {
+        let m = UInt256::from(7);
+        assert_eq!(UInt256::from(5).mod_add(UInt256::from(4), m), UInt256::from(2));
+        assert_eq!(UInt256::from(50).mod_add(UInt256::from(40), m), UInt256::from(6));
+        // The intermediate sums below need 257 bits.
+        assert_eq!(UInt256::MAX.mod_add(UInt256::MAX, UInt256::MAX), UInt256::ZERO);
+        let m = UInt256::MAX - UInt256::ONE;
+        assert_eq!(UInt256::MAX.mod_add(UInt256::MAX, m), UInt256::from(2));
+    }
+
+    #[test]
+    fn test_mod_sub() {
+        let m = UInt256::from(7);
+        assert_eq!(UInt256::from(5).mod_sub(UInt256::from(4), m), UInt256::ONE);
+        assert_eq!(UInt256::from(4).mod_sub(UInt256::from(5), m), UInt256::from(6));
+        assert_eq!(UInt256::ZERO.mod_sub(UInt256::MAX, UInt256::MAX), UInt256::ZERO);
+        assert_eq!(UInt256::ZERO.mod_sub(UInt256::ONE, UInt256::MAX), UInt256::MAX - UInt256::ONE);
+    }
+
+    #[test]
+    fn test_mod_mul() {
+        let m = UInt256::from(13);
+        assert_eq!(UInt256::from(6).mod_mul(UInt256::from(5), m), UInt256::from(4));
+        // (2^256 - 1)^2 mod (2^256 - 2) = 1
+        let m = UInt256::MAX - UInt256::ONE;
+        assert_eq!(UInt256::MAX.mod_mul(UInt256::MAX, m), UInt256::ONE);
+        assert_eq!(UInt256::MAX.mod_mul(UInt256::MAX, UInt256::MAX), UInt256::ZERO);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        let m = UInt256::from(1_000_000_007);
+        assert_eq!(UInt256::from(2).mod_pow(UInt256::from(10), m), UInt256::from(1024));
+        // Fermat's little theorem: a^(p - 1) = 1 mod p
+        assert_eq!(UInt256::from(12345).mod_pow(m - UInt256::ONE, m), UInt256::ONE);
+        assert_eq!(UInt256::from(7).mod_pow(UInt256::ZERO, m), UInt256::ONE);
+        assert_eq!(UInt256::ZERO.mod_pow(UInt256::ZERO, UInt256::ONE), UInt256::ZERO);
+        assert_eq!(UInt256::ZERO.mod_pow(UInt256::from(5), m), UInt256::ZERO);
+    }
+
+    #[test]
+    fn test_mod_pow_large_modulus() {
+        // secp256k1 field prime p = 2^256 - 2^32 - 977; 3^(p - 1) = 1 mod p.
+        let p = UInt256::MAX - UInt256::from(0x1_0000_03d0);
+        assert_eq!(UInt256::from(3).mod_pow(p - UInt256::ONE, p), UInt256::ONE);
+        // 2^255 mod (2^256 - 1) = 2^255 (the exponent reaches the top bit).
+        let top = UInt256::new(1 << 127, 0, Endian::Big);
+        assert_eq!(UInt256::from(2).mod_pow(UInt256::from(255), UInt256::MAX), top);
+        assert_eq!(UInt256::from(2).mod_pow(UInt256::from(256), UInt256::MAX), UInt256::ONE);
+    }
+
+    #[test]
+    fn test_mod_pow_window_sizes() {
+        let moduli = [UInt256::from(1_000_000_007), UInt256::MAX - UInt256::from(0x1_0000_03d0), UInt256::MAX];
+        let exponents = [
+            UInt256::ZERO,
+            UInt256::ONE,
+            UInt256::from(0b1000_0001),
+            UInt256::from(0xffff),
+            UInt256::new(0, 1 << 100, Endian::Big),
+            UInt256::new(0x8000_0000_0000_0001, 0xdead_beef_0000_cafe, Endian::Big),
+            UInt256::MAX,
+        ];
+        let base = UInt256::new(0x1234_5678, 0x9abc_def0, Endian::Big);
+        for m in moduli {
+            for e in exponents {
+                let expected = base.mod_pow_window(e, m, 1);
+                assert_eq!(base.mod_pow(e, m), expected);
+                for window_bits in 2..=8 {
+                    assert_eq!(base.mod_pow_window(e, m, window_bits), expected, "{} ^ {} mod {}", base, e, m);
+                }
+            }
+        }
+        // Square-and-multiply against a value computed independently.
+        let m = UInt256::from(1_000_000_007);
+        assert_eq!(UInt256::from(3).mod_pow_window(UInt256::from(1_000_000), m, 4), UInt256::from(64_935_414));
+    }
+
+    #[test]
+    #[should_panic(expected = "window_bits must be between 1 and 8")]
+    fn test_mod_pow_window_zero_bits() {
+        let _ = UInt256::from(3).mod_pow_window(UInt256::from(5), UInt256::from(7), 0);
+    }
+
+    #[test]
+    fn test_mod_sqrt_three_mod_four() {
+        // secp256k1 field prime, which is 3 mod 4.
+        let p = UInt256::MAX - UInt256::from(0x1_0000_03d0);
+        for x in [UInt256::from(2), UInt256::from(123_456_789), p - UInt256::from(7)] {
+            let square = x.mod_mul(x, p);
+            let root = square.mod_sqrt(p).unwrap();
+            assert!(root == x || root == p - x);
+        }
+        // -1 is not a residue modulo a prime that is 3 mod 4.
+        assert_eq!((p - UInt256::ONE).mod_sqrt(p), None);
+    }
+
+    #[test]
+    fn test_mod_sqrt_tonelli_shanks() {
+        // 2^255 - 19 and 17 are both 1 mod 4, and 2^255 - 19 is 5 mod 8.
+        let p25519 = UInt256::new(1 << 126, 0, Endian::Big) * UInt256::from(2) - UInt256::from(19);
+        for p in [UInt256::from(17), UInt256::from(1_000_000_009), p25519] {
+            for x in [UInt256::from(3), UInt256::from(5), UInt256::from(16)] {
+                let square = x.mod_mul(x, p);
+                let root = square.mod_sqrt(p).unwrap();
+                assert_eq!(root.mod_mul(root, p), square);
+            }
+        }
+        assert_eq!(UInt256::from(3).mod_sqrt(UInt256::from(17)), None);
+    }
+
+    #[test]
+    fn test_mod_sqrt_edge_cases() {
+        let p = UInt256::from(13);
+        assert_eq!(UInt256::ZERO.mod_sqrt(p), Some(UInt256::ZERO));
+        assert_eq!(UInt256::from(26).mod_sqrt(p), Some(UInt256::ZERO));
+        assert_eq!(UInt256::from(3).mod_sqrt(UInt256::from(2)), Some(UInt256::ONE));
+        assert_eq!(UInt256::from(3).mod_sqrt(UInt256::ZERO), None);
+    }
+
+    #[test]
+    fn test_mod_sqrt_composite_modulus() {
+        // Even moduli other than 2 have no Jacobi symbol.
+        assert_eq!(UInt256::ONE.mod_sqrt(UInt256::from(4)), None);
+        assert_eq!(UInt256::from(4).mod_sqrt(UInt256::from(6)), None);
+        assert_eq!(UInt256::from(9).mod_sqrt(UInt256::MAX - UInt256::ONE), None);
+
+        // Every Jacobi symbol modulo a perfect square is 0 or 1, so the non-residue search gives up.
+        let q = UInt256::from_parts(0, u128::MAX >> 1);
+        let p = q * q;
+        assert_eq!(p.low & 3, 1);
+        assert_eq!(UInt256::from(4).mod_sqrt(p), None);
+    }
+
+    #[test]
+    fn test_zero_modulus() {
+        let a = UInt256::from(5);
+        assert_eq!(a.mod_add(a, UInt256::ZERO), UInt256::ZERO);
+        assert_eq!(a.mod_sub(a, UInt256::ZERO), UInt256::ZERO);
+        assert_eq!(a.mod_mul(a, UInt256::ZERO), UInt256::ZERO);
+        assert_eq!(a.mod_pow(a, UInt256::ZERO), UInt256::ZERO);
+    }
+}
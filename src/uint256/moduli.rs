@@ -0,0 +1,308 @@
+//! Well-known moduli, with reductions specialized to the structure of each.
+//!
+//! Every modulus has a constant and a type implementing [`Modulus`], whose
+//! [`Modulus::reduce`] avoids the generic 512-bit division:
+//!
+//! - [`Secp256k1P`] and [`Secp256k1N`] are pseudo-Mersenne, `2^256 - c` for a small `c`,
+//!   so the part above 2^256 is folded back in as a multiple of `c`.
+//! - [`Ed25519L`] is `2^252 + δ` for a 125-bit `δ`, and is reduced the same way with
+//!   alternating signs.
+//! - [`Bn254P`] and [`Bn254R`] have no such structure and use Montgomery reduction
+//!   with precomputed constants.
+//!
+//! The reductions are branch-free with respect to the reduced value.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use uint256::moduli::{Modulus, Secp256k1P, SECP256K1_P};
+//! use uint256::UInt256;
+//!
+//! let a = SECP256K1_P - UInt256::ONE;
+//! assert_eq!(Secp256k1P::mul_mod(a, a), UInt256::ONE);
+//! assert_eq!(Secp256k1P::reduce(UInt256::MAX.widening_mul(UInt256::MAX)), UInt256::MAX.mod_mul(UInt256::MAX, SECP256K1_P));
+//! ```
+
+use super::division::div_rem_wide;
+use super::montgomery::montgomery_mul;
+use super::*;
+
+/// The order of the secp256k1 base field, `2^256 - 2^32 - 977`.
+pub const SECP256K1_P: UInt256 = UInt256 {
+    high: 0xffffffff_ffffffff_ffffffff_ffffffff,
+    low: 0xffffffff_ffffffff_fffffffe_fffffc2f,
+    endian: Endian::Big,
+};
+
+/// The order of the secp256k1 group.
+pub const SECP256K1_N: UInt256 = UInt256 {
+    high: 0xffffffff_ffffffff_ffffffff_fffffffe,
+    low: 0xbaaedce6_af48a03b_bfd25e8c_d0364141,
+    endian: Endian::Big,
+};
+
+/// The order ℓ of the prime-order subgroup of Curve25519, `2^252 + 27742317777372353535851937790883648493`.
+pub const ED25519_L: UInt256 = UInt256 {
+    high: 0x10000000_00000000_00000000_00000000,
+    low: 0x14def9de_a2f79cd6_5812631a_5cf5d3ed,
+    endian: Endian::Big,
+};
+
+/// The order of the BN254 (alt_bn128) base field.
+pub const BN254_P: UInt256 = UInt256 {
+    high: 0x30644e72_e131a029_b85045b6_8181585d,
+    low: 0x97816a91_6871ca8d_3c208c16_d87cfd47,
+    endian: Endian::Big,
+};
+
+/// The order of the BN254 (alt_bn128) scalar field, i.e. of its groups.
+pub const BN254_R: UInt256 = UInt256 {
+    high: 0x30644e72_e131a029_b85045b6_8181585d,
+    low: 0x2833e848_79b97091_43e1f593_f0000001,
+    endian: Endian::Big,
+};
+
+/// A modulus known at compile time, with a reduction of 512-bit values.
+///
+/// Implementing it for a custom modulus only needs [`Self::MODULUS`]; the default
+/// [`Self::reduce`] goes through the generic division.
+pub trait Modulus {
+    /// The modulus.
+    const MODULUS: UInt256;
+
+    /// Reduces the 512-bit value given as a `(low, high)` pair, as returned by
+    /// [`UInt256::widening_mul`], modulo [`Self::MODULUS`].
+    fn reduce((low, high): (UInt256, UInt256)) -> UInt256 {
+        div_rem_wide(low, high, Self::MODULUS).1
+    }
+
+    /// Calculates `(a * b) % MODULUS`.
+    fn mul_mod(a: UInt256, b: UInt256) -> UInt256 {
+        Self::reduce(a.widening_mul(b))
+    }
+}
+
+/// The secp256k1 base field modulus, [`SECP256K1_P`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Secp256k1P;
+
+/// The secp256k1 group order, [`SECP256K1_N`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Secp256k1N;
+
+/// The ed25519 group order, [`ED25519_L`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Ed25519L;
+
+/// The BN254 base field modulus, [`BN254_P`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bn254P;
+
+/// The BN254 scalar field modulus, [`BN254_R`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bn254R;
+
+impl Modulus for Secp256k1P {
+    const MODULUS: UInt256 = SECP256K1_P;
+
+    fn reduce(wide: (UInt256, UInt256)) -> UInt256 {
+        // 2^256 - p = 2^32 + 977
+        reduce_pseudo_mersenne(wide, UInt256 { high: 0, low: 0x1_0000_03d1, endian: Endian::Big }, SECP256K1_P)
+    }
+}
+
+impl Modulus for Secp256k1N {
+    const MODULUS: UInt256 = SECP256K1_N;
+
+    fn reduce(wide: (UInt256, UInt256)) -> UInt256 {
+        // 2^256 - n, which is 129 bits long.
+        let c = UInt256 { high: 1, low: 0x4551231950b75fc4402da1732fc9bebf, endian: Endian::Big };
+        reduce_pseudo_mersenne(wide, c, SECP256K1_N)
+    }
+}
+
+impl Modulus for Ed25519L {
+    const MODULUS: UInt256 = ED25519_L;
+
+    fn reduce((low, high): (UInt256, UInt256)) -> UInt256 {
+        // 2^256 = 16 * 2^252 ≡ -16δ (mod ℓ), so x = high * 2^256 + low ≡ low - high * 16δ.
+        // Splitting each product at 2^256 and folding again gives
+        // x ≡ low - a + c - e, where every term is below 2^256.
+        let delta16 = UInt256 { high: 1, low: 0x4def9dea2f79cd65812631a5cf5d3ed0, endian: Endian::Big };
+        let (a, b) = high.widening_mul(delta16);
+        let (c, d) = b.widening_mul(delta16);
+        let e = d.wrapping_mul(delta16);
+
+        let m = ED25519_L;
+        let r = sub_mod(reduce_ed25519_l_narrow(low), reduce_ed25519_l_narrow(a), m);
+        let r = add_mod(r, reduce_ed25519_l_narrow(c), m);
+        let r = sub_mod(r, reduce_ed25519_l_narrow(e), m);
+        UInt256 { endian: low.endian, ..r }
+    }
+}
+
+impl Modulus for Bn254P {
+    const MODULUS: UInt256 = BN254_P;
+
+    fn reduce(wide: (UInt256, UInt256)) -> UInt256 {
+        let r2 = UInt256 {
+            high: 0x06d89f71_cab8351f_47ab1eff_0a417ff6,
+            low: 0xb5e71911_d44501fb_f32cfc5b_538afa89,
+            endian: Endian::Big,
+        };
+        let r3 = UInt256 {
+            high: 0x20fd6e90_2d592544_ef7f0b0c_0ada0afb,
+            low: 0x62f210e6_a7283db6_b1cd6daf_da1530df,
+            endian: Endian::Big,
+        };
+        reduce_montgomery(wide, BN254_P, 0x87d20782e4866389, r2, r3)
+    }
+}
+
+impl Modulus for Bn254R {
+    const MODULUS: UInt256 = BN254_R;
+
+    fn reduce(wide: (UInt256, UInt256)) -> UInt256 {
+        let r2 = UInt256 {
+            high: 0x0216d0b1_7f4e44a5_8c49833d_53bb8085,
+            low: 0x53fe3ab1_e35c59e3_1bb8e645_ae216da7,
+            endian: Endian::Big,
+        };
+        let r3 = UInt256 {
+            high: 0x0cf8594b_7fcc657c_893cc664_a19fcfed,
+            low: 0x2a489cbe_1cfbb6b8_5e94d8e1_b4bf0040,
+            endian: Endian::Big,
+        };
+        reduce_montgomery(wide, BN254_R, 0xc2e1f593efffffff, r2, r3)
+    }
+}
+
+/// Reduces modulo `2^256 - c`, for a `c` below 2^130, using `2^256 ≡ c`.
+fn reduce_pseudo_mersenne((mut low, mut high): (UInt256, UInt256), c: UInt256, modulus: UInt256) -> UInt256 {
+    // Each fold replaces `high * 2^256` with `high * c`. For c < 2^130 the high part
+    // drops below 2^131, then below 2^6, then to at most 1 and finally to 0, so four
+    // folds always suffice.
+    for _ in 0..4 {
+        let (product_low, product_high) = high.widening_mul(c);
+        let (sum, carry) = low.overflowing_add(product_low);
+        low = sum;
+        high = product_high.wrapping_add(UInt256 { high: 0, low: carry as u128, endian: Endian::Big });
+    }
+    debug_assert!(high.is_zero());
+
+    // low < 2^256 < 2 * modulus, so one subtraction is enough.
+    let (difference, borrow) = low.overflowing_sub(modulus);
+    UInt256::conditional_select(&difference, &low, Choice::from(borrow as u8))
+}
+
+/// Reduces a value below 2^256 modulo ℓ = 2^252 + δ, using `2^252 ≡ -δ`.
+fn reduce_ed25519_l_narrow(value: UInt256) -> UInt256 {
+    let mut limbs = value.to_limbs();
+    let top = limbs[3] >> 60;
+    limbs[3] &= (1 << 60) - 1;
+    // value = top * 2^252 + rest, with rest < 2^252 < ℓ and top * δ < 2^129 < ℓ.
+    let rest = UInt256::from_limbs_with(limbs, value.endian);
+    let delta = UInt256 { high: 0, low: 0x14def9dea2f79cd65812631a5cf5d3ed, endian: Endian::Big };
+    let top_delta = delta.wrapping_mul(UInt256 { high: 0, low: top as u128, endian: Endian::Big });
+    sub_mod(rest, top_delta, ED25519_L)
+}
+
+/// Reduces `x = high * R + low` modulo `modulus`, given `-modulus^-1 mod 2^64` and
+/// `R^2` and `R^3` modulo `modulus`, for `R = 2^256`.
+fn reduce_montgomery(
+    (low, high): (UInt256, UInt256),
+    modulus: UInt256,
+    n_prime: u64,
+    r2: UInt256,
+    r3: UInt256,
+) -> UInt256 {
+    let n = modulus.to_limbs();
+    let mul = |a: UInt256, b: UInt256| {
+        UInt256::from_limbs_with(montgomery_mul(&a.to_limbs(), &b.to_limbs(), &n, n_prime), low.endian)
+    };
+    // Each Montgomery multiplication divides by R: low * R^2 and high * R^3 become
+    // low * R and high * R^2, whose sum is x * R. One more multiplication by 1 gives x.
+    let x_r = add_mod(mul(low, r2), mul(high, r3), modulus);
+    mul(x_r, UInt256::ONE)
+}
+
+/// Calculates `(a + b) mod m` for reduced `a` and `b` and `m < 2^255`.
+fn add_mod(a: UInt256, b: UInt256, m: UInt256) -> UInt256 {
+    let sum = a.wrapping_add(b);
+    let (difference, borrow) = sum.overflowing_sub(m);
+    UInt256::conditional_select(&difference, &sum, Choice::from(borrow as u8))
+}
+
+/// Calculates `(a - b) mod m` for reduced `a` and `b`.
+fn sub_mod(a: UInt256, b: UInt256, m: UInt256) -> UInt256 {
+    let (difference, borrow) = a.overflowing_sub(b);
+    let wrapped = difference.wrapping_add(m);
+    UInt256::conditional_select(&difference, &wrapped, Choice::from(borrow as u8))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{xorshift, SEED};
+
+    /// Checks `M::reduce` against the generic division for edge cases and
+    /// xorshift-generated 512-bit values.
+    fn check_reduce<M: Modulus>() {
+        let m = M::MODULUS;
+        let mut cases = vec![
+            (UInt256::ZERO, UInt256::ZERO),
+            (m, UInt256::ZERO),
+            (m - UInt256::ONE, UInt256::ZERO),
+            (UInt256::MAX, UInt256::ZERO),
+            (UInt256::MAX, UInt256::MAX),
+            (m - UInt256::ONE).widening_mul(m - UInt256::ONE),
+            m.widening_mul(m),
+        ];
+        let mut next = xorshift(SEED);
+        for _ in 0..200 {
+            let low = UInt256::from_limbs_with([next(), next(), next(), next()], Endian::Big);
+            let high = UInt256::from_limbs_with([next(), next(), next(), next()], Endian::Big);
+            cases.push((low, high));
+        }
+
+        for (low, high) in cases {
+            let expected = div_rem_wide(low, high, m).1;
+            assert_eq!(M::reduce((low, high)), expected, "{:?} mod {}", (low, high), m);
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_reductions() {
+        check_reduce::<Secp256k1P>();
+        check_reduce::<Secp256k1N>();
+    }
+
+    #[test]
+    fn test_ed25519_reduction() {
+        check_reduce::<Ed25519L>();
+    }
+
+    #[test]
+    fn test_bn254_reductions() {
+        check_reduce::<Bn254P>();
+        check_reduce::<Bn254R>();
+    }
+
+    #[test]
+    fn test_default_reduction() {
+        struct Small;
+        impl Modulus for Small {
+            const MODULUS: UInt256 = UInt256 { high: 0, low: 1_000_000_007, endian: Endian::Big };
+        }
+        check_reduce::<Small>();
+        assert_eq!(Small::mul_mod(UInt256::MAX, UInt256::MAX), UInt256::from(832_694_962));
+    }
+
+    #[test]
+    fn test_moduli_are_prime() {
+        for m in [SECP256K1_P, SECP256K1_N, ED25519_L, BN254_P, BN254_R] {
+            assert!(m.is_probable_prime(20), "{}", m);
+        }
+    }
+}
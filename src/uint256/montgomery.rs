@@ -0,0 +1,208 @@
+//! Montgomery multiplication for repeated arithmetic modulo a fixed odd modulus.
+
+use super::*;
+
+/// Precomputed values for Montgomery arithmetic modulo a fixed odd modulus.
+///
+/// Values are converted into Montgomery form `a * R mod n`, with `R = 2^256`, where
+/// a modular multiplication only needs multiplications and shifts instead of a
+/// 512-bit division. Convert back with [`Self::from_montgomery`] once done.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::{MontgomeryContext, UInt256};
+///
+/// let ctx = MontgomeryContext::new(UInt256::from(1_000_000_007)).unwrap();
+/// let a = ctx.to_montgomery(UInt256::from(123_456));
+/// let b = ctx.to_montgomery(UInt256::from(654_321));
+/// let product = ctx.from_montgomery(ctx.mul(a, b));
+/// assert_eq!(product, UInt256::from(123_456).mod_mul(UInt256::from(654_321), UInt256::from(1_000_000_007)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct MontgomeryContext {
+    /// The odd modulus `n`.
+    modulus: UInt256,
+    /// `-n^-1 mod 2^64`, used to clear one limb per reduction step.
+    n_prime: u64,
+    /// `R mod n`, which is 1 in Montgomery form.
+    r: UInt256,
+    /// `R^2 mod n`, used to convert values into Montgomery form.
+    r2: UInt256,
+}
+
+impl MontgomeryContext {
+    /// Prepare a context for the given modulus.
+    ///
+    /// Returns `None` if `modulus` is even, since Montgomery reduction requires the
+    /// modulus to be coprime with `R = 2^256`.
+    pub fn new(modulus: UInt256) -> Option<Self> {
+        if modulus.is_zero() || modulus.is_even() {
+            return None;
+        }
+
+        let n_prime = inv_mod_2pow64(modulus.low as u64).wrapping_neg();
+        // R mod n = (2^256 - 1 + 1) mod n, computed without overflowing.
+        let r = UInt256::MAX.mod_add(UInt256::ONE, modulus);
+        let r2 = r.mod_mul(r, modulus);
+
+        Some(MontgomeryContext { modulus, n_prime, r, r2 })
+    }
+
+    /// Returns the modulus of this context.
+    pub fn modulus(&self) -> UInt256 {
+        self.modulus
+    }
+
+    /// Converts `a` into Montgomery form, `a * R mod n`. `a` does not need to be reduced.
+    pub fn to_montgomery(&self, a: UInt256) -> UInt256 {
+        // a * R^2 < R * n for any a, which is all `montgomery_mul` needs for a reduced result.
+        self.mul(a, self.r2)
+    }
+
+    /// Converts `a` out of Montgomery form, returning `a * R^-1 mod n`.
+    pub fn from_montgomery(&self, a: UInt256) -> UInt256 {
+        self.mul(a, UInt256::ONE)
+    }
+
+    /// Multiplies two values in Montgomery form, returning `a * b * R^-1 mod n`,
+    /// which is the Montgomery form of the product.
+    ///
+    /// Both operands must be reduced, i.e. less than the modulus.
+    pub fn mul(&self, a: UInt256, b: UInt256) -> UInt256 {
+        let limbs = montgomery_mul(&a.to_limbs(), &b.to_limbs(), &self.modulus.to_limbs(), self.n_prime);
+        UInt256::from_limbs_with(limbs, a.endian)
+    }
+
+    /// Raises a value in Montgomery form to the power `exponent`, returning the result
+    /// in Montgomery form.
+    ///
+    /// With the `ct` feature, every one of the 256 exponent bits takes a squaring and a
+    /// multiplication, so the running time depends on neither the exponent nor its length.
+    pub fn pow(&self, base: UInt256, exponent: UInt256) -> UInt256 {
+        let mut result = UInt256 { endian: base.endian, ..self.r };
+        let bits = if cfg!(feature = "ct") { 256 } else { 256 - exponent.leading_zeros() as usize };
+        for i in (0..bits).rev() {
+            result = self.mul(result, result);
+            if cfg!(feature = "ct") {
+                let product = self.mul(result, base);
+                let bit = Choice::from(exponent.bit_at(i) as u8);
+                result = UInt256::conditional_select(&result, &product, bit);
+            } else if exponent.bit_at(i) {
+                result = self.mul(result, base);
+            }
+        }
+        result
+    }
+}
+
+/// Returns the inverse of the odd value `n` modulo 2^64 by Newton iteration.
+pub(crate) fn inv_mod_2pow64(n: u64) -> u64 {
+    // `n` is its own inverse modulo 2^3; each step doubles the number of correct bits.
+    let mut inv = n;
+    for _ in 0..5 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(n.wrapping_mul(inv)));
+    }
+    inv
+}
+
+/// Montgomery multiplication with the coarsely integrated operand scanning (CIOS)
+/// method, interleaving one limb of multiplication with one limb of reduction.
+pub(crate) fn montgomery_mul(a: &[u64; 4], b: &[u64; 4], n: &[u64; 4], n_prime: u64) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    for &b_i in b {
+        // t += a * b_i
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let s = t[j] as u128 + a[j] as u128 * b_i as u128 + carry;
+            t[j] = s as u64;
+            carry = s >> 64;
+        }
+        let s = t[4] as u128 + carry;
+        t[4] = s as u64;
+        t[5] = (s >> 64) as u64;
+
+        // t = (t + m * n) / 2^64, where m makes the lowest limb vanish.
+        let m = t[0].wrapping_mul(n_prime);
+        let s = t[0] as u128 + m as u128 * n[0] as u128;
+        let mut carry = s >> 64;
+        for j in 1..4 {
+            let s = t[j] as u128 + m as u128 * n[j] as u128 + carry;
+            t[j - 1] = s as u64;
+            carry = s >> 64;
+        }
+        let s = t[4] as u128 + carry;
+        t[3] = s as u64;
+        t[4] = t[5] + (s >> 64) as u64;
+    }
+
+    // The result is less than 2n; subtract n once unless that borrows out of t[4].
+    let result = UInt256::from_limbs_with([t[0], t[1], t[2], t[3]], Endian::Big);
+    let modulus = UInt256::from_limbs_with(*n, Endian::Big);
+    let (difference, borrow) = result.overflowing_sub(modulus);
+    let keep = Choice::from((borrow as u64 & (t[4] ^ 1)) as u8);
+    UInt256::conditional_select(&difference, &result, keep).to_limbs()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_montgomery_rejects_even_modulus() {
+        assert!(MontgomeryContext::new(UInt256::ZERO).is_none());
+        assert!(MontgomeryContext::new(UInt256::from(10)).is_none());
+        assert!(MontgomeryContext::new(UInt256::from(11)).is_some());
+    }
+
+    #[test]
+    fn test_montgomery_round_trip() {
+        let ctx = MontgomeryContext::new(UInt256::MAX).unwrap();
+        for a in [UInt256::ZERO, UInt256::ONE, UInt256::from(12345), UInt256::MAX - UInt256::ONE] {
+            assert_eq!(ctx.from_montgomery(ctx.to_montgomery(a)), a);
+        }
+        assert_eq!(ctx.to_montgomery(UInt256::MAX), UInt256::ZERO);
+    }
+
+    #[test]
+    fn test_montgomery_mul_matches_mod_mul() {
+        // secp256k1 field prime, plus a small modulus and the largest odd one.
+        let moduli = [
+            UInt256::MAX - UInt256::from(0x1_0000_03d0),
+            UInt256::from(1_000_000_007),
+            UInt256::MAX,
+        ];
+        let values = [UInt256::from(3), UInt256::new(7, 11, Endian::Big), UInt256::MAX - UInt256::from(5)];
+        for m in moduli {
+            let ctx = MontgomeryContext::new(m).unwrap();
+            for a in values {
+                for b in values {
+                    let product = ctx.mul(ctx.to_montgomery(a), ctx.to_montgomery(b));
+                    assert_eq!(ctx.from_montgomery(product), a.mod_mul(b, m));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_montgomery_to_montgomery_unreduced() {
+        // Inputs above the modulus, up to 2^256 - 1, are reduced on the way in.
+        let m = UInt256::from(1_000_000_007);
+        let ctx = MontgomeryContext::new(m).unwrap();
+        for a in [m, m + UInt256::ONE, UInt256::MAX - UInt256::ONE, UInt256::MAX] {
+            assert_eq!(ctx.from_montgomery(ctx.to_montgomery(a)), a % m);
+        }
+    }
+
+    #[test]
+    fn test_montgomery_pow_matches_mod_pow() {
+        let p = UInt256::MAX - UInt256::from(0x1_0000_03d0);
+        let ctx = MontgomeryContext::new(p).unwrap();
+        let base = UInt256::new(0xdeadbeef, 0xcafebabe, Endian::Big);
+        for exponent in [UInt256::ZERO, UInt256::ONE, UInt256::from(65537), p - UInt256::from(2)] {
+            let result = ctx.from_montgomery(ctx.pow(ctx.to_montgomery(base), exponent));
+            assert_eq!(result, base.mod_pow(exponent, p));
+        }
+    }
+}
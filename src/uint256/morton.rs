@@ -0,0 +1,120 @@
+//! Morton (Z-order) encoding of two 128-bit coordinates into a [`UInt256`].
+
+use super::*;
+
+/// Masks for spreading the bits of a `u64` over the even bits of a `u128`, from the
+/// widest step to the narrowest, along with the shift of each step.
+const SPREAD_STEPS: [(u32, u128); 6] = [
+    (32, 0x0000_0000_ffff_ffff_0000_0000_ffff_ffff),
+    (16, 0x0000_ffff_0000_ffff_0000_ffff_0000_ffff),
+    (8, 0x00ff_00ff_00ff_00ff_00ff_00ff_00ff_00ff),
+    (4, 0x0f0f_0f0f_0f0f_0f0f_0f0f_0f0f_0f0f_0f0f),
+    (2, 0x3333_3333_3333_3333_3333_3333_3333_3333),
+    (1, 0x5555_5555_5555_5555_5555_5555_5555_5555),
+];
+
+impl UInt256 {
+    /// Interleaves the bits of `x` and `y` into a Morton code.
+    ///
+    /// Bit `i` of `x` becomes bit `2 * i` of the result and bit `i` of `y` becomes bit
+    /// `2 * i + 1`, so that sorting by the code keeps points that are close on both
+    /// axes close together.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::morton_encode(0b11, 0b00), UInt256::from(0b0101));
+    /// assert_eq!(UInt256::morton_encode(0b00, 0b11), UInt256::from(0b1010));
+    /// assert_eq!(UInt256::morton_encode(u128::MAX, u128::MAX), UInt256::MAX);
+    /// ```
+    pub fn morton_encode(x: u128, y: u128) -> Self {
+        let low = spread(x as u64) | spread(y as u64) << 1;
+        let high = spread((x >> 64) as u64) | spread((y >> 64) as u64) << 1;
+        UInt256 { high, low, endian: Endian::Big }
+    }
+
+    /// Splits a Morton code back into its `(x, y)` coordinates.
+    ///
+    /// This is the inverse of [`Self::morton_encode`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let code = UInt256::morton_encode(12345, 67890);
+    /// assert_eq!(code.morton_decode(), (12345, 67890));
+    /// ```
+    pub fn morton_decode(&self) -> (u128, u128) {
+        let x = compact(self.low) as u128 | (compact(self.high) as u128) << 64;
+        let y = compact(self.low >> 1) as u128 | (compact(self.high >> 1) as u128) << 64;
+        (x, y)
+    }
+}
+
+/// Moves bit `i` of `value` to bit `2 * i`.
+fn spread(value: u64) -> u128 {
+    SPREAD_STEPS
+        .iter()
+        .fold(value as u128, |v, &(shift, mask)| (v | v << shift) & mask)
+}
+
+/// Moves bit `2 * i` of `value` to bit `i`, dropping the odd bits.
+fn compact(value: u128) -> u64 {
+    // Undo the steps of `spread` in reverse, each one keeping the bits that the step
+    // before it had kept.
+    let mut v = value & SPREAD_STEPS[5].1;
+    for step in (0..SPREAD_STEPS.len()).rev() {
+        let mask = match step {
+            0 => u64::MAX as u128,
+            _ => SPREAD_STEPS[step - 1].1,
+        };
+        v = (v | v >> SPREAD_STEPS[step].0) & mask;
+    }
+    v as u64
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{xorshift, SEED};
+
+    #[test]
+    fn test_morton_bits() {
+        for i in 0..128 {
+            let x = UInt256::morton_encode(1 << i, 0);
+            let y = UInt256::morton_encode(0, 1 << i);
+            assert_eq!(x, UInt256::ZERO.with_bit(2 * i, true), "x bit {}", i);
+            assert_eq!(y, UInt256::ZERO.with_bit(2 * i + 1, true), "y bit {}", i);
+            assert_eq!(x.morton_decode(), (1 << i, 0));
+            assert_eq!(y.morton_decode(), (0, 1 << i));
+        }
+    }
+
+    #[test]
+    fn test_morton_round_trip() {
+        let mut next = xorshift(SEED);
+        for _ in 0..200 {
+            let x = (next() as u128) << 64 | next() as u128;
+            let y = (next() as u128) << 64 | next() as u128;
+            let code = UInt256::morton_encode(x, y);
+            assert_eq!(code.count_ones(), x.count_ones() + y.count_ones());
+            assert_eq!(code.morton_decode(), (x, y));
+        }
+        assert_eq!(UInt256::MAX.morton_decode(), (u128::MAX, u128::MAX));
+        assert_eq!(UInt256::ZERO.morton_decode(), (0, 0));
+    }
+
+    #[test]
+    fn test_morton_order() {
+        // The first points of the Z curve over a 2x2 grid, then the next quadrant.
+        let codes: Vec<UInt256> = [(0, 0), (1, 0), (0, 1), (1, 1), (2, 0)]
+            .iter()
+            .map(|&(x, y)| UInt256::morton_encode(x, y))
+            .collect();
+        assert!(codes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,610 @@
+//! Number-theoretic functions on [`UInt256`] values.
+
+use super::montgomery::inv_mod_2pow64;
+use super::*;
+
+impl UInt256 {
+    /// Returns the greatest common divisor of `self` and `other`.
+    ///
+    /// Uses the binary (Stein's) algorithm, which only needs shifts and subtractions
+    /// and never goes through the division routine. `gcd(0, 0)` is defined as 0.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let a = UInt256::from(462);
+    /// let b = UInt256::from(1071);
+    /// assert_eq!(a.gcd(b), UInt256::from(21));
+    /// ```
+    pub fn gcd(self, other: Self) -> Self {
+        let mut a = self;
+        let mut b = other;
+
+        if a.is_zero() {
+            return UInt256 { endian: self.endian, ..b };
+        }
+        if b.is_zero() {
+            return a;
+        }
+
+        // Factor out the common power of two.
+        let shift = (a | b).trailing_zeros();
+        a = strip_twos(a);
+
+        loop {
+            // `a` is always odd here.
+            b = strip_twos(b);
+            if a > b {
+                std::mem::swap(&mut a, &mut b);
+            }
+            b -= a;
+            if b.is_zero() {
+                break;
+            }
+        }
+
+        if shift > 0 {
+            a <<= shift;
+        }
+        UInt256 { endian: self.endian, ..a }
+    }
+
+    /// Returns the least common multiple of `self` and `other`.
+    ///
+    /// `lcm(0, n)` is defined as 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in 256 bits. Use [`Self::checked_lcm`] to handle that case.
+    pub fn lcm(self, other: Self) -> Self {
+        match self.checked_lcm(other) {
+            Some(lcm) => lcm,
+            None => panic!("lcm overflow"),
+        }
+    }
+
+    /// Returns the least common multiple of `self` and `other`, or `None` if it
+    /// does not fit in 256 bits.
+    pub fn checked_lcm(self, other: Self) -> Option<Self> {
+        if self.is_zero() || other.is_zero() {
+            return Some(UInt256 { endian: self.endian, ..UInt256::ZERO });
+        }
+        // Dividing first keeps the product as small as it can be.
+        (self / self.gcd(other)).checked_mul(other)
+    }
+
+    /// Returns the greatest common divisor `g` of `self` and `other` together with
+    /// Bézout coefficients `x` and `y` such that `self * x + other * y = g`.
+    ///
+    /// The coefficients are returned as sign and magnitude. They are the minimal ones
+    /// produced by the extended Euclidean algorithm, so `|x| <= other / g` and
+    /// `|y| <= self / g` and they always fit in 256 bits.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Sign, UInt256};
+    ///
+    /// // 240 * (-9) + 46 * 47 = 2
+    /// let (g, x, y) = UInt256::from(240).extended_gcd(UInt256::from(46));
+    /// assert_eq!(g, UInt256::from(2));
+    /// assert_eq!(x, (Sign::Negative, UInt256::from(9)));
+    /// assert_eq!(y, (Sign::Positive, UInt256::from(47)));
+    /// ```
+    pub fn extended_gcd(self, other: Self) -> (Self, (Sign, Self), (Sign, Self)) {
+        let (mut r0, mut r1) = (self, other);
+        let (mut s0, mut s1) = (UInt256::ONE, UInt256::ZERO);
+        let (mut t0, mut t1) = (UInt256::ZERO, UInt256::ONE);
+
+        // The coefficients alternate in sign at every step, so only their magnitudes
+        // are tracked: |s_{i+1}| = |s_{i-1}| + q * |s_i|, and likewise for t.
+        let mut odd_steps = false;
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(r1);
+            (r0, r1) = (r1, r);
+            (s0, s1) = (s1, s0 + q * s1);
+            (t0, t1) = (t1, t0 + q * t1);
+            odd_steps = !odd_steps;
+        }
+
+        let sign = |magnitude: UInt256, negative: bool| {
+            if negative && !magnitude.is_zero() {
+                (Sign::Negative, magnitude)
+            } else {
+                (Sign::Positive, magnitude)
+            }
+        };
+        let endian = self.endian;
+        (
+            UInt256 { endian, ..r0 },
+            sign(UInt256 { endian, ..s0 }, odd_steps),
+            sign(UInt256 { endian, ..t0 }, !odd_steps),
+        )
+    }
+
+    /// Returns the Jacobi symbol `(self / n)`: 0 if `self` and `n` share a factor, and
+    /// otherwise 1 or -1. For a prime `n` this is the Legendre symbol, which is 1
+    /// exactly when `self` is a non-zero quadratic residue modulo `n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is even, for which the symbol is not defined.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let p = UInt256::from(13);
+    /// assert_eq!(UInt256::from(10).jacobi(p), 1);
+    /// assert_eq!(UInt256::from(5).jacobi(p), -1);
+    /// assert_eq!(UInt256::from(26).jacobi(p), 0);
+    /// ```
+    pub fn jacobi(self, n: Self) -> i8 {
+        assert!(n.is_odd(), "Jacobi symbol requires an odd modulus");
+
+        let mut a = self % n;
+        let mut n = n;
+        let mut result = 1;
+        while !a.is_zero() {
+            // (2 / n) = -1 exactly when n = 3 or 5 (mod 8).
+            let twos = a.trailing_zeros();
+            a = strip_twos(a);
+            if twos & 1 == 1 && matches!(n.low & 7, 3 | 5) {
+                result = -result;
+            }
+            // Quadratic reciprocity flips the sign when both are 3 (mod 4).
+            if a.low & 3 == 3 && n.low & 3 == 3 {
+                result = -result;
+            }
+            std::mem::swap(&mut a, &mut n);
+            a %= n;
+        }
+
+        if n == UInt256::ONE {
+            result
+        } else {
+            0
+        }
+    }
+
+    /// Returns the multiplicative inverse of `self` modulo `modulus`, i.e. the value `x`
+    /// in `[0, modulus)` such that `self * x ≡ 1 (mod modulus)`.
+    ///
+    /// Returns `None` if `modulus` is zero or if `self` and `modulus` are not coprime,
+    /// in which case no inverse exists.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let m = UInt256::from(11);
+    /// assert_eq!(UInt256::from(3).mod_inv(m), Some(UInt256::from(4)));
+    /// assert_eq!(UInt256::from(4).mod_inv(UInt256::from(8)), None);
+    /// ```
+    pub fn mod_inv(self, modulus: Self) -> Option<Self> {
+        if modulus.is_zero() {
+            return None;
+        }
+
+        // Extended Euclidean algorithm, tracking only the coefficient of `self`,
+        // kept reduced modulo `modulus` so it never goes negative.
+        let (mut r0, mut r1) = (modulus, self % modulus);
+        let (mut t0, mut t1) = (UInt256::ZERO, UInt256::ONE);
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(r1);
+            (r0, r1) = (r1, r);
+            (t0, t1) = (t1, t0.mod_sub(q.mod_mul(t1, modulus), modulus));
+        }
+
+        if r0 == UInt256::ONE {
+            Some(UInt256 { endian: self.endian, ..t0 % modulus })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the multiplicative inverse of `self` modulo 2^k, i.e. the value `x` below
+    /// 2^k such that `self * x ≡ 1 (mod 2^k)`.
+    ///
+    /// Computed by Newton (Hensel) iteration, which doubles the number of correct low
+    /// bits each step, so no division is needed. Returns `None` if `self` is even and
+    /// `k` is non-zero, since only odd values are invertible modulo a power of two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is greater than 256.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let inv = UInt256::from(3).inv_mod_2pow(256).unwrap();
+    /// assert_eq!(inv.wrapping_mul(UInt256::from(3)), UInt256::ONE);
+    /// assert_eq!(UInt256::from(3).inv_mod_2pow(4), Some(UInt256::from(11)));
+    /// assert_eq!(UInt256::from(4).inv_mod_2pow(8), None);
+    /// ```
+    pub fn inv_mod_2pow(self, k: u32) -> Option<Self> {
+        assert!(k <= 256, "k must be at most 256");
+        if k == 0 {
+            return Some(UInt256 { endian: self.endian, ..UInt256::ZERO });
+        }
+        if self.is_even() {
+            return None;
+        }
+
+        // Correct modulo 2^64 to start with; each step x = x * (2 - self * x) doubles that.
+        let two = UInt256::from(2);
+        let mut inverse = UInt256::from_limbs_with([inv_mod_2pow64(self.low as u64), 0, 0, 0], self.endian);
+        for _ in 0..2 {
+            inverse = inverse.wrapping_mul(two.wrapping_sub(self.wrapping_mul(inverse)));
+        }
+
+        // Keep the low k bits.
+        let mut limbs = inverse.to_limbs();
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let kept = (k as usize).saturating_sub(64 * i).min(64);
+            *limb &= u64::MAX.checked_shr(64 - kept as u32).unwrap_or(0);
+        }
+        Some(UInt256::from_limbs_with(limbs, self.endian))
+    }
+
+    /// Replaces every value with its multiplicative inverse modulo `modulus`.
+    ///
+    /// Uses Montgomery's trick: a single [`Self::mod_inv`] of the product of all values,
+    /// plus three modular multiplications per value, instead of one inversion each.
+    ///
+    /// Returns `false`, leaving `values` unchanged, if `modulus` is zero or if any value
+    /// has no inverse (for example zero).
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let m = UInt256::from(11);
+    /// let mut values = [UInt256::from(2), UInt256::from(3), UInt256::from(10)];
+    /// assert!(UInt256::batch_mod_inv(&mut values, m));
+    /// assert_eq!(values, [UInt256::from(6), UInt256::from(4), UInt256::from(10)]);
+    ///
+    /// let mut with_zero = [UInt256::from(2), UInt256::ZERO];
+    /// assert!(!UInt256::batch_mod_inv(&mut with_zero, m));
+    /// assert_eq!(with_zero, [UInt256::from(2), UInt256::ZERO]);
+    /// ```
+    pub fn batch_mod_inv(values: &mut [Self], modulus: Self) -> bool {
+        if modulus.is_zero() {
+            return false;
+        }
+        if values.is_empty() {
+            return true;
+        }
+
+        let reducer = BarrettReducer::new(modulus);
+        // prefix[i] is the product of values[..=i].
+        let mut prefix = Vec::with_capacity(values.len());
+        let mut product = UInt256::ONE % modulus;
+        for value in values.iter() {
+            product = reducer.mul_mod(product, *value);
+            prefix.push(product);
+        }
+
+        let mut inverse = match product.mod_inv(modulus) {
+            Some(inverse) => inverse,
+            None => return false,
+        };
+        // Walk backwards: inverse holds the inverse of values[..=i], so multiplying by
+        // the product of values[..i] isolates the inverse of values[i].
+        for i in (1..values.len()).rev() {
+            let value = values[i];
+            values[i] = UInt256 { endian: value.endian, ..reducer.mul_mod(inverse, prefix[i - 1]) };
+            inverse = reducer.mul_mod(inverse, value);
+        }
+        values[0] = UInt256 { endian: values[0].endian, ..inverse };
+        true
+    }
+
+    /// Combines the congruences `x ≡ residue (mod modulus)` with the Chinese Remainder
+    /// Theorem, returning the unique solution `x` below the product of the moduli.
+    ///
+    /// Residues do not need to be reduced. An empty slice gives 0. Returns `None` if a
+    /// modulus is zero, if the moduli are not pairwise coprime, or if their product
+    /// does not fit in 256 bits.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// // x ≡ 2 (mod 3), x ≡ 3 (mod 5), x ≡ 2 (mod 7)
+    /// let congruences = [
+    ///     (UInt256::from(2), UInt256::from(3)),
+    ///     (UInt256::from(3), UInt256::from(5)),
+    ///     (UInt256::from(2), UInt256::from(7)),
+    /// ];
+    /// assert_eq!(UInt256::crt(&congruences), Some(UInt256::from(23)));
+    ///
+    /// let not_coprime = [(UInt256::from(1), UInt256::from(4)), (UInt256::from(3), UInt256::from(6))];
+    /// assert_eq!(UInt256::crt(&not_coprime), None);
+    /// ```
+    pub fn crt(congruences: &[(Self, Self)]) -> Option<Self> {
+        // Invariant: x is the solution of the congruences so far, and x < product.
+        let mut x = UInt256::ZERO;
+        let mut product = UInt256::ONE;
+        for &(residue, modulus) in congruences {
+            if modulus.is_zero() {
+                return None;
+            }
+            // Lift x to x + product * t, where t solves product * t ≡ residue - x.
+            let inv = product.mod_inv(modulus)?;
+            let t = residue.mod_sub(x, modulus).mod_mul(inv, modulus);
+            let next = product.checked_mul(modulus)?;
+            // product * t < product * modulus and x < product, so neither overflows.
+            x += product * t;
+            product = next;
+        }
+        Some(x)
+    }
+}
+
+/// Shift out all trailing zero bits of a non-zero value.
+fn strip_twos(n: UInt256) -> UInt256 {
+    n >> n.trailing_zeros()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_gcd_basic() {
+        assert_eq!(UInt256::from(48).gcd(UInt256::from(18)), UInt256::from(6));
+        assert_eq!(UInt256::from(17).gcd(UInt256::from(5)), UInt256::ONE);
+        assert_eq!(UInt256::from(1024).gcd(UInt256::from(96)), UInt256::from(32));
+    }
+
+    #[test]
+    fn test_gcd_zero() {
+        assert_eq!(UInt256::ZERO.gcd(UInt256::ZERO), UInt256::ZERO);
+        assert_eq!(UInt256::ZERO.gcd(UInt256::from(7)), UInt256::from(7));
+        assert_eq!(UInt256::MAX.gcd(UInt256::ZERO), UInt256::MAX);
+    }
+
+    #[test]
+    fn test_gcd_large() {
+        // 2^255 and 2^200 * 3 share a factor of 2^200.
+        let a = UInt256::new(1 << 127, 0, Endian::Big);
+        let b = UInt256::new(3 << 72, 0, Endian::Big);
+        assert_eq!(a.gcd(b), UInt256::new(1 << 72, 0, Endian::Big));
+
+        let c = UInt256::MAX;
+        assert_eq!(c.gcd(c), UInt256::MAX);
+        assert_eq!(c.gcd(UInt256::from(3)), UInt256::from(3));
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(UInt256::from(4).lcm(UInt256::from(6)), UInt256::from(12));
+        assert_eq!(UInt256::from(21).lcm(UInt256::from(6)), UInt256::from(42));
+        assert_eq!(UInt256::ZERO.lcm(UInt256::from(6)), UInt256::ZERO);
+        assert_eq!(UInt256::MAX.lcm(UInt256::from(3)), UInt256::MAX);
+    }
+
+    #[test]
+    fn test_checked_lcm_overflow() {
+        // Two large coprime values whose product exceeds 256 bits.
+        let a = UInt256::new(1 << 127, 0, Endian::Big);
+        let b = UInt256::from(3);
+        assert_eq!(a.checked_lcm(b), None);
+        assert_eq!(UInt256::MAX.checked_lcm(UInt256::MAX - UInt256::ONE), None);
+        assert_eq!(UInt256::from(8).checked_lcm(UInt256::from(12)), Some(UInt256::from(24)));
+    }
+
+    /// Checks `a * x + b * y == g` for small enough values.
+    fn check_bezout(a: UInt256, b: UInt256) {
+        let (g, (x_sign, x), (y_sign, y)) = a.extended_gcd(b);
+        assert_eq!(g, a.gcd(b));
+        let ax = a * x;
+        let by = b * y;
+        let combined = match (x_sign, y_sign) {
+            (Sign::Positive, Sign::Negative) => ax - by,
+            (Sign::Negative, Sign::Positive) => by - ax,
+            _ => ax + by,
+        };
+        assert_eq!(combined, g, "bezout identity failed for {} and {}", a, b);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        check_bezout(UInt256::from(240), UInt256::from(46));
+        check_bezout(UInt256::from(46), UInt256::from(240));
+        check_bezout(UInt256::from(17), UInt256::from(5));
+        check_bezout(UInt256::from(7), UInt256::from(7));
+        check_bezout(UInt256::from(12), UInt256::ZERO);
+        check_bezout(UInt256::ZERO, UInt256::from(12));
+        check_bezout(UInt256::new(0, u128::MAX, Endian::Big), UInt256::new(0, 1 << 100, Endian::Big));
+
+        assert_eq!(
+            UInt256::ZERO.extended_gcd(UInt256::ZERO),
+            (UInt256::ZERO, (Sign::Positive, UInt256::ONE), (Sign::Positive, UInt256::ZERO)),
+        );
+    }
+
+    #[test]
+    fn test_extended_gcd_large() {
+        // Coefficients of full-width inputs still fit; check the identity modulo `b`.
+        let a = UInt256::MAX;
+        let b = UInt256::MAX - UInt256::from(58);
+        let (g, (x_sign, x), _) = a.extended_gcd(b);
+        assert_eq!(g, a.gcd(b));
+        assert!(x <= b / g);
+        let ax = a.mod_mul(x, b);
+        let residue = match x_sign {
+            Sign::Positive => ax,
+            Sign::Negative => UInt256::ZERO.mod_sub(ax, b),
+        };
+        assert_eq!(residue, g % b);
+    }
+
+    #[test]
+    fn test_jacobi() {
+        // Legendre symbols modulo 7: the residues are 1, 2 and 4.
+        let p = UInt256::from(7);
+        let expected = [0, 1, 1, -1, 1, -1, -1];
+        for (a, &symbol) in expected.iter().enumerate() {
+            assert_eq!(UInt256::from(a).jacobi(p), symbol, "({} / 7)", a);
+        }
+        // Composite moduli: (2 / 15) = 1 even though 2 is not a square mod 15.
+        assert_eq!(UInt256::from(2).jacobi(UInt256::from(15)), 1);
+        assert_eq!(UInt256::from(7).jacobi(UInt256::from(15)), -1);
+        assert_eq!(UInt256::from(6).jacobi(UInt256::from(15)), 0);
+        assert_eq!(UInt256::from(5).jacobi(UInt256::ONE), 1);
+    }
+
+    #[test]
+    fn test_jacobi_large() {
+        // -1 is a non-residue modulo the secp256k1 field prime, which is 3 mod 4.
+        let p = UInt256::MAX - UInt256::from(0x1_0000_03d0);
+        assert_eq!((p - UInt256::ONE).jacobi(p), -1);
+        let x = UInt256::new(0xdeadbeef, 0xcafebabe, Endian::Big);
+        assert_eq!(x.mod_mul(x, p).jacobi(p), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Jacobi symbol requires an odd modulus")]
+    fn test_jacobi_even_modulus() {
+        let _ = UInt256::from(3).jacobi(UInt256::from(8));
+    }
+
+    #[test]
+    fn test_mod_inv() {
+        let m = UInt256::from(1_000_000_007);
+        let a = UInt256::from(123_456_789);
+        let inv = a.mod_inv(m).unwrap();
+        assert_eq!(a.mod_mul(inv, m), UInt256::ONE);
+
+        assert_eq!(UInt256::from(6).mod_inv(UInt256::from(9)), None);
+        assert_eq!(UInt256::ZERO.mod_inv(m), None);
+        assert_eq!(UInt256::from(5).mod_inv(UInt256::ZERO), None);
+        assert_eq!(UInt256::from(5).mod_inv(UInt256::ONE), Some(UInt256::ZERO));
+    }
+
+    #[test]
+    fn test_mod_inv_large() {
+        // secp256k1 group order n.
+        let n = UInt256::new(
+            0xfffffffffffffffffffffffffffffffe,
+            0xbaaedce6af48a03bbfd25e8cd0364141,
+            Endian::Big,
+        );
+        for a in [UInt256::from(2), UInt256::MAX % n, n - UInt256::ONE] {
+            let inv = a.mod_inv(n).unwrap();
+            assert!(inv < n);
+            assert_eq!(a.mod_mul(inv, n), UInt256::ONE);
+        }
+        // An even modulus has no inverse for even values, but does for odd ones.
+        assert_eq!(UInt256::from(2).mod_inv(UInt256::MAX - UInt256::ONE), None);
+        let inv = UInt256::from(3).mod_inv(UInt256::MAX - UInt256::ONE).unwrap();
+        assert_eq!(UInt256::from(3).mod_mul(inv, UInt256::MAX - UInt256::ONE), UInt256::ONE);
+    }
+
+    #[test]
+    fn test_inv_mod_2pow() {
+        let values = [
+            UInt256::ONE,
+            UInt256::from(3),
+            UInt256::MAX,
+            UInt256::new(0xdeadbeef, 0xcafebabe_00000001, Endian::Big),
+            UInt256::MAX - UInt256::from(0x1_0000_03d0),
+        ];
+        for a in values {
+            let inverse = a.inv_mod_2pow(256).unwrap();
+            assert_eq!(a.wrapping_mul(inverse), UInt256::ONE);
+            for k in [1, 8, 63, 64, 65, 128, 200, 255] {
+                let modulus = UInt256::ONE << k;
+                let inverse_k = a.inv_mod_2pow(k).unwrap();
+                assert!(inverse_k < modulus);
+                assert_eq!(inverse_k, inverse % modulus, "k = {}", k);
+                assert_eq!(a.mod_mul(inverse_k, modulus), UInt256::ONE % modulus);
+            }
+        }
+        assert_eq!(UInt256::from(6).inv_mod_2pow(0), Some(UInt256::ZERO));
+        assert_eq!(UInt256::from(6).inv_mod_2pow(1), None);
+        assert_eq!(UInt256::ZERO.inv_mod_2pow(256), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "k must be at most 256")]
+    fn test_inv_mod_2pow_out_of_range() {
+        let _ = UInt256::ONE.inv_mod_2pow(257);
+    }
+
+    #[test]
+    fn test_batch_mod_inv() {
+        let m = UInt256::new(
+            0xfffffffffffffffffffffffffffffffe,
+            0xbaaedce6af48a03bbfd25e8cd0364141,
+            Endian::Big,
+        );
+        let original: Vec<UInt256> = (1..50usize)
+            .map(|i| UInt256::MAX - UInt256::from(i * 7919))
+            .collect();
+        let mut values = original.clone();
+        assert!(UInt256::batch_mod_inv(&mut values, m));
+        for (value, inverse) in original.iter().zip(&values) {
+            assert_eq!(Some(*inverse), value.mod_inv(m));
+        }
+
+        let mut empty: [UInt256; 0] = [];
+        assert!(UInt256::batch_mod_inv(&mut empty, m));
+        let mut single = [UInt256::from(5)];
+        assert!(!UInt256::batch_mod_inv(&mut single, UInt256::ZERO));
+        assert!(UInt256::batch_mod_inv(&mut single, UInt256::ONE));
+        assert_eq!(single, [UInt256::ZERO]);
+
+        // 4 shares a factor with 10, so nothing is inverted.
+        let mut values = [UInt256::from(3), UInt256::from(4), UInt256::from(7)];
+        assert!(!UInt256::batch_mod_inv(&mut values, UInt256::from(10)));
+        assert_eq!(values, [UInt256::from(3), UInt256::from(4), UInt256::from(7)]);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(UInt256::crt(&[]), Some(UInt256::ZERO));
+        assert_eq!(UInt256::crt(&[(UInt256::from(10), UInt256::from(7))]), Some(UInt256::from(3)));
+        assert_eq!(UInt256::crt(&[(UInt256::from(5), UInt256::ONE)]), Some(UInt256::ZERO));
+        assert_eq!(UInt256::crt(&[(UInt256::ONE, UInt256::ZERO)]), None);
+
+        // Reassemble a value from its residues modulo several primes.
+        let primes = [1_000_000_007usize, 998_244_353, 1_000_000_009, 754_974_721, 167_772_161];
+        let value = UInt256::new(0x1234, 0x5678_9abc_def0_1234_5678_9abc_def0, Endian::Big);
+        let congruences: Vec<_> = primes
+            .iter()
+            .map(|&p| (value % UInt256::from(p), UInt256::from(p)))
+            .collect();
+        assert_eq!(UInt256::crt(&congruences), Some(value));
+    }
+
+    #[test]
+    fn test_crt_large_moduli() {
+        // Two coprime moduli whose product is just below 2^256.
+        let m1 = UInt256::new(0, u128::MAX, Endian::Big);
+        let m2 = UInt256::new(0, u128::MAX - 1, Endian::Big);
+        let value = UInt256::MAX - UInt256::new(0, u128::MAX, Endian::Big) * UInt256::from(3);
+        let x = UInt256::crt(&[(value % m1, m1), (value % m2, m2)]);
+        assert_eq!(x, Some(value % (m1 * m2)));
+
+        // The product of the moduli overflows.
+        assert_eq!(UInt256::crt(&[(UInt256::ONE, m1), (UInt256::ONE, m2), (UInt256::ONE, UInt256::from(5))]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm overflow")]
+    fn test_lcm_overflow() {
+        let _ = UInt256::MAX.lcm(UInt256::from(2));
+    }
+}
@@ -0,0 +1,515 @@
+//! Compound assignment and other operator trait implementations for [`UInt256`].
+
+use std::borrow::Borrow;
+use std::iter::{Product, Sum};
+use std::ops::{
+    AddAssign, BitAndAssign, BitOrAssign, BitXorAssign, DivAssign, MulAssign, RemAssign, ShlAssign, ShrAssign,
+    SubAssign,
+};
+
+use super::*;
+
+/// Implements a compound assignment operator in terms of its binary operator,
+/// for both owned and borrowed right-hand sides.
+macro_rules! impl_assign_op {
+    ($assign_trait:ident, $assign_method:ident, $method:ident) => {
+        impl $assign_trait for UInt256 {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = (*self).$method(rhs);
+            }
+        }
+
+        impl $assign_trait<&UInt256> for UInt256 {
+            fn $assign_method(&mut self, rhs: &Self) {
+                *self = (*self).$method(*rhs);
+            }
+        }
+    };
+}
+
+impl_assign_op!(AddAssign, add_assign, add);
+impl_assign_op!(SubAssign, sub_assign, sub);
+impl_assign_op!(MulAssign, mul_assign, mul);
+impl_assign_op!(DivAssign, div_assign, div);
+impl_assign_op!(RemAssign, rem_assign, rem);
+impl_assign_op!(BitOrAssign, bitor_assign, bitor);
+impl_assign_op!(BitAndAssign, bitand_assign, bitand);
+impl_assign_op!(BitXorAssign, bitxor_assign, bitxor);
+
+impl<T: ShiftAmount> ShlAssign<T> for UInt256 {
+    fn shl_assign(&mut self, rhs: T) {
+        *self = (*self).shl(rhs);
+    }
+}
+
+impl<T: ShiftAmount> ShrAssign<T> for UInt256 {
+    fn shr_assign(&mut self, rhs: T) {
+        *self = (*self).shr(rhs);
+    }
+}
+
+/// Implements a binary operator for the borrowed combinations of operands
+/// by copying them into the owned implementation.
+macro_rules! forward_ref_binop {
+    ($trait:ident, $method:ident, $rhs:ty) => {
+        impl $trait<$rhs> for &UInt256 {
+            type Output = UInt256;
+
+            fn $method(self, rhs: $rhs) -> UInt256 {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl $trait<&$rhs> for UInt256 {
+            type Output = UInt256;
+
+            fn $method(self, rhs: &$rhs) -> UInt256 {
+                self.$method(*rhs)
+            }
+        }
+
+        impl $trait<&$rhs> for &UInt256 {
+            type Output = UInt256;
+
+            fn $method(self, rhs: &$rhs) -> UInt256 {
+                (*self).$method(*rhs)
+            }
+        }
+    };
+}
+
+forward_ref_binop!(Add, add, UInt256);
+forward_ref_binop!(Sub, sub, UInt256);
+forward_ref_binop!(Mul, mul, UInt256);
+forward_ref_binop!(Div, div, UInt256);
+forward_ref_binop!(Rem, rem, UInt256);
+forward_ref_binop!(BitOr, bitor, UInt256);
+forward_ref_binop!(BitAnd, bitand, UInt256);
+forward_ref_binop!(BitXor, bitxor, UInt256);
+
+impl Not for &UInt256 {
+    type Output = UInt256;
+
+    fn not(self) -> UInt256 {
+        !*self
+    }
+}
+
+impl<T: ShiftAmount> Shl<T> for &UInt256 {
+    type Output = UInt256;
+
+    fn shl(self, rhs: T) -> UInt256 {
+        (*self).shl(rhs)
+    }
+}
+
+impl<T: ShiftAmount> Shr<T> for &UInt256 {
+    type Output = UInt256;
+
+    fn shr(self, rhs: T) -> UInt256 {
+        (*self).shr(rhs)
+    }
+}
+
+/// Widens a primitive right-hand side to a [`UInt256`] with the endianness of the left-hand side.
+fn widen(value: u128, endian: Endian) -> UInt256 {
+    UInt256::new(0, value, endian)
+}
+
+/// Implements an arithmetic or bitwise operator and its compound assignment form
+/// with unsigned primitive right-hand sides.
+macro_rules! impl_primitive_binop {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident; $($t:ty),*) => {
+        $(
+            impl $trait<$t> for UInt256 {
+                type Output = UInt256;
+
+                fn $method(self, rhs: $t) -> UInt256 {
+                    self.$method(widen(rhs as u128, self.endian))
+                }
+            }
+
+            impl $assign_trait<$t> for UInt256 {
+                fn $assign_method(&mut self, rhs: $t) {
+                    *self = (*self).$method(rhs);
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_binop!(Add, add, AddAssign, add_assign; u8, u16, u32, u64, u128, usize);
+impl_primitive_binop!(Sub, sub, SubAssign, sub_assign; u8, u16, u32, u64, u128, usize);
+impl_primitive_binop!(Mul, mul, MulAssign, mul_assign; u8, u16, u32, u64, u128, usize);
+impl_primitive_binop!(Div, div, DivAssign, div_assign; u8, u16, u32, u64, u128, usize);
+impl_primitive_binop!(Rem, rem, RemAssign, rem_assign; u8, u16, u32, u64, u128, usize);
+
+impl_primitive_binop!(BitOr, bitor, BitOrAssign, bitor_assign; u8, u16, u32, u64, u128, usize);
+impl_primitive_binop!(BitAnd, bitand, BitAndAssign, bitand_assign; u8, u16, u32, u64, u128, usize);
+impl_primitive_binop!(BitXor, bitxor, BitXorAssign, bitxor_assign; u8, u16, u32, u64, u128, usize);
+
+/// Implements comparisons between [`UInt256`] and unsigned primitives, in both directions.
+macro_rules! impl_primitive_cmp {
+    ($($t:ty),*) => {
+        $(
+            impl PartialEq<$t> for UInt256 {
+                fn eq(&self, other: &$t) -> bool {
+                    self.high == 0 && self.low == *other as u128
+                }
+            }
+
+            impl PartialEq<UInt256> for $t {
+                fn eq(&self, other: &UInt256) -> bool {
+                    other == self
+                }
+            }
+
+            impl PartialOrd<$t> for UInt256 {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    Some(self.cmp(&widen(*other as u128, self.endian)))
+                }
+            }
+
+            impl PartialOrd<UInt256> for $t {
+                fn partial_cmp(&self, other: &UInt256) -> Option<Ordering> {
+                    Some(widen(*self as u128, other.endian).cmp(other))
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_cmp!(u8, u16, u32, u64, u128, usize);
+
+/// Sums the values with [`Add`], so it panics on overflow just like `+`.
+/// The sum of an empty iterator is [`UInt256::ZERO`].
+impl Sum for UInt256 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(UInt256::ZERO, |acc, v| acc + v)
+    }
+}
+
+/// Sums the values with [`Add`], so it panics on overflow just like `+`.
+/// The sum of an empty iterator is [`UInt256::ZERO`].
+impl<'a> Sum<&'a UInt256> for UInt256 {
+    fn sum<I: Iterator<Item = &'a UInt256>>(iter: I) -> Self {
+        iter.fold(UInt256::ZERO, |acc, v| acc + v)
+    }
+}
+
+/// Multiplies the values with [`Mul`], so it panics on overflow just like `*`.
+/// The product of an empty iterator is [`UInt256::ONE`].
+impl Product for UInt256 {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(UInt256::ONE, |acc, v| acc * v)
+    }
+}
+
+/// Multiplies the values with [`Mul`], so it panics on overflow just like `*`.
+/// The product of an empty iterator is [`UInt256::ONE`].
+impl<'a> Product<&'a UInt256> for UInt256 {
+    fn product<I: Iterator<Item = &'a UInt256>>(iter: I) -> Self {
+        iter.fold(UInt256::ONE, |acc, v| acc * v)
+    }
+}
+
+impl UInt256 {
+    /// Sums the values of an iterator, returning `None` instead of panicking if the
+    /// sum overflows. The sum of an empty iterator is [`UInt256::ZERO`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let balances = [UInt256::from(5), UInt256::from(7)];
+    /// assert_eq!(UInt256::try_sum(&balances), Some(UInt256::from(12)));
+    /// assert_eq!(UInt256::try_sum([UInt256::MAX, UInt256::ONE]), None);
+    /// ```
+    pub fn try_sum<I>(iter: I) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<UInt256>,
+    {
+        iter.into_iter()
+            .try_fold(UInt256::ZERO, |acc, v| acc.checked_add(*v.borrow()))
+    }
+
+    /// Multiplies the values of an iterator, returning `None` instead of panicking if
+    /// the product overflows. The product of an empty iterator is [`UInt256::ONE`].
+    pub fn try_product<I>(iter: I) -> Option<Self>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<UInt256>,
+    {
+        iter.into_iter()
+            .try_fold(UInt256::ONE, |acc, v| acc.checked_mul(*v.borrow()))
+    }
+}
+
+pub(crate) use sealed::ShiftAmount;
+
+mod sealed {
+    /// A type that can be used as the right-hand side of a shift of a [`UInt256`](super::UInt256).
+    ///
+    /// The shift operators are implemented generically over this trait, rather than once
+    /// per primitive type, so that the result type is known even for an unsuffixed
+    /// literal like `x << 1`. As with the primitive integers, signed amounts are accepted
+    /// and negative amounts are out of range. A [`UInt256`](super::UInt256) amount is
+    /// accepted too, as used by the EVM `SHL` and `SHR` opcodes.
+    pub trait ShiftAmount: Copy {
+        /// Returns the shift amount, saturated to `u32::MAX` when it is out of range.
+        fn shift_amount(self) -> u32;
+    }
+
+    impl<T: ShiftAmount> ShiftAmount for &T {
+        fn shift_amount(self) -> u32 {
+            (*self).shift_amount()
+        }
+    }
+
+    macro_rules! impl_shift_amount {
+        ($($t:ty),*) => {
+            $(
+                impl ShiftAmount for $t {
+                    fn shift_amount(self) -> u32 {
+                        u32::try_from(self).unwrap_or(u32::MAX)
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_shift_amount!(u8, u16, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+    impl ShiftAmount for u32 {
+        fn shift_amount(self) -> u32 {
+            self
+        }
+    }
+
+    impl ShiftAmount for super::UInt256 {
+        fn shift_amount(self) -> u32 {
+            if self.high == 0 {
+                u32::try_from(self.low).unwrap_or(u32::MAX)
+            } else {
+                u32::MAX
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_assign_ops() {
+        let mut total = UInt256::ZERO;
+        for amount in [UInt256::from(100), UInt256::from(250), UInt256::from(650)] {
+            total += amount;
+        }
+        assert_eq!(total, UInt256::from(1_000));
+
+        total -= UInt256::from(1);
+        assert_eq!(total, UInt256::from(999));
+        total *= UInt256::from(3);
+        assert_eq!(total, UInt256::from(2_997));
+        total /= UInt256::from(10);
+        assert_eq!(total, UInt256::from(299));
+        total %= UInt256::from(7);
+        assert_eq!(total, UInt256::from(5));
+    }
+
+    #[test]
+    fn test_assign_ops_by_reference() {
+        let step = UInt256::from(4);
+        let mut a = UInt256::from(10);
+        a += &step;
+        a -= &step;
+        a *= &step;
+        assert_eq!(a, UInt256::from(40));
+        a /= &step;
+        a %= &step;
+        assert_eq!(a, UInt256::from(2));
+    }
+
+    /// Generic numeric code that only borrows its operands.
+    fn combine<T>(a: &T, b: &T) -> [T; 8]
+    where
+        for<'a> &'a T: Add<&'a T, Output = T>
+            + Sub<&'a T, Output = T>
+            + Mul<&'a T, Output = T>
+            + Div<&'a T, Output = T>
+            + Rem<&'a T, Output = T>
+            + BitOr<&'a T, Output = T>
+            + BitAnd<&'a T, Output = T>
+            + BitXor<&'a T, Output = T>,
+    {
+        [a + b, a - b, a * b, a / b, a % b, a | b, a & b, a ^ b]
+    }
+
+    #[test]
+    fn test_ref_ops() {
+        let a = UInt256::from(12);
+        let b = UInt256::from(5);
+        let expected = [17, 7, 60, 2, 2, 13, 4, 9].map(UInt256::from);
+        assert_eq!(combine(&a, &b), expected);
+        assert_eq!(!&a, UInt256::MAX - a);
+
+        let shifted: Vec<UInt256> = [a, b].iter().map(|v| v << 2).collect();
+        assert_eq!(shifted, [UInt256::from(48), UInt256::from(20)]);
+    }
+
+    #[test]
+    fn test_ref_ops_in_iterators() {
+        let values = [UInt256::from(1), UInt256::from(2), UInt256::from(3)];
+        let total = values.iter().fold(UInt256::ZERO, |acc, v| acc + v);
+        assert_eq!(total, UInt256::from(6));
+        let product = values.iter().fold(UInt256::ONE, |acc, v| acc * v);
+        assert_eq!(product, UInt256::from(6));
+    }
+
+    #[test]
+    fn test_primitive_rhs_ops() {
+        let a = UInt256::from(1_000);
+        assert_eq!(a + 5u64, UInt256::from(1_005));
+        assert_eq!(a - 1u8, UInt256::from(999));
+        assert_eq!(a * 10u128, UInt256::from(10_000));
+        assert_eq!(a / 3u16, UInt256::from(333));
+        assert_eq!(a % 3usize, UInt256::from(1));
+        assert_eq!(a | 7u32, UInt256::from(1_007));
+        assert_eq!(a & 0xffu64, UInt256::from(0xe8));
+        assert_eq!(a ^ 1_000u16, UInt256::ZERO);
+        assert_eq!(a >> 3usize, UInt256::from(125));
+        assert_eq!(a << 2u8, UInt256::from(4_000));
+
+        let big = UInt256::new(1, 0, Endian::Big);
+        assert_eq!(big - 1u64, UInt256::new(0, u128::MAX, Endian::Big));
+        assert_eq!(big * u128::MAX, UInt256::new(u128::MAX, 0, Endian::Big));
+    }
+
+    #[test]
+    fn test_primitive_rhs_assign_ops() {
+        let mut total = UInt256::ZERO;
+        for amount in [10u64, 20, 30] {
+            total += amount;
+        }
+        assert_eq!(total, UInt256::from(60));
+        total *= 2u8;
+        total -= 20u32;
+        total /= 4usize;
+        total %= 7u128;
+        assert_eq!(total, UInt256::from(4));
+    }
+
+    #[test]
+    fn test_bit_assign_ops() {
+        let mut flags = UInt256::ZERO;
+        flags |= UInt256::ONE;
+        flags |= 0b1000u8;
+        flags <<= 4u32;
+        assert_eq!(flags, UInt256::from(0b1001_0000));
+        flags ^= &UInt256::from(0b1000_0000);
+        flags &= 0xf0u64;
+        assert_eq!(flags, UInt256::from(0b1_0000));
+        flags >>= 4;
+        assert_eq!(flags, UInt256::ONE);
+
+        let mut high = UInt256::MAX;
+        high &= !UInt256::new(0, u128::MAX, Endian::Big);
+        high >>= 128usize;
+        assert_eq!(high, UInt256::new(0, u128::MAX, Endian::Big));
+    }
+
+    #[test]
+    fn test_uint256_shift_amount() {
+        let a = UInt256::from(0b1010);
+        assert_eq!(a << UInt256::from(3), UInt256::from(0b101_0000));
+        assert_eq!(a >> UInt256::ONE, UInt256::from(0b101));
+        assert_eq!(a << UInt256::from(255), UInt256::ZERO);
+
+        let mut b = a;
+        b <<= UInt256::from(128);
+        assert_eq!(b, UInt256::new(0b1010, 0, Endian::Big));
+        b >>= &UInt256::from(129);
+        assert_eq!(b, UInt256::from(0b101));
+
+        assert_eq!(UInt256::from(300).shift_amount(), 300);
+        assert_eq!(UInt256::from(u32::MAX as usize + 1).shift_amount(), u32::MAX);
+        assert_eq!(UInt256::new(1, 0, Endian::Big).shift_amount(), u32::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to shift left with overflow")]
+    fn test_uint256_shift_amount_overflow() {
+        // Large amounts must not wrap around to a small shift.
+        let _ = UInt256::ONE << UInt256::new(1, 3, Endian::Big);
+    }
+
+    #[test]
+    #[should_panic(expected = "subtraction overflow")]
+    fn test_primitive_rhs_sub_overflow() {
+        let _ = UInt256::ZERO - 1u64;
+    }
+
+    #[test]
+    fn test_primitive_comparisons() {
+        let a = UInt256::from(330);
+        assert_eq!(a, 330u64);
+        assert_eq!(330u16, a);
+        assert_ne!(a, 331u128);
+        assert!(a > 0u64);
+        assert!(a < 331usize);
+        assert!(a >= 330u32);
+        assert!(255u8 < a);
+
+        let big = UInt256::new(1, 0, Endian::Big);
+        assert_ne!(big, 0u128);
+        assert!(big > u128::MAX);
+        assert!(u64::MAX < big);
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let amounts = vec![UInt256::from(2), UInt256::from(3), UInt256::from(7)];
+        assert_eq!(amounts.iter().sum::<UInt256>(), UInt256::from(12));
+        assert_eq!(amounts.iter().product::<UInt256>(), UInt256::from(42));
+        assert_eq!(amounts.into_iter().sum::<UInt256>(), UInt256::from(12));
+
+        let empty: Vec<UInt256> = vec![];
+        assert_eq!(empty.iter().sum::<UInt256>(), UInt256::ZERO);
+        assert_eq!(empty.iter().product::<UInt256>(), UInt256::ONE);
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn test_product_overflow() {
+        let _ = [UInt256::MAX, UInt256::from(2)].iter().product::<UInt256>();
+    }
+
+    #[test]
+    fn test_try_sum_and_try_product() {
+        let values = vec![UInt256::from(2), UInt256::from(3), UInt256::from(7)];
+        assert_eq!(UInt256::try_sum(&values), Some(UInt256::from(12)));
+        assert_eq!(UInt256::try_product(values.iter()), Some(UInt256::from(42)));
+        assert_eq!(UInt256::try_sum(Vec::<UInt256>::new()), Some(UInt256::ZERO));
+        assert_eq!(UInt256::try_product(Vec::<UInt256>::new()), Some(UInt256::ONE));
+    }
+
+    #[test]
+    fn test_try_sum_and_try_product_overflow() {
+        let near_max = UInt256::MAX - UInt256::from(10);
+        assert_eq!(UInt256::try_sum([near_max, UInt256::from(10)]), Some(UInt256::MAX));
+        assert_eq!(UInt256::try_sum([near_max, UInt256::from(6), UInt256::from(5)]), None);
+        assert_eq!(UInt256::try_product([UInt256::MAX, UInt256::from(2), UInt256::ZERO]), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "addition overflow")]
+    fn test_add_assign_overflow() {
+        let mut a = UInt256::MAX;
+        a += UInt256::ONE;
+    }
+}
@@ -0,0 +1,563 @@
+//! Parsing [`UInt256`] values from strings.
+
+use std::fmt;
+
+use super::*;
+
+/// The error returned when a string cannot be parsed as a [`UInt256`].
+///
+/// Along with the [`ParseErrorKind`], it records the byte offset in the input of the
+/// character that caused the error, so that it can be pointed at.
+///
+/// ## Examples
+///
+/// ```rust
+/// use uint256::{ParseErrorKind, UInt256};
+///
+/// let err = "0x12g4".parse::<UInt256>().unwrap_err();
+/// assert_eq!(err.kind(), ParseErrorKind::InvalidDigit);
+/// assert_eq!(err.offset(), 4);
+/// assert_eq!(err.to_string(), "invalid digit at offset 4");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseUInt256Error {
+    kind: ParseErrorKind,
+    offset: usize,
+}
+
+/// The reason a string could not be parsed as a [`UInt256`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ParseErrorKind {
+    /// There are no digits, e.g. the string is empty or only a prefix.
+    Empty,
+    /// A character is not a digit of the radix.
+    InvalidDigit,
+    /// The string starts with a radix prefix other than `0x`, `0o` or `0b`.
+    BadPrefix,
+    /// The value does not fit in 256 bits.
+    Overflow,
+    /// The input has more digits than a fixed-width encoding allows.
+    InvalidLength,
+    /// A decimal amount has more non-zero fractional digits than its decimals allow,
+    /// so it cannot be scaled exactly.
+    TooManyDecimals,
+    /// A number that must be written without leading zeros has one, as in the JSON-RPC
+    /// quantity `0x01`.
+    LeadingZero,
+}
+
+impl ParseUInt256Error {
+    pub(crate) fn new(kind: ParseErrorKind, offset: usize) -> Self {
+        ParseUInt256Error { kind, offset }
+    }
+
+    /// Returns the reason parsing failed.
+    pub fn kind(&self) -> ParseErrorKind {
+        self.kind
+    }
+
+    /// Returns the byte offset in the input of the offending character.
+    ///
+    /// For [`ParseErrorKind::Empty`] this is where the first digit was expected, and for
+    /// [`ParseErrorKind::Overflow`] the digit that made the value exceed 256 bits, or the
+    /// end of the input if scaling by [`UInt256::parse_units`] did.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl fmt::Display for ParseUInt256Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Empty => write!(f, "cannot parse UInt256 from empty string"),
+            ParseErrorKind::InvalidDigit => write!(f, "invalid digit at offset {}", self.offset),
+            ParseErrorKind::BadPrefix => write!(f, "invalid radix prefix at offset {}", self.offset),
+            ParseErrorKind::Overflow => write!(f, "number too large to fit in UInt256 at offset {}", self.offset),
+            ParseErrorKind::InvalidLength => write!(f, "too many digits at offset {}", self.offset),
+            ParseErrorKind::TooManyDecimals => {
+                write!(f, "fractional digit beyond the decimals at offset {}", self.offset)
+            }
+            ParseErrorKind::LeadingZero => write!(f, "leading zero at offset {}", self.offset),
+        }
+    }
+}
+
+impl std::error::Error for ParseUInt256Error {}
+
+impl UInt256 {
+    /// Parses a string of digits in the given `radix`, from 2 to 36.
+    ///
+    /// Digits above 9 are the letters `a` to `z`, in either case. Any number of digits is
+    /// accepted, including leading zeros, as long as the value fits in 256 bits. Surrounding
+    /// whitespace is ignored, and signs and prefixes are not accepted.
+    ///
+    /// The result is tagged with `endian`. With [`Endian::Little`], the high and low 128-bit
+    /// halves of the parsed value are swapped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 36.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Endian, ParseErrorKind, UInt256};
+    ///
+    /// assert_eq!(UInt256::from_str_radix("14a", 16, Endian::Big), Ok(UInt256::from(330)));
+    /// assert_eq!(UInt256::from_str_radix("101001010", 2, Endian::Big), Ok(UInt256::from(330)));
+    /// assert_eq!(UInt256::from_str_radix("96", 36, Endian::Big), Ok(UInt256::from(330)));
+    /// assert_eq!(UInt256::from_str_radix(&"f".repeat(64), 16, Endian::Big), Ok(UInt256::MAX));
+    ///
+    /// let err = UInt256::from_str_radix(&"1".repeat(257), 2, Endian::Big).unwrap_err();
+    /// assert_eq!(err.kind(), ParseErrorKind::Overflow);
+    /// ```
+    pub fn from_str_radix(s: &str, radix: u32, endian: Endian) -> Result<Self, ParseUInt256Error> {
+        assert!((2..=36).contains(&radix), "radix must be between 2 and 36");
+        let (digits, offset) = trim(s);
+        let value = parse_digits(digits, offset, radix, false)?;
+        Ok(match endian {
+            Endian::Big => UInt256 { endian, ..value },
+            Endian::Little => UInt256 { high: value.low, low: value.high, endian },
+        })
+    }
+
+    /// Parses a decimal string, such as `"1000000007"`.
+    ///
+    /// Surrounding whitespace is ignored. Signs, prefixes and separators are not accepted.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ParseErrorKind, UInt256};
+    ///
+    /// assert_eq!(UInt256::from_dec_str("1000000007"), Ok(UInt256::from(1_000_000_007)));
+    ///
+    /// // 2^256 - 1 and 2^256.
+    /// let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+    /// let too_large = "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+    /// assert_eq!(UInt256::from_dec_str(max), Ok(UInt256::MAX));
+    /// assert_eq!(UInt256::from_dec_str(too_large).unwrap_err().kind(), ParseErrorKind::Overflow);
+    /// assert_eq!(UInt256::from_dec_str("12a").unwrap_err().kind(), ParseErrorKind::InvalidDigit);
+    /// ```
+    pub fn from_dec_str(s: &str) -> Result<Self, ParseUInt256Error> {
+        let (digits, offset) = trim(s);
+        parse_digits(digits, offset, 10, false)
+    }
+
+    /// Parses a decimal amount such as `"1.5"` and scales it by `10^decimals`, the way
+    /// token amounts are converted to their smallest unit.
+    ///
+    /// The integer or the fractional part may be left out, as in `"1."` or `".5"`, and
+    /// surrounding whitespace is ignored. Nothing is rounded: fractional digits beyond
+    /// `decimals` must be zeros, and are otherwise a [`ParseErrorKind::TooManyDecimals`]
+    /// error. [`Self::format_units`] is the inverse.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ParseErrorKind, UInt256};
+    ///
+    /// assert_eq!(UInt256::parse_units("1.5", 18), Ok(UInt256::from(1_500_000_000_000_000_000)));
+    /// assert_eq!(UInt256::parse_units("0.000001", 6), Ok(UInt256::ONE));
+    /// assert_eq!(UInt256::parse_units("42", 0), Ok(UInt256::from(42)));
+    ///
+    /// let err = UInt256::parse_units("0.0000001", 6).unwrap_err();
+    /// assert_eq!(err.kind(), ParseErrorKind::TooManyDecimals);
+    /// assert_eq!(err.offset(), 8);
+    /// ```
+    pub fn parse_units(s: &str, decimals: u32) -> Result<Self, ParseUInt256Error> {
+        let (s, offset) = trim(s);
+        let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return Err(ParseUInt256Error::new(ParseErrorKind::Empty, offset + s.len()));
+        }
+        let fraction_offset = offset + integer.len() + 1;
+        if let Some(i) = integer.bytes().position(|b| !b.is_ascii_digit()) {
+            return Err(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, offset + i));
+        }
+        if let Some(i) = fraction.bytes().position(|b| !b.is_ascii_digit()) {
+            return Err(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, fraction_offset + i));
+        }
+
+        let kept = fraction.len().min(decimals as usize);
+        if let Some(i) = fraction.bytes().skip(kept).position(|b| b != b'0') {
+            return Err(ParseUInt256Error::new(ParseErrorKind::TooManyDecimals, fraction_offset + kept + i));
+        }
+
+        let mut value = UInt256::ZERO;
+        let digits = integer.bytes().enumerate().map(|(i, b)| (offset + i, b));
+        let fraction_digits = fraction.bytes().take(kept).enumerate().map(|(i, b)| (fraction_offset + i, b));
+        for (i, b) in digits.chain(fraction_digits) {
+            value = value
+                .checked_mul_add_u64(10, (b - b'0') as u64)
+                .ok_or(ParseUInt256Error::new(ParseErrorKind::Overflow, i))?;
+        }
+        // Scale by the missing fractional digits. Zero stays zero however large the scale,
+        // and anything else overflows within 78 digits.
+        if !value.is_zero() {
+            for _ in kept..decimals as usize {
+                value = value
+                    .checked_mul_add_u64(10, 0)
+                    .ok_or(ParseUInt256Error::new(ParseErrorKind::Overflow, offset + s.len()))?;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Builds a value from its digits in the given `radix`, from 2 to 256, least
+    /// significant first, as returned by [`Self::to_radix_digits_le`].
+    ///
+    /// Trailing zeros are allowed, and no digits at all make zero. The error offsets are
+    /// indices into `digits`: [`ParseErrorKind::InvalidDigit`] for a digit that is not
+    /// below `radix`, and [`ParseErrorKind::Overflow`] for the digit at which the value
+    /// no longer fits in 256 bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not between 2 and 256.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ParseErrorKind, UInt256};
+    ///
+    /// assert_eq!(UInt256::from_radix_digits_le(&[67, 45, 23, 1], 100), Ok(UInt256::from(1_234_567)));
+    /// assert_eq!(UInt256::from_radix_digits_le(&[7, 0, 0], 10), Ok(UInt256::from(7)));
+    ///
+    /// let err = UInt256::from_radix_digits_le(&[1, 10], 10).unwrap_err();
+    /// assert_eq!((err.kind(), err.offset()), (ParseErrorKind::InvalidDigit, 1));
+    /// ```
+    pub fn from_radix_digits_le(digits: &[u8], radix: u32) -> Result<Self, ParseUInt256Error> {
+        assert!((2..=256).contains(&radix), "radix must be between 2 and 256");
+        let mut value = UInt256::ZERO;
+        for (i, &digit) in digits.iter().enumerate().rev() {
+            if digit as u32 >= radix {
+                return Err(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, i));
+            }
+            value = value
+                .checked_mul_add_u64(radix as u64, digit as u64)
+                .ok_or(ParseUInt256Error::new(ParseErrorKind::Overflow, i))?;
+        }
+        Ok(value)
+    }
+
+    /// Calculates `self * mul + add`, returning `None` if the result does not fit in 256 bits.
+    pub(crate) fn checked_mul_add_u64(self, mul: u64, add: u64) -> Option<Self> {
+        let mut limbs = self.to_limbs();
+        let mut carry = add as u128;
+        for limb in limbs.iter_mut() {
+            let t = *limb as u128 * mul as u128 + carry;
+            *limb = t as u64;
+            carry = t >> 64;
+        }
+        (carry == 0).then(|| Self::from_limbs_with(limbs, self.endian))
+    }
+}
+
+/// Parses a string the way Rust integer literals are written: decimal by default, or
+/// hex, octal or binary with a `0x`, `0o` or `0b` prefix, with underscores ignored.
+pub(crate) fn parse_literal(s: &str) -> Result<UInt256, ParseUInt256Error> {
+    let (s, offset) = trim(s);
+    let (digits, radix) = match s.as_bytes() {
+        [b'0', b'x', ..] => (&s[2..], 16),
+        [b'0', b'o', ..] => (&s[2..], 8),
+        [b'0', b'b', ..] => (&s[2..], 2),
+        // No decimal number has a letter after a leading zero, so this is a prefix.
+        [b'0', prefix, ..] if prefix.is_ascii_alphabetic() => {
+            return Err(ParseUInt256Error::new(ParseErrorKind::BadPrefix, offset + 1));
+        }
+        // Like `_1` in Rust, a leading underscore does not start a number.
+        [b'_', ..] => return Err(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, offset)),
+        _ => (s, 10),
+    };
+    parse_digits(digits, offset + s.len() - digits.len(), radix, true)
+}
+
+/// Trims surrounding whitespace, returning the rest and its offset in `s`.
+fn trim(s: &str) -> (&str, usize) {
+    let trimmed = s.trim_start();
+    (trimmed.trim_end(), s.len() - trimmed.len())
+}
+
+/// Parses the digits of `s` in `radix`, without any prefix, sign or whitespace.
+///
+/// Underscores are skipped if `underscores` is set, and rejected otherwise. Errors
+/// report offsets relative to `offset`, the position of `s` in the whole input.
+pub(crate) fn parse_digits(
+    s: &str,
+    offset: usize,
+    radix: u32,
+    underscores: bool,
+) -> Result<UInt256, ParseUInt256Error> {
+    let digits = s.bytes().enumerate().filter(|&(_, b)| !(underscores && b == b'_'));
+
+    // Accumulate as many digits as fit in a `u64` before folding them into the value,
+    // e.g. 19 decimal or 16 hex digits at a time.
+    let chunk_digits = u64::MAX.ilog(radix as u64);
+    let mut value = UInt256::ZERO;
+    let (mut chunk, mut chunk_len, mut chunk_start) = (0u64, 0, 0);
+    let mut empty = true;
+    let fold = |value: UInt256, chunk: u64, chunk_len: u32, chunk_start: usize| {
+        value
+            .checked_mul_add_u64((radix as u64).pow(chunk_len), chunk)
+            .ok_or_else(|| overflow_error(value, &s[chunk_start..], offset + chunk_start, radix))
+    };
+    for (i, b) in digits {
+        let digit = (b as char)
+            .to_digit(radix)
+            .ok_or(ParseUInt256Error::new(ParseErrorKind::InvalidDigit, offset + i))?;
+        if chunk_len == 0 {
+            chunk_start = i;
+        }
+        chunk = chunk * radix as u64 + digit as u64;
+        chunk_len += 1;
+        empty = false;
+        if chunk_len == chunk_digits {
+            value = fold(value, chunk, chunk_len, chunk_start)?;
+            (chunk, chunk_len) = (0, 0);
+        }
+    }
+    if empty {
+        return Err(ParseUInt256Error::new(ParseErrorKind::Empty, offset + s.len()));
+    }
+    if chunk_len > 0 {
+        value = fold(value, chunk, chunk_len, chunk_start)?;
+    }
+    Ok(value)
+}
+
+/// Finds the digit of `s` at which accumulating onto `value` overflows, given that the
+/// whole chunk starting at `s` does, and returns the error pointing at it.
+#[cold]
+fn overflow_error(mut value: UInt256, s: &str, offset: usize, radix: u32) -> ParseUInt256Error {
+    for (i, b) in s.bytes().enumerate() {
+        // Only digits and skipped underscores precede the overflowing digit.
+        let Some(digit) = (b as char).to_digit(radix) else { continue };
+        match value.checked_mul_add_u64(radix as u64, digit as u64) {
+            Some(next) => value = next,
+            None => return ParseUInt256Error::new(ParseErrorKind::Overflow, offset + i),
+        }
+    }
+    unreachable!("the chunk overflows")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    use ParseErrorKind::*;
+
+    const MAX_DEC: &str = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+    fn err(kind: ParseErrorKind, offset: usize) -> Result<UInt256, ParseUInt256Error> {
+        Err(ParseUInt256Error::new(kind, offset))
+    }
+
+    #[test]
+    fn test_from_dec_str() {
+        assert_eq!(UInt256::from_dec_str("0"), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::from_dec_str("000"), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::from_dec_str(" 330\n"), Ok(UInt256::from(330)));
+        assert_eq!(UInt256::from_dec_str(MAX_DEC), Ok(UInt256::MAX));
+        // 2^64 and 2^128 straddle the 19-digit chunks.
+        assert_eq!(
+            UInt256::from_dec_str("18446744073709551616"),
+            Ok(UInt256::new(0, 1 << 64, Endian::Big)),
+        );
+        assert_eq!(
+            UInt256::from_dec_str("340282366920938463463374607431768211456"),
+            Ok(UInt256::new(1, 0, Endian::Big)),
+        );
+        let leading_zeros = format!("{}{}", "0".repeat(100), MAX_DEC);
+        assert_eq!(UInt256::from_dec_str(&leading_zeros), Ok(UInt256::MAX));
+    }
+
+    #[test]
+    fn test_from_dec_str_errors() {
+        assert_eq!(UInt256::from_dec_str(""), err(Empty, 0));
+        assert_eq!(UInt256::from_dec_str("  "), err(Empty, 2));
+        assert_eq!(UInt256::from_dec_str("-1"), err(InvalidDigit, 0));
+        assert_eq!(UInt256::from_dec_str("+1"), err(InvalidDigit, 0));
+        assert_eq!(UInt256::from_dec_str("12ab"), err(InvalidDigit, 2));
+        assert_eq!(UInt256::from_dec_str(" 1 000"), err(InvalidDigit, 2));
+        // MAX + 1, and a value with one more digit.
+        assert_eq!(
+            UInt256::from_dec_str("115792089237316195423570985008687907853269984665640564039457584007913129639936"),
+            err(Overflow, 77),
+        );
+        assert_eq!(UInt256::from_dec_str(&format!("{}0", MAX_DEC)), err(Overflow, 78));
+        assert_eq!(UInt256::from_dec_str(&format!("2{}", "0".repeat(77))), err(Overflow, 77));
+        assert_eq!(UInt256::from_dec_str(&format!("9{}", "0".repeat(200))), err(Overflow, 77));
+    }
+
+    #[test]
+    fn test_from_str_radix() {
+        for radix in 2..=36 {
+            assert_eq!(UInt256::from_str_radix("0", radix, Endian::Big), Ok(UInt256::ZERO));
+            assert_eq!(UInt256::from_str_radix("10", radix, Endian::Big), Ok(UInt256::from(radix as usize)));
+        }
+        assert_eq!(UInt256::from_str_radix("FfFf", 16, Endian::Big), Ok(UInt256::from(0xffff)));
+        assert_eq!(UInt256::from_str_radix("777", 8, Endian::Big), Ok(UInt256::from(0o777)));
+        assert_eq!(UInt256::from_str_radix("zz", 36, Endian::Big), Ok(UInt256::from(36 * 36 - 1)));
+        assert_eq!(UInt256::from_str_radix(&"1".repeat(256), 2, Endian::Big), Ok(UInt256::MAX));
+        assert_eq!(UInt256::from_str_radix(MAX_DEC, 10, Endian::Big), Ok(UInt256::MAX));
+        // 2^256 - 1 in base 36, and with leading zeros.
+        let max36 = "6dp5qcb22im238nr3wvp0ic7q99w035jmy2iw7i6n43d37jtof";
+        assert_eq!(UInt256::from_str_radix(max36, 36, Endian::Big), Ok(UInt256::MAX));
+        let padded = format!("{}{}", "0".repeat(300), "1".repeat(256));
+        assert_eq!(UInt256::from_str_radix(&padded, 2, Endian::Big), Ok(UInt256::MAX));
+
+        let value = UInt256::new(0x0123_4567, 0x89ab_cdef, Endian::Big);
+        let hex = format!("1234567{:032x}", 0x89ab_cdefu32);
+        assert_eq!(UInt256::from_str_radix(&hex, 16, Endian::Big), Ok(value));
+        let little = UInt256::from_str_radix(&hex, 16, Endian::Little).unwrap();
+        assert_eq!(little, UInt256::new(0x89ab_cdef, 0x0123_4567, Endian::Little));
+        assert_eq!(little.endian(), Endian::Little);
+    }
+
+    #[test]
+    fn test_from_str_radix_errors() {
+        assert_eq!(UInt256::from_str_radix("", 16, Endian::Big), err(Empty, 0));
+        assert_eq!(UInt256::from_str_radix("12", 2, Endian::Big), err(InvalidDigit, 1));
+        assert_eq!(UInt256::from_str_radix("g", 16, Endian::Big), err(InvalidDigit, 0));
+        assert_eq!(UInt256::from_str_radix("0x14a", 16, Endian::Big), err(InvalidDigit, 1));
+        assert_eq!(UInt256::from_str_radix("1١٢", 10, Endian::Big), err(InvalidDigit, 1));
+        assert_eq!(UInt256::from_str_radix(&"1".repeat(257), 2, Endian::Big), err(Overflow, 256));
+        let hex = format!("1{}", "0".repeat(64));
+        assert_eq!(UInt256::from_str_radix(&hex, 16, Endian::Big), err(Overflow, 64));
+        let max36_plus_one = "6dp5qcb22im238nr3wvp0ic7q99w035jmy2iw7i6n43d37jtog";
+        assert_eq!(UInt256::from_str_radix(max36_plus_one, 36, Endian::Big), err(Overflow, 49));
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be between 2 and 36")]
+    fn test_from_str_radix_invalid_radix() {
+        let _ = UInt256::from_str_radix("1", 37, Endian::Big);
+    }
+
+    #[test]
+    fn test_from_str_decimal_and_hex() {
+        assert_eq!("1000000007".parse::<UInt256>(), Ok(UInt256::from(1_000_000_007)));
+        assert_eq!(MAX_DEC.parse::<UInt256>(), Ok(UInt256::MAX));
+        assert_eq!(
+            "0x000000000000000000000000000000000000000000000000000000000000014a".parse::<UInt256>(),
+            Ok(UInt256::from(330)),
+        );
+        assert_eq!("ff".parse::<UInt256>(), err(InvalidDigit, 0));
+        assert!(format!("{}1", MAX_DEC).parse::<UInt256>().is_err());
+    }
+
+    #[test]
+    fn test_from_str_prefixes_and_underscores() {
+        assert_eq!("0b1010_1111".parse::<UInt256>(), Ok(UInt256::from(0b1010_1111)));
+        assert_eq!("0o777".parse::<UInt256>(), Ok(UInt256::from(0o777)));
+        assert_eq!("0xdead_BEEF".parse::<UInt256>(), Ok(UInt256::from(0xdead_beef)));
+        assert_eq!("1_000_000_000_000_000_000".parse::<UInt256>(), Ok(UInt256::from(1_000_000_000_000_000_000)));
+        // Underscores may repeat, trail, or follow the prefix, as in Rust literals.
+        assert_eq!("1__0_".parse::<UInt256>(), Ok(UInt256::from(10)));
+        assert_eq!("0x_ff".parse::<UInt256>(), Ok(UInt256::from(0xff)));
+        assert_eq!(format!("0b{}", "1_".repeat(256)).parse::<UInt256>(), Ok(UInt256::MAX));
+        assert_eq!(format!("0x{}", "f".repeat(64)).parse::<UInt256>(), Ok(UInt256::MAX));
+
+        assert_eq!("_1".parse::<UInt256>(), err(InvalidDigit, 0));
+        assert_eq!("0x".parse::<UInt256>(), err(Empty, 2));
+        assert_eq!("0b_".parse::<UInt256>(), err(Empty, 3));
+        assert_eq!("0b102".parse::<UInt256>(), err(InvalidDigit, 4));
+        assert_eq!("0o8".parse::<UInt256>(), err(InvalidDigit, 2));
+        assert_eq!(format!("0b1{}", "0".repeat(256)).parse::<UInt256>(), err(Overflow, 258));
+        // Only `FromStr` accepts underscores.
+        assert_eq!(UInt256::from_dec_str("1_000"), err(InvalidDigit, 1));
+        assert_eq!(UInt256::from_str_radix("ff_ff", 16, Endian::Big), err(InvalidDigit, 2));
+    }
+
+    #[test]
+    fn test_parse_error_offsets() {
+        assert_eq!("0XFF".parse::<UInt256>(), err(BadPrefix, 1));
+        assert_eq!("  0d10".parse::<UInt256>(), err(BadPrefix, 3));
+        assert_eq!("\t0x_1g".parse::<UInt256>(), err(InvalidDigit, 5));
+        assert_eq!(" 1_000_x".parse::<UInt256>(), err(InvalidDigit, 7));
+        assert_eq!(" 0o ".parse::<UInt256>(), err(Empty, 3));
+        // Underscores between the digits are counted in the offset.
+        let max_plus_one = format!("0x1{}", "_0000".repeat(16));
+        assert_eq!(max_plus_one.parse::<UInt256>(), err(Overflow, 82));
+        assert_eq!(format!("0x{}", "f_".repeat(65)).parse::<UInt256>(), err(Overflow, 130));
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        assert_eq!("".parse::<UInt256>().unwrap_err().to_string(), "cannot parse UInt256 from empty string");
+        assert_eq!("12x".parse::<UInt256>().unwrap_err().to_string(), "invalid digit at offset 2");
+        assert_eq!("0h12".parse::<UInt256>().unwrap_err().to_string(), "invalid radix prefix at offset 1");
+        assert_eq!(
+            format!("{}1", MAX_DEC).parse::<UInt256>().unwrap_err().to_string(),
+            "number too large to fit in UInt256 at offset 78",
+        );
+        let err: Box<dyn std::error::Error> = Box::new("x".parse::<UInt256>().unwrap_err());
+        assert_eq!(err.to_string(), "invalid digit at offset 0");
+        assert_eq!(
+            UInt256::parse_units("0.05", 1).unwrap_err().to_string(),
+            "fractional digit beyond the decimals at offset 3",
+        );
+    }
+
+    #[test]
+    fn test_from_radix_digits_le() {
+        assert_eq!(UInt256::from_radix_digits_le(&[], 10), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::from_radix_digits_le(&[255; 32], 256), Ok(UInt256::MAX));
+        assert_eq!(UInt256::from_radix_digits_le(&[1; 256], 2), Ok(UInt256::MAX));
+        let digits: Vec<u8> = MAX_DEC.bytes().rev().map(|b| b - b'0').collect();
+        assert_eq!(UInt256::from_radix_digits_le(&digits, 10), Ok(UInt256::MAX));
+
+        assert_eq!(UInt256::from_radix_digits_le(&[0, 2], 2), err(InvalidDigit, 1));
+        assert_eq!(UInt256::from_radix_digits_le(&[1; 257], 2), err(Overflow, 0));
+        let mut digits = [0; 33];
+        digits[32] = 1;
+        assert_eq!(UInt256::from_radix_digits_le(&digits, 256), err(Overflow, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be between 2 and 256")]
+    fn test_from_radix_digits_le_invalid_radix() {
+        let _ = UInt256::from_radix_digits_le(&[0], 1);
+    }
+
+    #[test]
+    fn test_parse_units() {
+        let ether = UInt256::from(1_000_000_000_000_000_000);
+        assert_eq!(UInt256::parse_units("1", 18), Ok(ether));
+        assert_eq!(UInt256::parse_units("1.", 18), Ok(ether));
+        assert_eq!(UInt256::parse_units("1.000", 18), Ok(ether));
+        assert_eq!(UInt256::parse_units(" 1.5 ", 18), Ok(UInt256::from(1_500_000_000_000_000_000)));
+        assert_eq!(UInt256::parse_units(".5", 1), Ok(UInt256::from(5)));
+        assert_eq!(UInt256::parse_units("0.000000000000000001", 18), Ok(UInt256::ONE));
+        assert_eq!(UInt256::parse_units("123.4500", 2), Ok(UInt256::from(12345)));
+        assert_eq!(UInt256::parse_units("0", u32::MAX), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::parse_units("0.000", 0), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::parse_units(MAX_DEC, 0), Ok(UInt256::MAX));
+        // The same digits with the point moved left, scaled back up.
+        let (integer, fraction) = MAX_DEC.split_at(60);
+        assert_eq!(UInt256::parse_units(&format!("{}.{}", integer, fraction), 18), Ok(UInt256::MAX));
+    }
+
+    #[test]
+    fn test_parse_units_errors() {
+        assert_eq!(UInt256::parse_units("", 18), err(Empty, 0));
+        assert_eq!(UInt256::parse_units(" . ", 18), err(Empty, 2));
+        assert_eq!(UInt256::parse_units("-1", 18), err(InvalidDigit, 0));
+        assert_eq!(UInt256::parse_units("1.5.0", 18), err(InvalidDigit, 3));
+        assert_eq!(UInt256::parse_units("1,5", 18), err(InvalidDigit, 1));
+        assert_eq!(UInt256::parse_units("1e18", 18), err(InvalidDigit, 1));
+        assert_eq!(UInt256::parse_units("1_000", 18), err(InvalidDigit, 1));
+        assert_eq!(UInt256::parse_units("1.25", 1), err(TooManyDecimals, 3));
+        assert_eq!(UInt256::parse_units("1.2050", 2), err(TooManyDecimals, 4));
+        assert_eq!(UInt256::parse_units("0.5", 0), err(TooManyDecimals, 2));
+        // Overflow in the digits, and in the scaling after them.
+        assert_eq!(UInt256::parse_units(&format!("{}0", MAX_DEC), 0), err(Overflow, 78));
+        assert_eq!(UInt256::parse_units(&format!("{}.1", MAX_DEC), 1), err(Overflow, 79));
+        assert_eq!(UInt256::parse_units("1", 78), err(Overflow, 1));
+        let ten_pow_77 = UInt256::from_dec_str(&format!("1{}", "0".repeat(77))).unwrap();
+        assert_eq!(UInt256::parse_units("1", 77), Ok(ten_pow_77));
+    }
+}
@@ -0,0 +1,324 @@
+//! Primality testing for [`UInt256`] values.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use super::*;
+
+/// The primes below 100, used for trial division before Miller–Rabin.
+const SMALL_PRIMES: [u64; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+/// Miller–Rabin rounds used when searching for primes, for an error probability of
+/// at most 2^-64 per candidate.
+const SEARCH_ROUNDS: u32 = 32;
+
+/// Bases that make Miller–Rabin deterministic for every `n < 2^64`.
+const DETERMINISTIC_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+impl UInt256 {
+    /// Returns `true` if `self` is probably prime, using the Miller–Rabin test.
+    ///
+    /// Values below 2^64 are checked against a fixed set of bases that is known to
+    /// give the exact answer, and `rounds` is ignored. Larger values are tested with
+    /// `rounds` random bases (at least one). A prime is always reported as prime, and
+    /// a composite passes with probability at most `4^-rounds`.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert!(UInt256::from(1_000_000_007).is_probable_prime(20));
+    /// assert!(!UInt256::from(561).is_probable_prime(20));
+    ///
+    /// // The secp256k1 field prime.
+    /// let p = UInt256::MAX - UInt256::from(0x1_0000_03d0);
+    /// assert!(p.is_probable_prime(20));
+    /// ```
+    pub fn is_probable_prime(&self, rounds: u32) -> bool {
+        let n = *self;
+        if n < UInt256::from(2) {
+            return false;
+        }
+        for &p in &SMALL_PRIMES {
+            let p = UInt256::from(p as usize);
+            if n == p {
+                return true;
+            }
+            if (n % p).is_zero() {
+                return false;
+            }
+        }
+
+        // n is odd and has no factor below 100 from here on.
+        let ctx = MontgomeryContext::new(n).expect("odd modulus");
+        let witness = MillerRabin::new(&ctx);
+        if n.high == 0 && n.low <= u64::MAX as u128 {
+            return DETERMINISTIC_BASES
+                .iter()
+                .all(|&a| !witness.is_witness(UInt256::from(a as usize)));
+        }
+
+        let mut rng = WitnessRng::new(n);
+        let range = n - UInt256::from(3);
+        (0..rounds.max(1)).all(|_| {
+            // A base in [2, n - 2].
+            let a = rng.next_uint256() % range + UInt256::from(2);
+            !witness.is_witness(a)
+        })
+    }
+
+    /// Returns the smallest prime greater than `self`.
+    ///
+    /// Candidates are checked with [`Self::is_probable_prime`], so a composite is
+    /// returned with negligible probability for values of 2^64 and above.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no prime above `self` that fits in 256 bits.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::from(0).next_prime(), UInt256::from(2));
+    /// assert_eq!(UInt256::from(13).next_prime(), UInt256::from(17));
+    /// assert_eq!(UInt256::from(1_000_000_000).next_prime(), UInt256::from(1_000_000_007));
+    /// ```
+    pub fn next_prime(self) -> Self {
+        self.checked_next_prime().expect("next_prime overflow")
+    }
+
+    /// Returns the smallest prime greater than `self`, or `None` if it does not fit
+    /// in 256 bits.
+    pub fn checked_next_prime(self) -> Option<Self> {
+        let two = UInt256::from(2);
+        if self < two {
+            return Some(UInt256 { endian: self.endian, ..two });
+        }
+        // Start from the next odd value and skip even candidates.
+        let mut candidate = self.checked_add(UInt256::ONE)?;
+        if candidate.is_even() {
+            candidate = candidate.checked_add(UInt256::ONE)?;
+        }
+        while !candidate.is_probable_prime(SEARCH_ROUNDS) {
+            candidate = candidate.checked_add(two)?;
+        }
+        Some(candidate)
+    }
+
+    /// Returns a random prime of exactly `bits` bits, drawn from the thread-local
+    /// random number generator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not between 2 and 256.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{Endian, UInt256};
+    ///
+    /// let p = UInt256::random_prime(128);
+    /// assert!(p.is_probable_prime(20));
+    /// assert!(p >= UInt256::new(0, 1 << 127, Endian::Big));
+    /// assert!(p < UInt256::new(1, 0, Endian::Big));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn random_prime(bits: u32) -> Self {
+        Self::random_prime_with(bits, &mut rand::thread_rng())
+    }
+
+    /// Returns a random prime of exactly `bits` bits, drawn from `rng`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not between 2 and 256.
+    #[cfg(feature = "rand")]
+    pub fn random_prime_with<R: rand::Rng + ?Sized>(bits: u32, rng: &mut R) -> Self {
+        assert!((2..=256).contains(&bits), "bits must be between 2 and 256");
+        let bits = bits as usize;
+        loop {
+            let mut limbs: [u64; 4] = rng.gen();
+            for (i, limb) in limbs.iter_mut().enumerate() {
+                let kept = bits.saturating_sub(64 * i).min(64);
+                *limb &= u64::MAX.checked_shr(64 - kept as u32).unwrap_or(0);
+            }
+            // Set the top bit so the prime has exactly `bits` bits, and the lowest one
+            // to only try odd candidates.
+            let mut candidate = UInt256::from_limbs_with(limbs, Endian::Big);
+            candidate.set_bit(bits - 1);
+            candidate.set_bit(0);
+            if candidate.is_probable_prime(SEARCH_ROUNDS) {
+                return candidate;
+            }
+        }
+    }
+}
+
+/// A Miller–Rabin test for an odd modulus `n = d * 2^s + 1`.
+struct MillerRabin<'a> {
+    ctx: &'a MontgomeryContext,
+    d: UInt256,
+    s: u32,
+    /// 1 and `n - 1` in Montgomery form.
+    one: UInt256,
+    minus_one: UInt256,
+}
+
+impl<'a> MillerRabin<'a> {
+    fn new(ctx: &'a MontgomeryContext) -> Self {
+        let n_minus_one = ctx.modulus() - UInt256::ONE;
+        let s = n_minus_one.trailing_zeros();
+        MillerRabin {
+            ctx,
+            d: n_minus_one >> s,
+            s,
+            one: ctx.to_montgomery(UInt256::ONE),
+            minus_one: ctx.to_montgomery(n_minus_one),
+        }
+    }
+
+    /// Returns `true` if `a` proves that `n` is composite.
+    fn is_witness(&self, a: UInt256) -> bool {
+        let a = self.ctx.to_montgomery(a);
+        if a.is_zero() {
+            // a is a multiple of n, which says nothing about n.
+            return false;
+        }
+        let mut x = self.ctx.pow(a, self.d);
+        if x == self.one || x == self.minus_one {
+            return false;
+        }
+        for _ in 1..self.s {
+            x = self.ctx.mul(x, x);
+            if x == self.minus_one {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A SplitMix64 generator for picking Miller–Rabin bases.
+///
+/// It is seeded from the randomly keyed hasher of [`RandomState`], so different runs
+/// test different bases without needing an external source of randomness.
+struct WitnessRng(u64);
+
+impl WitnessRng {
+    fn new(n: UInt256) -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(n.high);
+        hasher.write_u128(n.low);
+        WitnessRng(hasher.finish())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn next_uint256(&mut self) -> UInt256 {
+        let limbs = [self.next_u64(), self.next_u64(), self.next_u64(), self.next_u64()];
+        UInt256::from_limbs_with(limbs, Endian::Big)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_small_values() {
+        let primes: Vec<usize> = (0..200usize)
+            .filter(|&n| n >= 2 && (2..n).all(|d| n % d != 0))
+            .collect();
+        for n in 0..200usize {
+            assert_eq!(UInt256::from(n).is_probable_prime(1), primes.contains(&n), "{}", n);
+        }
+    }
+
+    #[test]
+    fn test_pseudoprimes() {
+        // Carmichael numbers and strong pseudoprimes to several small bases.
+        for n in [561usize, 41_041, 3_215_031_751, 3_825_123_056_546_413_051] {
+            assert!(!UInt256::from(n).is_probable_prime(1), "{}", n);
+        }
+        // The largest prime below 2^64.
+        assert!(UInt256::from(18_446_744_073_709_551_557usize).is_probable_prime(1));
+    }
+
+    #[test]
+    fn test_large_values() {
+        let secp256k1_p = UInt256::MAX - UInt256::from(0x1_0000_03d0);
+        let secp256k1_n = UInt256::new(
+            0xffffffff_ffffffff_ffffffff_fffffffe,
+            0xbaaedce6_af48a03b_bfd25e8c_d0364141,
+            Endian::Big,
+        );
+        // 2^127 - 1 and 2^255 - 19.
+        let mersenne = UInt256::new(0, u128::MAX >> 1, Endian::Big);
+        let ed25519_p = UInt256::new(u128::MAX >> 1, u128::MAX - 18, Endian::Big);
+        for p in [secp256k1_p, secp256k1_n, mersenne, ed25519_p] {
+            assert!(p.is_probable_prime(20), "{}", p);
+        }
+
+        // The largest prime below 2^256, and the odd values just above it.
+        let largest = UInt256::MAX - UInt256::from(188);
+        assert!(largest.is_probable_prime(0));
+        let mut n = largest + UInt256::from(2);
+        while n < UInt256::MAX {
+            assert!(!n.is_probable_prime(20), "{}", n);
+            n += UInt256::from(2);
+        }
+
+        // Products of two primes with no small factors.
+        assert!(!(mersenne * mersenne).is_probable_prime(20));
+        assert!(!(mersenne * UInt256::from(1_000_000_007)).is_probable_prime(20));
+    }
+
+    #[test]
+    fn test_next_prime() {
+        let mut p = UInt256::ZERO;
+        for expected in [2usize, 3, 5, 7, 11, 13, 17, 19, 23, 29] {
+            p = p.next_prime();
+            assert_eq!(p, UInt256::from(expected));
+        }
+        // The largest prime below 2^64, and the first one above it.
+        let below = UInt256::from(18_446_744_073_709_551_557usize);
+        assert_eq!(UInt256::from(18_446_744_073_709_551_533usize).next_prime(), below);
+        assert_eq!(below.next_prime(), UInt256::new(0, (1 << 64) + 13, Endian::Big));
+
+        let largest = UInt256::MAX - UInt256::from(188);
+        assert_eq!((largest - UInt256::ONE).next_prime(), largest);
+        assert_eq!(largest.checked_next_prime(), None);
+        assert_eq!(UInt256::MAX.checked_next_prime(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "next_prime overflow")]
+    fn test_next_prime_overflow() {
+        let _ = UInt256::MAX.next_prime();
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_random_prime() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for bits in [2, 3, 8, 64, 65, 200, 256] {
+            let p = UInt256::random_prime_with(bits, &mut rng);
+            assert_eq!(p.leading_zeros(), 256 - bits, "{}", p);
+            assert!(p.is_probable_prime(20), "{}", p);
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Protocol Buffers mapping of [`UInt256`] values to `bytes` fields, as 32 big-endian
+//! bytes.
+//!
+//! With the `prost` feature, `ProtoUInt256` is a ready-made [`prost`](https://docs.rs/prost)
+//! message for the schema
+//!
+//! ```proto
+//! message UInt256 {
+//!   bytes value = 1;
+//! }
+//! ```
+
+use super::*;
+
+impl UInt256 {
+    /// Returns the value for a protobuf `bytes` field: its 32 big-endian bytes.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// let bytes = UInt256::from(0x1234).to_proto_bytes();
+    /// assert_eq!(bytes.len(), 32);
+    /// assert_eq!(bytes[30..], [0x12, 0x34]);
+    /// ```
+    pub fn to_proto_bytes(&self) -> Vec<u8> {
+        self.to_be_array().to_vec()
+    }
+
+    /// Reads a value from a protobuf `bytes` field, as written by [`Self::to_proto_bytes`].
+    ///
+    /// The field must hold exactly 32 bytes, or none at all for zero, since proto3 leaves
+    /// an unset `bytes` field empty. Fails with [`ConversionError::WrongLength`] otherwise.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{ConversionError, UInt256};
+    ///
+    /// let value = UInt256::from(0x1234);
+    /// assert_eq!(UInt256::from_proto_bytes(&value.to_proto_bytes()), Ok(value));
+    /// assert_eq!(UInt256::from_proto_bytes(&[]), Ok(UInt256::ZERO));
+    /// assert_eq!(UInt256::from_proto_bytes(&[0x12, 0x34]), Err(ConversionError::WrongLength { len: 2 }));
+    /// ```
+    pub fn from_proto_bytes(bytes: &[u8]) -> Result<Self, ConversionError> {
+        match bytes.len() {
+            0 => Ok(UInt256::ZERO),
+            32 => UInt256::from_be_slice(bytes),
+            len => Err(ConversionError::WrongLength { len }),
+        }
+    }
+}
+
+/// A protobuf message holding a [`UInt256`] in a `bytes` field with tag 1, for embedding
+/// in `prost` messages.
+///
+/// ## Examples
+///
+/// ```rust
+/// use prost::Message;
+/// use uint256::{ProtoUInt256, UInt256};
+///
+/// let encoded = ProtoUInt256::from(UInt256::from(7)).encode_to_vec();
+/// assert_eq!(encoded[..2], [0x0a, 32]);
+///
+/// let decoded = ProtoUInt256::decode(&encoded[..]).unwrap();
+/// assert_eq!(UInt256::try_from(decoded), Ok(UInt256::from(7)));
+/// ```
+#[cfg(feature = "prost")]
+#[derive(Clone, PartialEq, Eq, Hash, prost::Message)]
+pub struct ProtoUInt256 {
+    /// The 32 big-endian bytes of the value, or none for zero.
+    #[prost(bytes = "vec", tag = "1")]
+    pub value: Vec<u8>,
+}
+
+#[cfg(feature = "prost")]
+impl From<UInt256> for ProtoUInt256 {
+    fn from(value: UInt256) -> Self {
+        ProtoUInt256 { value: value.to_proto_bytes() }
+    }
+}
+
+/// Fails with [`ConversionError::WrongLength`] unless the field holds 32 bytes or none.
+#[cfg(feature = "prost")]
+impl TryFrom<&ProtoUInt256> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(message: &ProtoUInt256) -> Result<Self, Self::Error> {
+        UInt256::from_proto_bytes(&message.value)
+    }
+}
+
+#[cfg(feature = "prost")]
+impl TryFrom<ProtoUInt256> for UInt256 {
+    type Error = ConversionError;
+
+    fn try_from(message: ProtoUInt256) -> Result<Self, Self::Error> {
+        UInt256::try_from(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_proto_bytes() {
+        for value in [UInt256::ZERO, UInt256::ONE, UInt256::new(1, 0, Endian::Little), UInt256::MAX] {
+            let bytes = value.to_proto_bytes();
+            assert_eq!(bytes, value.to_be_array());
+            assert_eq!(UInt256::from_proto_bytes(&bytes), Ok(value));
+        }
+        assert_eq!(UInt256::from_proto_bytes(&[]), Ok(UInt256::ZERO));
+        assert_eq!(UInt256::from_proto_bytes(&[1]), Err(ConversionError::WrongLength { len: 1 }));
+        assert_eq!(UInt256::from_proto_bytes(&[0; 33]), Err(ConversionError::WrongLength { len: 33 }));
+    }
+
+    #[cfg(feature = "prost")]
+    #[test]
+    fn test_prost_message() {
+        use prost::Message;
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        struct Transfer {
+            #[prost(message, optional, tag = "1")]
+            amount: Option<ProtoUInt256>,
+            #[prost(uint32, tag = "2")]
+            memo: u32,
+        }
+
+        let amount = UInt256::MAX - UInt256::from(5);
+        let transfer = Transfer { amount: Some(amount.into()), memo: 9 };
+        let encoded = transfer.encode_to_vec();
+        // The amount is a 34-byte nested message, then the memo.
+        assert_eq!(encoded.len(), 2 + 34 + 2);
+        let decoded = Transfer::decode(&encoded[..]).unwrap();
+        assert_eq!(UInt256::try_from(decoded.amount.as_ref().unwrap()), Ok(amount));
+
+        // An empty message is zero, and a short field is rejected.
+        assert_eq!(UInt256::try_from(ProtoUInt256::default()), Ok(UInt256::ZERO));
+        let short = ProtoUInt256 { value: vec![1, 2, 3] };
+        assert_eq!(UInt256::try_from(short), Err(ConversionError::WrongLength { len: 3 }));
+    }
+}
@@ -0,0 +1,183 @@
+//! RLP (Recursive Length Prefix) encoding of [`UInt256`] values, as used by Ethereum.
+//!
+//! An integer is the RLP string of its shortest big-endian bytes: a single byte below
+//! `0x80` is its own encoding, and anything else is prefixed with `0x80` plus its length,
+//! so zero is `[0x80]`.
+
+use std::fmt;
+
+use super::*;
+
+/// The error returned when bytes are not the canonical RLP encoding of a [`UInt256`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RlpError {
+    /// There are no bytes at all.
+    Empty,
+    /// The item is a list, or a string long enough to need a length of its own.
+    UnexpectedItem {
+        /// The prefix byte.
+        prefix: u8,
+    },
+    /// The string is longer than the 32 bytes of a `UInt256`.
+    TooManyBytes {
+        /// The length of the string.
+        len: usize,
+    },
+    /// The string starts with a zero byte.
+    LeadingZero,
+    /// A single byte below `0x80` has a length prefix, where it must stand alone.
+    NonCanonical,
+    /// The input ends before the string does.
+    Truncated {
+        /// The length of the string.
+        len: usize,
+    },
+    /// There are bytes after the item.
+    TrailingBytes {
+        /// The number of extra bytes.
+        len: usize,
+    },
+}
+
+impl fmt::Display for RlpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RlpError::Empty => write!(f, "cannot decode UInt256 from empty RLP"),
+            RlpError::UnexpectedItem { prefix } => {
+                write!(f, "expected an RLP string of at most 55 bytes, got prefix {:#04x}", prefix)
+            }
+            RlpError::TooManyBytes { len } => write!(f, "expected at most 32 bytes, got {}", len),
+            RlpError::LeadingZero => write!(f, "RLP integer has a leading zero byte"),
+            RlpError::NonCanonical => write!(f, "single RLP byte below 0x80 has a length prefix"),
+            RlpError::Truncated { len } => write!(f, "RLP string of {} bytes is truncated", len),
+            RlpError::TrailingBytes { len } => write!(f, "{} bytes after the RLP item", len),
+        }
+    }
+}
+
+impl std::error::Error for RlpError {}
+
+impl UInt256 {
+    /// Returns the canonical RLP encoding of the value.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::UInt256;
+    ///
+    /// assert_eq!(UInt256::ZERO.rlp_encode(), [0x80]);
+    /// assert_eq!(UInt256::from(0x0f).rlp_encode(), [0x0f]);
+    /// assert_eq!(UInt256::from(0x0400).rlp_encode(), [0x82, 0x04, 0x00]);
+    /// ```
+    pub fn rlp_encode(&self) -> Vec<u8> {
+        let bytes = self.to_be_array();
+        let len = self.byte_len();
+        if len == 1 && bytes[31] < 0x80 {
+            return vec![bytes[31]];
+        }
+        let mut encoded = Vec::with_capacity(1 + len);
+        encoded.push(0x80 + len as u8);
+        encoded.extend_from_slice(&bytes[32 - len..]);
+        encoded
+    }
+
+    /// Decodes the canonical RLP encoding of a value, as produced by [`Self::rlp_encode`].
+    ///
+    /// `bytes` must hold exactly one RLP string of at most 32 bytes, without leading
+    /// zeros, and a value below `0x80` must be a single byte without a prefix. Anything
+    /// else is rejected, since Ethereum treats other encodings as invalid.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use uint256::{RlpError, UInt256};
+    ///
+    /// assert_eq!(UInt256::rlp_decode(&[0x82, 0x04, 0x00]), Ok(UInt256::from(0x0400)));
+    /// assert_eq!(UInt256::rlp_decode(&[0x82, 0x00, 0x04]), Err(RlpError::LeadingZero));
+    /// assert_eq!(UInt256::rlp_decode(&[0x81, 0x04]), Err(RlpError::NonCanonical));
+    /// ```
+    pub fn rlp_decode(bytes: &[u8]) -> Result<Self, RlpError> {
+        let (&prefix, rest) = bytes.split_first().ok_or(RlpError::Empty)?;
+        let string = match prefix {
+            // Zero is the empty string, never the byte 0x00.
+            0x00 => return Err(RlpError::LeadingZero),
+            0x01..=0x7f => &bytes[..1],
+            0x80..=0xb7 => {
+                let len = (prefix - 0x80) as usize;
+                if len > 32 {
+                    return Err(RlpError::TooManyBytes { len });
+                }
+                let string = rest.get(..len).ok_or(RlpError::Truncated { len })?;
+                match string {
+                    [0, ..] => return Err(RlpError::LeadingZero),
+                    [byte] if *byte < 0x80 => return Err(RlpError::NonCanonical),
+                    _ => string,
+                }
+            }
+            _ => return Err(RlpError::UnexpectedItem { prefix }),
+        };
+        let consumed = string.len() + (prefix >= 0x80) as usize;
+        if bytes.len() > consumed {
+            return Err(RlpError::TrailingBytes { len: bytes.len() - consumed });
+        }
+        let mut padded = [0u8; 32];
+        padded[32 - string.len()..].copy_from_slice(string);
+        Ok(UInt256::from_be_bytes(&padded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::{random_values, SEED};
+
+    #[test]
+    fn test_rlp_encode() {
+        assert_eq!(UInt256::ZERO.rlp_encode(), [0x80]);
+        assert_eq!(UInt256::ONE.rlp_encode(), [0x01]);
+        assert_eq!(UInt256::from(0x7f).rlp_encode(), [0x7f]);
+        assert_eq!(UInt256::from(0x80).rlp_encode(), [0x81, 0x80]);
+        assert_eq!(UInt256::from(0xff).rlp_encode(), [0x81, 0xff]);
+        assert_eq!(UInt256::from(0x0100).rlp_encode(), [0x82, 0x01, 0x00]);
+        let mut max = vec![0xa0];
+        max.extend([0xff; 32]);
+        assert_eq!(UInt256::MAX.rlp_encode(), max);
+    }
+
+    #[test]
+    fn test_rlp_round_trip() {
+        for value in random_values(SEED, 200) {
+            let encoded = value.rlp_encode();
+            assert_eq!(encoded.len(), 1 + value.byte_len() - (value.byte_len() == 1 && value.low < 0x80) as usize);
+            assert_eq!(UInt256::rlp_decode(&encoded), Ok(value), "{:x?}", encoded);
+        }
+    }
+
+    #[test]
+    fn test_rlp_decode_errors() {
+        assert_eq!(UInt256::rlp_decode(&[]), Err(RlpError::Empty));
+        assert_eq!(UInt256::rlp_decode(&[0x00]), Err(RlpError::LeadingZero));
+        assert_eq!(UInt256::rlp_decode(&[0x81, 0x00]), Err(RlpError::LeadingZero));
+        assert_eq!(UInt256::rlp_decode(&[0x82, 0x00, 0x80]), Err(RlpError::LeadingZero));
+        assert_eq!(UInt256::rlp_decode(&[0x81, 0x7f]), Err(RlpError::NonCanonical));
+        assert_eq!(UInt256::rlp_decode(&[0x82, 0x01]), Err(RlpError::Truncated { len: 2 }));
+        assert_eq!(UInt256::rlp_decode(&[0x80, 0x01]), Err(RlpError::TrailingBytes { len: 1 }));
+        assert_eq!(UInt256::rlp_decode(&[0x01, 0x01, 0x01]), Err(RlpError::TrailingBytes { len: 2 }));
+        let mut long = vec![0xa1];
+        long.extend([0xff; 33]);
+        assert_eq!(UInt256::rlp_decode(&long), Err(RlpError::TooManyBytes { len: 33 }));
+        assert_eq!(UInt256::rlp_decode(&[0xb8, 0x38]), Err(RlpError::UnexpectedItem { prefix: 0xb8 }));
+        assert_eq!(UInt256::rlp_decode(&[0xc0]), Err(RlpError::UnexpectedItem { prefix: 0xc0 }));
+    }
+
+    #[test]
+    fn test_rlp_error_display() {
+        assert_eq!(RlpError::LeadingZero.to_string(), "RLP integer has a leading zero byte");
+        assert_eq!(
+            RlpError::UnexpectedItem { prefix: 0xc0 }.to_string(),
+            "expected an RLP string of at most 55 bytes, got prefix 0xc0",
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! [`serde`](https://docs.rs/serde) support for [`UInt256`], enabled by the `serde`
+//! feature.
+//!
+//! The encoding follows [`Serializer::is_human_readable`]:
+//!
+//! - Human-readable formats such as JSON and TOML get a `0x`-prefixed hex string
+//!   without leading zeros, such as `"0x14a"`. Deserializing accepts that, decimal
+//!   strings and unsigned integers, so hand-written configuration can use whichever is
+//!   clearer.
+//! - Binary formats such as bincode and postcard get exactly 32 big-endian bytes, as a
+//!   fixed-size tuple without a length prefix.
+//!
+//! The [`serde_hex`], [`serde_quantity`], [`serde_dec`] and [`serde_bytes`] modules
+//! force one encoding whatever the format, with `#[serde(with = "...")]`:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use uint256::UInt256;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Transfer {
+//!     #[serde(with = "uint256::serde_dec")]
+//!     amount: UInt256,
+//!     #[serde(with = "uint256::serde_hex")]
+//!     nonce: UInt256,
+//! }
+//!
+//! let transfer = Transfer { amount: UInt256::from(1_000_000), nonce: UInt256::from(255) };
+//! let json = serde_json::to_string(&transfer).unwrap();
+//! assert_eq!(json, r#"{"amount":"1000000","nonce":"0xff"}"#);
+//! ```
+//!
+//! The [`Endian`] tag is not serialized, and deserialized values are big endian.
+
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+use super::*;
+
+impl Serialize for UInt256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serde_hex::serialize(self, serializer)
+        } else {
+            serde_bytes::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for UInt256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(StrVisitor { parse: str::parse, expecting: "a hex or decimal string" })
+        } else {
+            serde_bytes::deserialize(deserializer)
+        }
+    }
+}
+
+/// Always serializes a [`UInt256`] as a `0x`-prefixed hex string without leading zeros,
+/// for use with `#[serde(with = "uint256::serde_hex")]`.
+///
+/// Deserializing accepts 1 to 64 hex digits with an optional `0x` prefix, like
+/// [`hex::decode`](crate::hex::decode).
+pub mod serde_hex {
+    use super::*;
+
+    /// Serializes `value` as a hex string.
+    pub fn serialize<S: Serializer>(value: &UInt256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{:#x}", value))
+    }
+
+    /// Deserializes a value from a hex string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UInt256, D::Error> {
+        deserializer.deserialize_str(StrVisitor { parse: hex::decode, expecting: "a hex string" })
+    }
+}
+
+/// Serializes a [`UInt256`] as an Ethereum JSON-RPC quantity, for use with
+/// `#[serde(with = "uint256::serde_quantity")]`.
+///
+/// The string is the same as [`serde_hex`] writes, but deserializing is strict, as
+/// [`UInt256::from_quantity_hex`] describes: `"0x0400"` and `"0x"` are rejected.
+pub mod serde_quantity {
+    use super::*;
+
+    /// Serializes `value` as a quantity.
+    pub fn serialize<S: Serializer>(value: &UInt256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&value.to_quantity_hex())
+    }
+
+    /// Deserializes a value from a quantity.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UInt256, D::Error> {
+        deserializer.deserialize_str(StrVisitor { parse: UInt256::from_quantity_hex, expecting: "a hex quantity" })
+    }
+}
+
+/// Always serializes a [`UInt256`] as a decimal string, for use with
+/// `#[serde(with = "uint256::serde_dec")]`.
+///
+/// A string rather than a number, since JSON parsers commonly lose precision above 2^53.
+/// Deserializing accepts decimal strings, and unsigned integers in formats that have them.
+pub mod serde_dec {
+    use super::*;
+
+    /// Serializes `value` as a decimal string.
+    pub fn serialize<S: Serializer>(value: &UInt256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(value)
+    }
+
+    /// Deserializes a value from a decimal string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UInt256, D::Error> {
+        let visitor = StrVisitor { parse: UInt256::from_dec_str, expecting: "a decimal string" };
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor)
+        } else {
+            deserializer.deserialize_str(visitor)
+        }
+    }
+}
+
+/// Always serializes a [`UInt256`] as its 32 big-endian bytes, for use with
+/// `#[serde(with = "uint256::serde_bytes")]`.
+///
+/// The bytes are a fixed-size tuple, like `[u8; 32]`, so binary formats write exactly
+/// 32 bytes and JSON writes an array of 32 numbers. Deserializing also accepts a byte
+/// string of exactly 32 bytes.
+pub mod serde_bytes {
+    use super::*;
+
+    /// Serializes `value` as 32 big-endian bytes.
+    pub fn serialize<S: Serializer>(value: &UInt256, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(32)?;
+        for byte in value.to_be_array() {
+            tuple.serialize_element(&byte)?;
+        }
+        tuple.end()
+    }
+
+    /// Deserializes a value from 32 big-endian bytes.
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<UInt256, D::Error> {
+        deserializer.deserialize_tuple(32, BytesVisitor)
+    }
+}
+
+/// Parses strings with `parse`, and takes unsigned integers as they are.
+struct StrVisitor {
+    parse: fn(&str) -> Result<UInt256, ParseUInt256Error>,
+    expecting: &'static str,
+}
+
+impl Visitor<'_> for StrVisitor {
+    type Value = UInt256;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<UInt256, E> {
+        (self.parse)(s).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<UInt256, E> {
+        Ok(UInt256::new(0, value as u128, Endian::Big))
+    }
+
+    // TOML and some other formats hand every integer to `visit_i64`.
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<UInt256, E> {
+        match u64::try_from(value) {
+            Ok(value) => self.visit_u64(value),
+            Err(_) => Err(E::invalid_value(de::Unexpected::Signed(value), &self)),
+        }
+    }
+
+    fn visit_u128<E: de::Error>(self, value: u128) -> Result<UInt256, E> {
+        Ok(UInt256::new(0, value, Endian::Big))
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = UInt256;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("32 big-endian bytes")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<UInt256, A::Error> {
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(33, &self));
+        }
+        Ok(UInt256::from_be_bytes(&bytes))
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<UInt256, E> {
+        let bytes: &[u8; 32] = bytes.try_into().map_err(|_| E::invalid_length(bytes.len(), &self))?;
+        Ok(UInt256::from_be_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::uint256::test_utils::{random_values, SEED};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Overrides {
+        #[serde(with = "serde_hex")]
+        hex: UInt256,
+        #[serde(with = "serde_dec")]
+        dec: UInt256,
+        #[serde(with = "serde_bytes")]
+        bytes: UInt256,
+    }
+
+    #[test]
+    fn test_serialize_json() {
+        assert_eq!(serde_json::to_string(&UInt256::ZERO).unwrap(), r#""0x0""#);
+        assert_eq!(serde_json::to_string(&UInt256::from(330)).unwrap(), r#""0x14a""#);
+        let max = format!(r#""0x{}""#, "f".repeat(64));
+        assert_eq!(serde_json::to_string(&UInt256::MAX).unwrap(), max);
+        assert_eq!(serde_json::to_string(&Endian::Little).unwrap(), r#""Little""#);
+    }
+
+    #[test]
+    fn test_deserialize_json() {
+        let parse = |json: &str| serde_json::from_str::<UInt256>(json);
+        assert_eq!(parse(r#""0x14a""#).unwrap(), UInt256::from(330));
+        assert_eq!(parse(r#""330""#).unwrap(), UInt256::from(330));
+        assert_eq!(parse("330").unwrap(), UInt256::from(330));
+        assert_eq!(parse(&format!("{}", u64::MAX)).unwrap(), UInt256::new(0, u64::MAX as u128, Endian::Big));
+        assert_eq!(parse(&format!(r#""{}""#, UInt256::MAX)).unwrap(), UInt256::MAX);
+        assert_eq!(serde_json::from_str::<Endian>(r#""Big""#).unwrap(), Endian::Big);
+
+        let err = parse(r#""0x""#).unwrap_err().to_string();
+        assert!(err.starts_with("cannot parse UInt256 from empty string"), "{}", err);
+        assert!(parse(r#""12a""#).is_err());
+        assert!(parse("-1").is_err());
+        assert!(parse("1.5").is_err());
+        assert!(parse(&format!(r#""0x1{}""#, "0".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_signed() {
+        use serde::de::value::{Error, I64Deserializer};
+
+        let parse = |value: i64| UInt256::deserialize(I64Deserializer::<Error>::new(value));
+        assert_eq!(parse(330).unwrap(), UInt256::from(330));
+        assert_eq!(parse(0).unwrap(), UInt256::ZERO);
+        assert_eq!(parse(i64::MAX).unwrap(), UInt256::new(0, i64::MAX as u128, Endian::Big));
+        let err = parse(-1).unwrap_err().to_string();
+        assert_eq!(err, "invalid value: integer `-1`, expected a hex or decimal string");
+        assert!(serde_dec::deserialize(I64Deserializer::<Error>::new(-5)).is_err());
+    }
+
+    #[test]
+    fn test_binary_is_32_bytes() {
+        let value = UInt256::from(0x0102);
+        let encoded = bincode::serialize(&value).unwrap();
+        assert_eq!(encoded, value.to_be_array());
+        assert_eq!(bincode::deserialize::<UInt256>(&encoded).unwrap(), value);
+        assert!(bincode::deserialize::<UInt256>(&encoded[..31]).is_err());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let value = UInt256::from(1_000_000);
+        let overrides = Overrides { hex: value, dec: value, bytes: value };
+        let json = serde_json::to_string(&overrides).unwrap();
+        let bytes = serde_json::to_string(&value.to_be_array()).unwrap();
+        assert_eq!(json, format!(r#"{{"hex":"0xf4240","dec":"1000000","bytes":{}}}"#, bytes));
+        assert_eq!(serde_json::from_str::<Overrides>(&json).unwrap(), overrides);
+
+        // The string encodings hold in binary formats too.
+        let encoded = bincode::serialize(&overrides).unwrap();
+        assert_eq!(encoded.len(), (8 + 7) + (8 + 7) + 32);
+        assert_eq!(bincode::deserialize::<Overrides>(&encoded).unwrap(), overrides);
+
+        // Each override only accepts its own encoding.
+        let mixed = format!(r#"{{"hex":"1000000","dec":"0xf4240","bytes":{}}}"#, bytes);
+        assert!(serde_json::from_str::<Overrides>(&mixed).is_err());
+    }
+
+    #[test]
+    fn test_quantity() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Block {
+            #[serde(with = "serde_quantity")]
+            number: UInt256,
+        }
+
+        let block = Block { number: UInt256::from(0x1b4) };
+        let json = serde_json::to_string(&block).unwrap();
+        assert_eq!(json, r#"{"number":"0x1b4"}"#);
+        assert_eq!(serde_json::from_str::<Block>(&json).unwrap(), block);
+        assert_eq!(serde_json::to_string(&Block { number: UInt256::ZERO }).unwrap(), r#"{"number":"0x0"}"#);
+
+        for invalid in [r#""0x""#, r#""0x01b4""#, r#""1b4""#, "436"] {
+            let json = format!(r#"{{"number":{}}}"#, invalid);
+            assert!(serde_json::from_str::<Block>(&json).is_err(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for value in random_values(SEED, 100) {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(serde_json::from_str::<UInt256>(&json).unwrap(), value, "{}", json);
+            let overrides = Overrides { hex: value, dec: value, bytes: value };
+            let json = serde_json::to_string(&overrides).unwrap();
+            assert_eq!(serde_json::from_str::<Overrides>(&json).unwrap(), overrides);
+            let encoded = bincode::serialize(&overrides).unwrap();
+            assert_eq!(bincode::deserialize::<Overrides>(&encoded).unwrap(), overrides);
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Database column types for [`UInt256`], each enabled by the feature of the same name:
+//!
+//! - `diesel`: `AsExpression`, `FromSqlRow`, `ToSql` and `FromSql` of [`diesel`](https://docs.rs/diesel)
+//!   for the `Numeric` and `Binary` SQL types of the Postgres backend.
+//! - `postgres`: `ToSql` and `FromSql` of [`postgres-types`](https://docs.rs/postgres-types),
+//!   the traits of `postgres` and `tokio-postgres`, for `NUMERIC` and `BYTEA` columns.
+//! - `sqlx`: `Type`, `Encode` and `Decode` of [`sqlx`](https://docs.rs/sqlx) for Postgres,
+//!   bound as `NUMERIC` and read from `NUMERIC` or `BYTEA`, and for SQLite, bound as decimal
+//!   `TEXT` and read from `TEXT` or `BLOB`.
+//!
+//! `NUMERIC` holds the value as a whole number in the binary format of the Postgres wire
+//! protocol, and reading fails for negative, fractional, NaN or infinite numbers and for
+//! numbers above [`UInt256::MAX`]. `BYTEA` holds the 32 big-endian bytes, which sort
+//! like the values, and reads any big-endian value of up to 32 bytes, as do SQLite blobs.
+
+use std::error::Error;
+
+use super::*;
+
+/// The error type of the database traits.
+type BoxError = Box<dyn Error + Send + Sync>;
+
+/// The sign word of a positive `NUMERIC`, and of the special values that are not numbers.
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xc000;
+const NUMERIC_PINF: u16 = 0xd000;
+const NUMERIC_NINF: u16 = 0xf000;
+
+/// Returns the base-10000 digits of the value, most significant first and without
+/// trailing zero digits, and the weight of the first digit, as `NUMERIC` stores them.
+fn numeric_digits(value: &UInt256) -> (Vec<i16>, i16) {
+    let mut digits = Vec::new();
+    let mut rest = *value;
+    while !rest.is_zero() {
+        let (q, r) = rest.div_rem_u64(10_000);
+        digits.push(r as i16);
+        rest = q;
+    }
+    let weight = digits.len() as i16 - 1;
+    let zeros = digits.iter().take_while(|&&d| d == 0).count();
+    digits.drain(..zeros);
+    digits.reverse();
+    (digits, weight)
+}
+
+/// Returns the whole number of the base-10000 `digits` whose first digit has `weight`.
+fn from_numeric_digits(digits: &[i16], weight: i16) -> Result<UInt256, ConversionError> {
+    let whole = (weight as isize + 1).max(0) as usize;
+    if digits.iter().skip(whole).any(|&d| d != 0) {
+        return Err(ConversionError::Fractional);
+    }
+    let mut value = UInt256::ZERO;
+    for i in 0..whole {
+        let digit = digits.get(i).copied().unwrap_or(0);
+        value = value.checked_mul_add_u64(10_000, digit as u64).ok_or(ConversionError::Overflow)?;
+    }
+    Ok(value)
+}
+
+/// Writes the value in the binary `NUMERIC` format: the digit count, weight, sign and
+/// display scale as 16-bit words, then the base-10000 digits.
+fn write_numeric(value: &UInt256, out: &mut Vec<u8>) {
+    let (digits, weight) = numeric_digits(value);
+    let weight = if digits.is_empty() { 0 } else { weight };
+    out.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+    out.extend_from_slice(&weight.to_be_bytes());
+    out.extend_from_slice(&NUMERIC_POS.to_be_bytes());
+    out.extend_from_slice(&0u16.to_be_bytes());
+    for digit in digits {
+        out.extend_from_slice(&digit.to_be_bytes());
+    }
+}
+
+/// Reads a value in the binary `NUMERIC` format.
+fn read_numeric(raw: &[u8]) -> Result<UInt256, BoxError> {
+    let words: Vec<u16> = raw.chunks_exact(2).map(|w| u16::from_be_bytes([w[0], w[1]])).collect();
+    if !raw.len().is_multiple_of(2) || words.len() < 4 || words.len() != 4 + words[0] as usize {
+        return Err("invalid NUMERIC value".into());
+    }
+    match words[2] {
+        NUMERIC_POS => {}
+        NUMERIC_NEG => return Err(ConversionError::Negative.into()),
+        NUMERIC_NAN => return Err(ConversionError::NotANumber.into()),
+        NUMERIC_PINF | NUMERIC_NINF => return Err(ConversionError::Overflow.into()),
+        _ => return Err("invalid NUMERIC sign".into()),
+    }
+    let digits: Vec<i16> = words[4..].iter().map(|&d| d as i16).collect();
+    if digits.iter().any(|&d| !(0..10_000).contains(&d)) {
+        return Err("invalid NUMERIC digit".into());
+    }
+    Ok(from_numeric_digits(&digits, words[1] as i16)?)
+}
+
+/// Reads a value in the text format of `NUMERIC`, such as `12345` or `12345.00`.
+#[cfg(feature = "sqlx")]
+fn parse_numeric(s: &str) -> Result<UInt256, BoxError> {
+    match s {
+        "NaN" => return Err(ConversionError::NotANumber.into()),
+        "Infinity" | "-Infinity" => return Err(ConversionError::Overflow.into()),
+        _ if s.starts_with('-') => return Err(ConversionError::Negative.into()),
+        _ => {}
+    }
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if fraction.bytes().any(|b| b != b'0') {
+        return Err(ConversionError::Fractional.into());
+    }
+    match UInt256::from_dec_str(whole) {
+        Err(e) if e.kind() == ParseErrorKind::Overflow => Err(ConversionError::Overflow.into()),
+        value => Ok(value?),
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl diesel::serialize::ToSql<diesel::sql_types::Numeric, diesel::pg::Pg> for UInt256 {
+    fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>) -> diesel::serialize::Result {
+        use std::io::Write;
+
+        let mut buf = Vec::with_capacity(48);
+        write_numeric(self, &mut buf);
+        out.write_all(&buf)?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl diesel::deserialize::FromSql<diesel::sql_types::Numeric, diesel::pg::Pg> for UInt256 {
+    fn from_sql(value: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        read_numeric(value.as_bytes())
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl diesel::serialize::ToSql<diesel::sql_types::Binary, diesel::pg::Pg> for UInt256 {
+    fn to_sql<'b>(&'b self, out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>) -> diesel::serialize::Result {
+        use std::io::Write;
+
+        out.write_all(&self.to_be_array())?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl diesel::deserialize::FromSql<diesel::sql_types::Binary, diesel::pg::Pg> for UInt256 {
+    fn from_sql(value: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(UInt256::from_be_slice(value.as_bytes())?)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl postgres_types::ToSql for UInt256 {
+    fn to_sql(
+        &self,
+        ty: &postgres_types::Type,
+        out: &mut ::bytes::BytesMut,
+    ) -> Result<postgres_types::IsNull, BoxError> {
+        if *ty == postgres_types::Type::BYTEA {
+            out.extend_from_slice(&self.to_be_array());
+        } else {
+            let mut buf = Vec::with_capacity(48);
+            write_numeric(self, &mut buf);
+            out.extend_from_slice(&buf);
+        }
+        Ok(postgres_types::IsNull::No)
+    }
+
+    postgres_types::accepts!(NUMERIC, BYTEA);
+
+    postgres_types::to_sql_checked!();
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> postgres_types::FromSql<'a> for UInt256 {
+    fn from_sql(ty: &postgres_types::Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        if *ty == postgres_types::Type::BYTEA {
+            Ok(UInt256::from_be_slice(raw)?)
+        } else {
+            read_numeric(raw)
+        }
+    }
+
+    postgres_types::accepts!(NUMERIC, BYTEA);
+}
+
+#[cfg(feature = "sqlx")]
+impl sqlx::Type<sqlx::Postgres> for UInt256 {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        // The OID of `NUMERIC`.
+        sqlx::postgres::PgTypeInfo::with_oid(sqlx::postgres::types::Oid(1700))
+    }
+
+    fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+        *ty == <Self as sqlx::Type<sqlx::Postgres>>::type_info()
+            || *ty == <[u8] as sqlx::Type<sqlx::Postgres>>::type_info()
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl sqlx::Encode<'_, sqlx::Postgres> for UInt256 {
+    fn encode_by_ref(&self, buf: &mut sqlx::postgres::PgArgumentBuffer) -> Result<sqlx::encode::IsNull, BoxError> {
+        write_numeric(self, buf);
+        Ok(sqlx::encode::IsNull::No)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl sqlx::Decode<'_, sqlx::Postgres> for UInt256 {
+    fn decode(value: sqlx::postgres::PgValueRef<'_>) -> Result<Self, BoxError> {
+        use sqlx::ValueRef;
+
+        if *value.type_info() == <[u8] as sqlx::Type<sqlx::Postgres>>::type_info() {
+            let bytes = <Vec<u8> as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+            return Ok(UInt256::from_be_slice(&bytes)?);
+        }
+        match value.format() {
+            sqlx::postgres::PgValueFormat::Binary => read_numeric(value.as_bytes()?),
+            sqlx::postgres::PgValueFormat::Text => parse_numeric(value.as_str()?),
+        }
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl sqlx::Type<sqlx::Sqlite> for UInt256 {
+    fn type_info() -> sqlx::sqlite::SqliteTypeInfo {
+        <str as sqlx::Type<sqlx::Sqlite>>::type_info()
+    }
+
+    fn compatible(ty: &sqlx::sqlite::SqliteTypeInfo) -> bool {
+        <str as sqlx::Type<sqlx::Sqlite>>::compatible(ty) || <[u8] as sqlx::Type<sqlx::Sqlite>>::compatible(ty)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl sqlx::Encode<'_, sqlx::Sqlite> for UInt256 {
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<sqlx::sqlite::SqliteArgumentValue<'_>>,
+    ) -> Result<sqlx::encode::IsNull, BoxError> {
+        args.push(sqlx::sqlite::SqliteArgumentValue::Text(self.to_string().into()));
+        Ok(sqlx::encode::IsNull::No)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl sqlx::Decode<'_, sqlx::Sqlite> for UInt256 {
+    fn decode(value: sqlx::sqlite::SqliteValueRef<'_>) -> Result<Self, BoxError> {
+        use sqlx::ValueRef;
+
+        if *value.type_info() == <[u8] as sqlx::Type<sqlx::Sqlite>>::type_info() {
+            let bytes = <&[u8] as sqlx::Decode<sqlx::Sqlite>>::decode(value)?;
+            return Ok(UInt256::from_be_slice(bytes)?);
+        }
+        parse_numeric(<&str as sqlx::Decode<sqlx::Sqlite>>::decode(value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::uint256::test_utils::edge_values;
+
+    fn numeric(words: &[u16]) -> Vec<u8> {
+        words.iter().flat_map(|w| w.to_be_bytes()).collect()
+    }
+
+    #[test]
+    fn test_numeric_format() {
+        let encode = |value: UInt256| {
+            let mut out = Vec::new();
+            write_numeric(&value, &mut out);
+            out
+        };
+        assert_eq!(encode(UInt256::ZERO), numeric(&[0, 0, 0, 0]));
+        assert_eq!(encode(UInt256::from(10_000)), numeric(&[1, 1, 0, 0, 1]));
+        assert_eq!(encode(UInt256::from(12_345_678)), numeric(&[2, 1, 0, 0, 1234, 5678]));
+        assert_eq!(encode(UInt256::from(999_900_000_001)), numeric(&[3, 2, 0, 0, 9999, 0, 1]));
+        // 78 decimal digits take 20 base-10000 digits.
+        assert_eq!(encode(UInt256::MAX).len(), 8 + 2 * 20);
+
+        for value in edge_values() {
+            assert_eq!(read_numeric(&encode(value)).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_numeric_read() {
+        // A display scale and trailing zero digits after the point, as in `5.0000`.
+        assert_eq!(read_numeric(&numeric(&[2, 0, 0, 4, 5, 0])).unwrap(), UInt256::from(5));
+        // Implied zero digits before the point.
+        assert_eq!(read_numeric(&numeric(&[1, 2, 0, 0, 3])).unwrap(), UInt256::from(300_000_000));
+
+        let err = |words: &[u16]| read_numeric(&numeric(words)).unwrap_err().to_string();
+        assert_eq!(err(&[2, 0, 0, 1, 1, 5000]), ConversionError::Fractional.to_string());
+        assert_eq!(err(&[1, 0xffff, 0, 4, 5]), ConversionError::Fractional.to_string());
+        assert_eq!(err(&[1, 0, NUMERIC_NEG, 0, 5]), ConversionError::Negative.to_string());
+        assert_eq!(err(&[0, 0, NUMERIC_NAN, 0]), ConversionError::NotANumber.to_string());
+        assert_eq!(err(&[0, 0, NUMERIC_PINF, 0]), ConversionError::Overflow.to_string());
+        assert_eq!(err(&[1, 20, 0, 0, 1]), ConversionError::Overflow.to_string());
+        assert_eq!(err(&[1, 0, 0, 0, 10_000]), "invalid NUMERIC digit");
+        assert_eq!(err(&[2, 0, 0, 0, 1]), "invalid NUMERIC value");
+        assert_eq!(err(&[0, 0, 0x1234, 0]), "invalid NUMERIC sign");
+        assert_eq!(read_numeric(&[0, 0, 0]).unwrap_err().to_string(), "invalid NUMERIC value");
+
+        // One above the largest value.
+        let mut words = vec![20, 19, 0, 0];
+        let (digits, _) = numeric_digits(&UInt256::MAX);
+        words.extend(digits.iter().map(|&d| d as u16));
+        *words.last_mut().unwrap() += 1;
+        assert_eq!(err(&words), ConversionError::Overflow.to_string());
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_postgres() {
+        use postgres_types::{FromSql, ToSql, Type};
+
+        for ty in [Type::NUMERIC, Type::BYTEA] {
+            for value in edge_values() {
+                let mut out = ::bytes::BytesMut::new();
+                value.to_sql_checked(&ty, &mut out).unwrap();
+                assert_eq!(UInt256::from_sql(&ty, &out).unwrap(), value);
+            }
+        }
+        let mut out = ::bytes::BytesMut::new();
+        UInt256::from(0x0400).to_sql(&Type::BYTEA, &mut out).unwrap();
+        assert_eq!(out[..], UInt256::from(0x0400).to_be_array());
+        assert_eq!(UInt256::from_sql(&Type::BYTEA, &[0x04, 0x00]).unwrap(), UInt256::from(0x0400));
+        assert!(UInt256::from_sql(&Type::BYTEA, &[0; 33]).is_err());
+
+        assert!(<UInt256 as ToSql>::accepts(&Type::NUMERIC));
+        assert!(!<UInt256 as FromSql>::accepts(&Type::INT8));
+        assert!(UInt256::ONE.to_sql_checked(&Type::TEXT, &mut out).is_err());
+    }
+
+    #[cfg(feature = "diesel")]
+    mod schema {
+        diesel::table! {
+            balances (id) {
+                id -> Int4,
+                amount -> Numeric,
+                key -> Binary,
+            }
+        }
+    }
+
+    #[cfg(feature = "diesel")]
+    #[test]
+    fn test_diesel() {
+        use diesel::prelude::*;
+        use diesel::sql_types;
+        use schema::balances;
+
+        let value = UInt256::from(12_345);
+        let insert = diesel::insert_into(balances::table)
+            .values((balances::id.eq(1), balances::amount.eq(value), balances::key.eq(&value)));
+        let sql = diesel::debug_query::<diesel::pg::Pg, _>(&insert).to_string();
+        assert!(sql.starts_with("INSERT INTO \"balances\" (\"id\", \"amount\", \"key\") VALUES ($1, $2, $3)"));
+
+        let query = balances::table.select((balances::amount, balances::key)).filter(balances::amount.gt(value));
+        let sql = diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string();
+        assert!(sql.contains("WHERE (\"balances\".\"amount\" > $1)"));
+
+        // Rows of both column types load into values.
+        fn loads<T: diesel::deserialize::FromSqlRow<(sql_types::Numeric, sql_types::Binary), diesel::pg::Pg>>() {}
+        loads::<(UInt256, UInt256)>();
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn test_parse_numeric() {
+        assert_eq!(parse_numeric("0").unwrap(), UInt256::ZERO);
+        assert_eq!(parse_numeric("12345.000").unwrap(), UInt256::from(12_345));
+        assert_eq!(parse_numeric(&UInt256::MAX.to_string()).unwrap(), UInt256::MAX);
+
+        let err = |s: &str| parse_numeric(s).unwrap_err().to_string();
+        assert_eq!(err("1.5"), ConversionError::Fractional.to_string());
+        assert_eq!(err("-1"), ConversionError::Negative.to_string());
+        assert_eq!(err("NaN"), ConversionError::NotANumber.to_string());
+        assert_eq!(err("Infinity"), ConversionError::Overflow.to_string());
+        assert_eq!(
+            err("115792089237316195423570985008687907853269984665640564039457584007913129639936"),
+            ConversionError::Overflow.to_string()
+        );
+        assert!(parse_numeric("").is_err());
+        assert!(parse_numeric("12a").is_err());
+    }
+
+    #[cfg(feature = "sqlx")]
+    #[test]
+    fn test_sqlx() {
+        use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+        use sqlx::sqlite::{SqliteArgumentValue, SqliteTypeInfo};
+        use sqlx::encode::IsNull;
+        use sqlx::{Encode, Postgres, Sqlite, Type};
+
+        for value in edge_values() {
+            let mut buf = PgArgumentBuffer::default();
+            let is_null = <UInt256 as Encode<Postgres>>::encode_by_ref(&value, &mut buf).unwrap();
+            assert!(matches!(is_null, IsNull::No));
+            assert_eq!(read_numeric(&buf).unwrap(), value);
+
+            let mut args = Vec::new();
+            let is_null = <UInt256 as Encode<Sqlite>>::encode_by_ref(&value, &mut args).unwrap();
+            assert!(matches!(is_null, IsNull::No));
+            match &args[..] {
+                [SqliteArgumentValue::Text(text)] => assert_eq!(parse_numeric(text).unwrap(), value),
+                _ => panic!("expected a text argument"),
+            }
+        }
+
+        let pg: fn(&PgTypeInfo) -> bool = <UInt256 as Type<Postgres>>::compatible;
+        assert!(pg(&<UInt256 as Type<Postgres>>::type_info()));
+        assert!(pg(&<Vec<u8> as Type<Postgres>>::type_info()));
+        assert!(!pg(&<i64 as Type<Postgres>>::type_info()));
+        assert!(!pg(&<str as Type<Postgres>>::type_info()));
+
+        let sqlite: fn(&SqliteTypeInfo) -> bool = <UInt256 as Type<Sqlite>>::compatible;
+        assert!(sqlite(&<str as Type<Sqlite>>::type_info()));
+        assert!(sqlite(&<Vec<u8> as Type<Sqlite>>::type_info()));
+        assert!(!sqlite(&<f64 as Type<Sqlite>>::type_info()));
+    }
+}